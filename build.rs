@@ -24,9 +24,13 @@ fn main() {
         }
         None => PROD_API_URL,
     };
+    // Skipping certificate verification is only useful against a local API server.
+    let insecure_allowed = api_server == LOCAL_API_URL;
     fs::write(
         dest_path,
-        format!("pub const API_BASE_URL: &str = \"{api_server}\";"),
+        format!(
+            "pub const API_BASE_URL: &str = \"{api_server}\";\npub const INSECURE_ALLOWED: bool = {insecure_allowed};"
+        ),
     )
     .unwrap();
     println!("cargo:rerun-if-changed=build.rs");
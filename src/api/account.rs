@@ -0,0 +1,115 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{deserialize_rows, Api, V4};
+use crate::authentication::AuthenticationError;
+use crate::commands;
+use crate::commands::CommandError;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Team {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+/// Team and user a token belongs to. Either may be unknown, e.g. for team-wide tokens.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct TokenOwner {
+    pub team: Option<Team>,
+    pub user: Option<User>,
+}
+
+impl Api {
+    /// Looks up who the token belongs to.
+    ///
+    /// `v4/teams` and `v4/users/me` aren't served by every API deployment, and team-wide
+    /// tokens have no user, so a 404 leaves the field unknown. Any other failure, such as
+    /// a revoked token or a network error, is returned.
+    pub fn get_token_owner(&self) -> Result<TokenOwner, CommandError> {
+        Ok(TokenOwner {
+            team: self.first_row_if_available(&format!("{V4}/teams?select=id,name"))?,
+            user: self.first_row_if_available(&format!("{V4}/users/me?select=id,email"))?,
+        })
+    }
+
+    fn first_row_if_available<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+    ) -> Result<Option<T>, CommandError> {
+        match commands::get(&self.authentication, endpoint) {
+            Ok(value) => Ok(deserialize_rows::<T>(value)?.into_iter().next()),
+            Err(CommandError::WrongResponseStatus(404)) => Ok(None),
+            Err(CommandError::WrongResponseStatus(401 | 403)) => Err(CommandError::Authentication(
+                AuthenticationError::WrongCredentials,
+            )),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::Method::GET;
+    use httpmock::MockServer;
+    use serde_json::json;
+
+    use super::*;
+    use crate::authentication::{Authentication, Config};
+
+    #[test]
+    fn test_get_token_owner_should_tolerate_missing_user() {
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/teams");
+            then.status(200)
+                .json_body(json!([{"id": "team-1", "name": "Acme"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/users/me");
+            then.status(404);
+        });
+
+        let api = Api::new(Authentication::new_with_config(
+            Config::new(mock_server.base_url()),
+            "token",
+        ));
+        assert_eq!(
+            api.get_token_owner().unwrap(),
+            TokenOwner {
+                team: Some(Team {
+                    id: "team-1".to_owned(),
+                    name: Some("Acme".to_owned()),
+                }),
+                user: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_token_owner_when_token_is_revoked_should_return_error() {
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/teams");
+            then.status(401);
+        });
+
+        let api = Api::new(Authentication::new_with_config(
+            Config::new(mock_server.base_url()),
+            "token",
+        ));
+        assert!(matches!(
+            api.get_token_owner(),
+            Err(CommandError::Authentication(
+                AuthenticationError::WrongCredentials
+            ))
+        ));
+    }
+}
@@ -1,13 +1,61 @@
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+
+use log::debug;
+use reqwest::blocking::multipart::Form;
+use reqwest::{StatusCode, Url};
 use serde::{Deserialize, Serialize};
 
-use crate::api::Api;
+use crate::api::{deserialize_rows, eq_filter, Api, V4};
 use crate::commands;
 use crate::commands::CommandError;
 
+/// Whether `url` is served by the same scheme, host and port as `api_url`.
+fn is_same_origin(api_url: &Url, url: &Url) -> bool {
+    api_url.scheme() == url.scheme()
+        && api_url.host_str() == url.host_str()
+        && api_url.port_or_known_default() == url.port_or_known_default()
+}
+
+/// An asset as returned by the `v4/assets` endpoint.
+///
+/// Fields the CLI doesn't use are kept in `extra`, so JSON output contains everything the API sent.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Asset {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(rename = "type", default)]
+    pub asset_type: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub js_injection: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct AssetSignature {
     pub(crate) signature: String,
 }
+/// A file of an Edge App revision and where to download it from.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EdgeAppFileAsset {
+    pub signature: String,
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+/// Optional upload features the server supports.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UploadCapabilities {
+    #[serde(default)]
+    pub delta_uploads: bool,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct AssetProcessingStatus {
     pub(crate) status: String,
@@ -16,6 +64,76 @@ pub struct AssetProcessingStatus {
 }
 
 impl Api {
+    /// Lists the assets of the team, without the files uploaded by Edge Apps.
+    pub fn list_assets(&self) -> Result<Vec<Asset>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!("{V4}/assets?type=neq.edge-app-file"),
+        )?)?)
+    }
+
+    pub fn get_asset(&self, id: &str) -> Result<Vec<Asset>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!("{V4}/assets?{}", eq_filter("id", id)),
+        )?)?)
+    }
+
+    pub fn get_asset_js_injection(&self, id: &str) -> Result<Vec<Asset>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!("{V4}/assets?{}&select=js_injection", eq_filter("id", id)),
+        )?)?)
+    }
+
+    /// Creates a web asset. The API fetches the content from the `source_url` of the payload.
+    pub fn create_web_asset(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<Vec<Asset>, CommandError> {
+        Ok(deserialize_rows(commands::post(
+            &self.authentication,
+            &format!("{V4}/assets"),
+            payload,
+        )?)?)
+    }
+
+    /// Uploads a file asset. The timeout is equal to the server timeout.
+    pub fn upload_asset(&self, form: Form) -> Result<Vec<Asset>, CommandError> {
+        let response = self
+            .authentication
+            .blocking_client()?
+            .post(format!("{}/{V4}/assets", &self.authentication.config.url))
+            .multipart(form)
+            .header("Prefer", "return=representation")
+            .timeout(Duration::from_secs(3600))
+            .send()?;
+
+        if response.status() != StatusCode::CREATED {
+            return Err(CommandError::WrongResponseStatus(
+                response.status().as_u16(),
+            ));
+        }
+
+        Ok(deserialize_rows(serde_json::from_str(&response.text()?)?)?)
+    }
+
+    pub fn update_asset(&self, id: &str, payload: &serde_json::Value) -> Result<(), CommandError> {
+        commands::patch(
+            &self.authentication,
+            &format!("{V4}/assets?{}", eq_filter("id", id)),
+            payload,
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_asset(&self, id: &str) -> Result<(), CommandError> {
+        commands::delete(
+            &self.authentication,
+            &format!("{V4}/assets?{}", eq_filter("id", id)),
+        )
+    }
+
     pub fn get_version_asset_signatures(
         &self,
         app_id: &str,
@@ -24,11 +142,65 @@ impl Api {
         Ok(serde_json::from_value(commands::get(
             &self.authentication,
             &format!(
-                "v4/assets?select=signature&app_id=eq.{app_id}&app_revision=eq.{revision}&type=eq.edge-app-file"
+                "{V4}/assets?select=signature&app_id=eq.{app_id}&app_revision=eq.{revision}&type=eq.edge-app-file"
+            ),
+        )?)?)
+    }
+
+    pub fn get_version_asset_files(
+        &self,
+        app_id: &str,
+        revision: u32,
+    ) -> Result<Vec<EdgeAppFileAsset>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!(
+                "{V4}/assets?select=signature,uri&app_id=eq.{app_id}&app_revision=eq.{revision}&type=eq.edge-app-file"
             ),
         )?)?)
     }
 
+    /// Streams the content behind an asset URI into `target`. The API token is only sent
+    /// when the URI points at the API itself.
+    pub fn download(&self, uri: &str, target: &Path) -> Result<(), CommandError> {
+        let parse = |url: &str| {
+            Url::parse(url).map_err(|e| CommandError::InvalidUrl(format!("{url}: {e}")))
+        };
+        let api_url = parse(&self.authentication.config.url)?;
+        let url = if uri.starts_with('/') {
+            parse(&format!(
+                "{}{uri}",
+                self.authentication.config.url.trim_end_matches('/')
+            ))?
+        } else {
+            parse(uri)?
+        };
+        let client = if is_same_origin(&api_url, &url) {
+            self.authentication.blocking_client()?
+        } else {
+            self.authentication.download_client()?
+        };
+
+        let mut response = client.get(url).send()?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(CommandError::WrongResponseStatus(status.as_u16()));
+        }
+        let mut file = File::create(target)?;
+        response.copy_to(&mut file)?;
+        Ok(())
+    }
+
+    /// Servers without the capabilities endpoint support none of the optional features.
+    pub fn get_upload_capabilities(&self) -> UploadCapabilities {
+        commands::get(&self.authentication, &format!("{V4}/assets/capabilities"))
+            .and_then(|response| Ok(serde_json::from_value(response)?))
+            .unwrap_or_else(|e| {
+                debug!("Upload capabilities unavailable: {e}");
+                UploadCapabilities::default()
+            })
+    }
+
     pub fn get_processing_statuses(
         &self,
         app_id: &str,
@@ -37,7 +209,7 @@ impl Api {
         let response = commands::get(
             &self.authentication,
             &format!(
-                "v4/assets?select=status,processing_error,title&app_id=eq.{app_id}&app_revision=eq.{revision}&status=neq.finished"
+                "{V4}/assets?select=status,processing_error,title&app_id=eq.{app_id}&app_revision=eq.{revision}&status=neq.finished"
             ),
         )?;
 
@@ -46,3 +218,33 @@ impl Api {
         )?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same_origin(url: &str) -> bool {
+        is_same_origin(
+            &Url::parse("https://api.screenlyapp.com").unwrap(),
+            &Url::parse(url).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_is_same_origin_should_match_api_host_only() {
+        assert!(same_origin("https://api.screenlyapp.com/files/index.html"));
+        assert!(same_origin(
+            "https://api.screenlyapp.com:443/files/index.html"
+        ));
+
+        assert!(!same_origin(
+            "https://api.screenlyapp.com.evil.com/files/index.html"
+        ));
+        assert!(!same_origin("https://api.screenlyapp.com@evil.com/"));
+        assert!(!same_origin("http://api.screenlyapp.com/files/index.html"));
+        assert!(!same_origin(
+            "https://api.screenlyapp.com:8443/files/index.html"
+        ));
+        assert!(!same_origin("https://cdn.screenlyapp.com/files/index.html"));
+    }
+}
@@ -2,7 +2,7 @@ use log::debug;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use crate::api::Api;
+use crate::api::{Api, V4};
 use crate::commands;
 use crate::commands::CommandError;
 
@@ -28,7 +28,7 @@ impl Api {
 
         let response = commands::post(
             &self.authentication,
-            "v4/edge-apps?select=id,name",
+            &format!("{V4}/edge-apps?select=id,name"),
             &json!({ "name": name }),
         )?;
 
@@ -45,14 +45,14 @@ impl Api {
     pub fn list_apps(&self) -> Result<EdgeApps, CommandError> {
         Ok(EdgeApps::new(commands::get(
             &self.authentication,
-            "v4/edge-apps?select=id,name&deleted=eq.false",
+            &format!("{V4}/edge-apps?select=id,name&deleted=eq.false"),
         )?))
     }
 
     pub fn delete_app(&self, app_id: &str) -> Result<(), CommandError> {
         commands::delete(
             &self.authentication,
-            &format!("v4/edge-apps?id=eq.{app_id}"),
+            &format!("{V4}/edge-apps?id=eq.{app_id}"),
         )?;
         Ok(())
     }
@@ -60,7 +60,7 @@ impl Api {
     pub fn update_app(&self, app_id: &str, name: &str) -> Result<(), CommandError> {
         commands::patch(
             &self.authentication,
-            &format!("v4/edge-apps?select=name&id=eq.{app_id}"),
+            &format!("{V4}/edge-apps?select=name&id=eq.{app_id}"),
             &json!({ "name": name }),
         )?;
         Ok(())
@@ -69,7 +69,7 @@ impl Api {
     pub fn get_app(&self, app_id: &str) -> Result<EdgeApp, CommandError> {
         let response = commands::get(
             &self.authentication,
-            &format!("v4/edge-apps?select=name&id=eq.{app_id}"),
+            &format!("{V4}/edge-apps?select=name&id=eq.{app_id}"),
         )?;
 
         let apps = serde_json::from_value::<Vec<EdgeApp>>(response)?;
@@ -83,7 +83,11 @@ impl Api {
     }
 
     pub fn copy_assets(&self, payload: Value) -> Result<Vec<String>, CommandError> {
-        let response = commands::post(&self.authentication, "v4/edge-apps/copy-assets", &payload)?;
+        let response = commands::post(
+            &self.authentication,
+            &format!("{V4}/edge-apps/copy-assets"),
+            &payload,
+        )?;
         let copied_assets = serde_json::from_value::<Vec<String>>(response)?;
 
         debug!("Copied assets: {copied_assets:?}");
@@ -1,11 +1,30 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::api::Api;
+use crate::api::{Api, V4};
 use crate::commands;
 use crate::commands::CommandError;
 
+/// A release channel of an Edge App and the revision it points to.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Channel {
+    pub channel: String,
+    #[serde(default)]
+    pub app_revision: Option<u32>,
+}
+
 impl Api {
+    pub fn list_channels(&self, app_id: &str) -> Result<Vec<Channel>, CommandError> {
+        let response = commands::get(
+            &self.authentication,
+            &format!(
+                "{V4}/edge-apps/channels?select=channel,app_revision&app_id=eq.{app_id}&order=channel.asc"
+            ),
+        )?;
+
+        Ok(serde_json::from_value(response)?)
+    }
+
     pub fn update_channel(
         &self,
         channel: &str,
@@ -15,7 +34,7 @@ impl Api {
         let response = commands::patch(
             &self.authentication,
             &format!(
-                "v4/edge-apps/channels?select=channel,app_revision&channel=eq.{channel}&app_id=eq.{app_id}"
+                "{V4}/edge-apps/channels?select=channel,app_revision&channel=eq.{channel}&app_id=eq.{app_id}"
             ),
             &json!(
             {
@@ -23,17 +42,11 @@ impl Api {
             }),
         )?;
 
-        #[derive(Clone, Debug, Default, PartialEq, Deserialize)]
-        struct Channel {
-            app_revision: u32,
-            channel: String,
-        }
-
         let channels = serde_json::from_value::<Vec<Channel>>(response)?;
         if channels.is_empty() {
             return Err(CommandError::MissingField);
         }
-        if channels[0].channel != channel || channels[0].app_revision != revision {
+        if channels[0].channel != channel || channels[0].app_revision != Some(revision) {
             return Err(CommandError::MissingField);
         }
 
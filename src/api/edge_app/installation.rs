@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::api::Api;
+use crate::api::{Api, V4, V4_1};
 use crate::commands;
 use crate::commands::CommandError;
 
@@ -20,7 +20,7 @@ impl Api {
     pub fn get_instance_name(&self, installation_id: &str) -> Result<String, CommandError> {
         let response = commands::get(
             &self.authentication,
-            &format!("v4.1/edge-apps/installations?select=name&id=eq.{installation_id}"),
+            &format!("{V4_1}/edge-apps/installations?select=name&id=eq.{installation_id}"),
         )?;
 
         #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
@@ -39,7 +39,7 @@ impl Api {
     pub fn list_installations(&self, app_id: &str) -> Result<EdgeAppInstances, CommandError> {
         let response = commands::get(
             &self.authentication,
-            &format!("v4/edge-apps/installations?select=id,name&app_id=eq.{app_id}"),
+            &format!("{V4}/edge-apps/installations?select=id,name&app_id=eq.{app_id}"),
         )?;
 
         let instances = EdgeAppInstances::new(response);
@@ -50,7 +50,7 @@ impl Api {
     pub fn delete_installation(&self, installation_id: &str) -> Result<(), CommandError> {
         commands::delete(
             &self.authentication,
-            &format!("v4.1/edge-apps/installations?id=eq.{installation_id}"),
+            &format!("{V4_1}/edge-apps/installations?id=eq.{installation_id}"),
         )?;
         Ok(())
     }
@@ -65,7 +65,7 @@ impl Api {
         });
         commands::patch(
             &self.authentication,
-            &format!("v4.1/edge-apps/installations?id=eq.{installation_id}"),
+            &format!("{V4_1}/edge-apps/installations?id=eq.{installation_id}"),
             &payload,
         )?;
         Ok(())
@@ -79,7 +79,7 @@ impl Api {
 
         let response = commands::post(
             &self.authentication,
-            "v4.1/edge-apps/installations?select=id",
+            &format!("{V4_1}/edge-apps/installations?select=id"),
             &payload,
         )?;
 
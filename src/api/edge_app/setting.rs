@@ -8,7 +8,7 @@ use serde_json::{json, Value};
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString};
 
-use crate::api::Api;
+use crate::api::{Api, V4_1};
 use crate::commands;
 use crate::commands::{CommandError, EdgeAppSettings};
 
@@ -245,7 +245,7 @@ impl Api {
         Ok(deserialize_settings_from_array(commands::get(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/settings?select=name,type,default_value,optional,title,help_text&app_id=eq.{app_id}&order=name.asc",
+                "{V4_1}/edge-apps/settings?select=name,type,default_value,optional,title,help_text&app_id=eq.{app_id}&order=name.asc",
             ),
         )?)?)
     }
@@ -254,7 +254,7 @@ impl Api {
         let response = commands::get(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/settings?select=is_global&app_id=eq.{app_id}&name=eq.{setting_key}",
+                "{V4_1}/edge-apps/settings?select=is_global&app_id=eq.{app_id}&name=eq.{setting_key}",
             ),
         )?;
 
@@ -278,7 +278,7 @@ impl Api {
         // TODO: test values are returned properly when there are several installations. Most likely need to feed installation_id to the request.
         // installation_id=is.null or installation_id=eq.smth
         let app_settings: Vec<HashMap<String, serde_json::Value>> = serde_json::from_value(commands::get(&self.authentication,
-            &format!("v4.1/edge-apps/settings?select=name,type,default_value,optional,title,help_text,edge_app_setting_values(value)&app_id=eq.{app_id}&order=name.asc",
+            &format!("{V4_1}/edge-apps/settings?select=name,type,default_value,optional,title,help_text,edge_app_setting_values(value)&app_id=eq.{app_id}&order=name.asc",
             ))?)?;
 
         Ok(EdgeAppSettings::new(serde_json::to_value(app_settings)?))
@@ -292,7 +292,7 @@ impl Api {
         let response = commands::get(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/settings?select=name,type,edge_app_setting_values(value)&app_id=eq.{app_id}&edge_app_setting_values.app_id=eq.{app_id}&name=eq.{setting_key}"
+                "{V4_1}/edge-apps/settings?select=name,type,edge_app_setting_values(value)&app_id=eq.{app_id}&edge_app_setting_values.app_id=eq.{app_id}&name=eq.{setting_key}"
             ),
         )?;
         let settings = serde_json::from_value::<Vec<SettingValue>>(response)?;
@@ -311,7 +311,7 @@ impl Api {
         let response = commands::get(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/settings?select=name,type,edge_app_setting_values(value)&edge_app_setting_values.installation_id=eq.{installation_id}&name=eq.{setting_key}&app_id=eq.{app_id}"
+                "{V4_1}/edge-apps/settings?select=name,type,edge_app_setting_values(value)&edge_app_setting_values.installation_id=eq.{installation_id}&name=eq.{setting_key}&app_id=eq.{app_id}"
             ),
         )?;
 
@@ -330,7 +330,11 @@ impl Api {
         payload.insert("name".to_owned(), json!(setting.name));
 
         debug!("Creating setting: {:?}", &payload);
-        commands::post(&self.authentication, "v4.1/edge-apps/settings", &payload)
+        commands::post(
+            &self.authentication,
+            &format!("{V4_1}/edge-apps/settings"),
+            &payload,
+        )
     }
 
     pub fn update_setting(&self, app_id: &str, setting: &Setting) -> Result<Value, CommandError> {
@@ -343,7 +347,7 @@ impl Api {
         commands::patch(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/settings?app_id=eq.{id}&name=eq.{name}",
+                "{V4_1}/edge-apps/settings?app_id=eq.{id}&name=eq.{name}",
                 id = app_id,
                 name = setting.name
             ),
@@ -355,7 +359,7 @@ impl Api {
         commands::delete(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/settings?app_id=eq.{id}&name=eq.{name}",
+                "{V4_1}/edge-apps/settings?app_id=eq.{id}&name=eq.{name}",
                 id = app_id,
                 name = setting.name
             ),
@@ -378,7 +382,7 @@ impl Api {
         );
         commands::post(
             &self.authentication,
-            "v4.1/edge-apps/settings/values",
+            &format!("{V4_1}/edge-apps/settings/values"),
             &settings_values_payload,
         )?;
 
@@ -400,7 +404,7 @@ impl Api {
         );
         commands::post(
             &self.authentication,
-            "v4.1/edge-apps/settings/values",
+            &format!("{V4_1}/edge-apps/settings/values"),
             &settings_values_payload,
         )?;
 
@@ -416,7 +420,7 @@ impl Api {
         commands::patch(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/settings/values?app_id=eq.{app_id}&name=eq.{setting_key}&installation_id=is.null"
+                "{V4_1}/edge-apps/settings/values?app_id=eq.{app_id}&name=eq.{setting_key}&installation_id=is.null"
             ),
             &json!({
                 "value": setting_value,
@@ -435,7 +439,7 @@ impl Api {
         commands::patch(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/settings/values?installation_id=eq.{installation_id}&name=eq.{setting_key}"
+                "{V4_1}/edge-apps/settings/values?installation_id=eq.{installation_id}&name=eq.{setting_key}"
             ),
             &json!({
                 "value": setting_value,
@@ -460,7 +464,7 @@ impl Api {
         );
         commands::post(
             &self.authentication,
-            "v4.1/edge-apps/secrets/values",
+            &format!("{V4_1}/edge-apps/secrets/values"),
             &payload,
         )?;
 
@@ -482,7 +486,7 @@ impl Api {
         );
         commands::post(
             &self.authentication,
-            "v4.1/edge-apps/secrets/values",
+            &format!("{V4_1}/edge-apps/secrets/values"),
             &payload,
         )?;
 
@@ -1,19 +1,66 @@
 use std::collections::HashMap;
 
 use log::debug;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use crate::api::Api;
+use crate::api::{Api, V4};
 use crate::commands;
 use crate::commands::CommandError;
 
+/// A revision of an Edge App as listed by `edge-app version list`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Version {
+    pub revision: u32,
+    #[serde(default)]
+    pub user_version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub published: bool,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Channels pointing to this revision, filled in from the channel list.
+    #[serde(default, skip_deserializing)]
+    pub channels: Vec<String>,
+    /// Only requested when showing a single revision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_tree: Option<HashMap<String, String>>,
+}
+
 impl Api {
+    pub fn list_versions(&self, app_id: &str) -> Result<Vec<Version>, CommandError> {
+        let response = commands::get(
+            &self.authentication,
+            &format!(
+                "{V4}/edge-apps/versions?select=revision,user_version,description,published,created_at&app_id=eq.{app_id}&order=revision.desc"
+            ),
+        )?;
+
+        Ok(serde_json::from_value(response)?)
+    }
+
+    pub fn get_version(
+        &self,
+        app_id: &str,
+        revision: u32,
+    ) -> Result<Option<Version>, CommandError> {
+        let response = commands::get(
+            &self.authentication,
+            &format!(
+                "{V4}/edge-apps/versions?select=revision,user_version,description,published,created_at,file_tree&app_id=eq.{app_id}&revision=eq.{revision}"
+            ),
+        )?;
+
+        let versions = serde_json::from_value::<Vec<Version>>(response)?;
+        Ok(versions.into_iter().next())
+    }
+
     pub fn version_exists(&self, app_id: &str, revision: u32) -> Result<bool, CommandError> {
         let get_response = commands::get(
             &self.authentication,
             &format!(
-                "v4/edge-apps/versions?select=revision&app_id=eq.{app_id}&revision=eq.{revision}"
+                "{V4}/edge-apps/versions?select=revision&app_id=eq.{app_id}&revision=eq.{revision}"
             ),
         )?;
         let version =
@@ -29,7 +76,7 @@ impl Api {
     pub fn create_version(&self, json: HashMap<&str, Value>) -> Result<u32, CommandError> {
         let response = commands::post(
             &self.authentication,
-            "v4/edge-apps/versions?select=revision",
+            &format!("{V4}/edge-apps/versions?select=revision"),
             &json,
         )?;
         if let Some(arr) = response.as_array() {
@@ -52,7 +99,7 @@ impl Api {
         let response = commands::get(
             &self.authentication,
             &format!(
-                "v4/edge-apps/versions?select=file_tree&app_id=eq.{app_id}&revision=eq.{revision}"
+                "{V4}/edge-apps/versions?select=file_tree&app_id=eq.{app_id}&revision=eq.{revision}"
             ),
         )?;
 
@@ -71,7 +118,7 @@ impl Api {
     pub fn publish_version(&self, app_id: &str, revision: u32) -> Result<(), CommandError> {
         commands::patch(
             &self.authentication,
-            &format!("v4/edge-apps/versions?app_id=eq.{app_id}&revision=eq.{revision}"),
+            &format!("{V4}/edge-apps/versions?app_id=eq.{app_id}&revision=eq.{revision}"),
             &json!({"published": true}),
         )?;
         Ok(())
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::api::{collect_field, eq_filter, Api, V4};
+use crate::commands;
+use crate::commands::CommandError;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreenLabel {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreenPlaylist {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub is_enabled: bool,
+}
+
+impl Api {
+    pub fn get_labels(&self, ids: &[String]) -> Result<Vec<ScreenLabel>, CommandError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!("{V4}/labels?select=id,name&id=in.({})", ids.join(",")),
+        )?)?)
+    }
+
+    pub fn find_label_ids_by_name(&self, name: &str) -> Result<Vec<String>, CommandError> {
+        let response = commands::get(
+            &self.authentication,
+            &format!("{V4}/labels?select=id&{}", eq_filter("name", name)),
+        )?;
+        Ok(collect_field(&response, "id"))
+    }
+
+    pub fn create_label(&self, name: &str) -> Result<String, CommandError> {
+        let response = commands::post(
+            &self.authentication,
+            &format!("{V4}/labels"),
+            &json!({ "name": name }),
+        )?;
+        response[0]["id"]
+            .as_str()
+            .map(str::to_owned)
+            .ok_or(CommandError::MissingField)
+    }
+
+    pub fn get_screen_label_ids(&self, screen_id: &str) -> Result<Vec<String>, CommandError> {
+        let response = commands::get(
+            &self.authentication,
+            &format!("{V4}/labels/screens?select=label_id&screen_id=eq.{screen_id}"),
+        )?;
+        Ok(collect_field(&response, "label_id"))
+    }
+
+    pub fn get_labelled_screen_ids(
+        &self,
+        label_ids: &[String],
+    ) -> Result<Vec<String>, CommandError> {
+        if label_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = commands::get(
+            &self.authentication,
+            &format!(
+                "{V4}/labels/screens?select=screen_id&label_id=in.({})",
+                label_ids.join(",")
+            ),
+        )?;
+        Ok(collect_field(&response, "screen_id"))
+    }
+
+    pub fn attach_label(&self, label_id: &str, screen_id: &str) -> Result<(), CommandError> {
+        commands::post(
+            &self.authentication,
+            &format!("{V4}/labels/screens"),
+            &json!({ "label_id": label_id, "screen_id": screen_id }),
+        )?;
+        Ok(())
+    }
+
+    /// Returns the playlists targeting any of the given labels.
+    pub fn get_label_playlists(
+        &self,
+        label_ids: &[String],
+    ) -> Result<Vec<ScreenPlaylist>, CommandError> {
+        if label_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let playlist_ids = collect_field(
+            &commands::get(
+                &self.authentication,
+                &format!(
+                    "{V4}/labels/playlists?select=playlist_id&label_id=in.({})",
+                    label_ids.join(",")
+                ),
+            )?,
+            "playlist_id",
+        );
+        if playlist_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!(
+                "{V4}/playlists?select=id,title,is_enabled&id=in.({})",
+                playlist_ids.join(",")
+            ),
+        )?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::Method::GET;
+    use httpmock::MockServer;
+
+    use super::*;
+    use crate::authentication::{Authentication, Config};
+
+    #[test]
+    fn test_find_label_ids_by_name_should_encode_name() {
+        let mock_server = MockServer::start();
+        let labels_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels")
+                .query_param("select", "id")
+                .query_param("name", "eq.Lobby & Hall #2, (east) 100%");
+            then.status(200)
+                .json_body(json!([{"id": "label-1"}, {"id": "label-2"}]));
+        });
+        let api = Api::new(Authentication::new_with_config(
+            Config::new(mock_server.base_url()),
+            "token",
+        ));
+
+        let ids = api
+            .find_label_ids_by_name("Lobby & Hall #2, (east) 100%")
+            .unwrap();
+
+        labels_mock.assert();
+        assert_eq!(ids, vec!["label-1".to_owned(), "label-2".to_owned()]);
+    }
+}
@@ -1,9 +1,62 @@
 use crate::authentication::Authentication;
 
+pub mod account;
 pub mod asset;
 pub mod edge_app;
+pub mod label;
+pub mod playlist;
+pub mod screen;
 pub mod version;
 
+/// API versions used by the client. Endpoints are always built from these constants,
+/// so moving a resource to a newer API version is a change in a single place.
+pub(crate) const V4: &str = "v4";
+pub(crate) const V4_1: &str = "v4.1";
+
+/// Typed client for the Screenly REST API.
+///
+/// Endpoint methods are grouped by resource in the submodules of [`crate::api`].
 pub struct Api {
     pub authentication: Authentication,
 }
+
+impl Api {
+    pub fn new(authentication: Authentication) -> Self {
+        Self { authentication }
+    }
+}
+
+/// Builds a PostgREST `column=eq.value` filter with the value percent-encoded, so values
+/// containing spaces, `&`, `#`, `%`, `,` or parentheses are matched literally.
+pub(crate) fn eq_filter(column: &str, value: &str) -> String {
+    let mut url = reqwest::Url::parse("http://localhost/").expect("static URL is valid");
+    url.query_pairs_mut()
+        .append_pair(column, &format!("eq.{value}"));
+    url.query().unwrap_or_default().to_owned()
+}
+
+/// Deserializes a response that is either a list of rows or a single row.
+///
+/// Some endpoints return a bare object for single inserts, others return nothing at all.
+pub(crate) fn deserialize_rows<T: serde::de::DeserializeOwned>(
+    value: serde_json::Value,
+) -> Result<Vec<T>, serde_json::Error> {
+    match value {
+        serde_json::Value::Null => Ok(Vec::new()),
+        serde_json::Value::Object(_) => Ok(vec![serde_json::from_value(value)?]),
+        _ => serde_json::from_value(value),
+    }
+}
+
+/// Collects the sorted, deduplicated string values of `field` from an array of rows.
+pub(crate) fn collect_field(value: &serde_json::Value, field: &str) -> Vec<String> {
+    let mut values: Vec<String> = value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|row| row[field].as_str().map(str::to_owned))
+        .collect();
+    values.sort();
+    values.dedup();
+    values
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::api::{deserialize_rows, eq_filter, Api, V4};
+use crate::commands;
+use crate::commands::CommandError;
+
+/// A playlist as returned by the `v4/playlists` endpoint.
+///
+/// Fields the CLI doesn't use are kept in `extra`, so JSON output contains everything the API sent.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Playlist {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub predicate: Option<String>,
+    #[serde(default)]
+    pub is_enabled: Option<bool>,
+    #[serde(default)]
+    pub priority: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// An item of a playlist as returned by the `v4/playlist-items` endpoint.
+///
+/// The same structure is used for the items of a playlist file, which only carry
+/// `asset_id` and `duration`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlaylistItem {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub playlist_id: Option<String>,
+    #[serde(default)]
+    pub asset_id: String,
+    /// Seconds. The API stores fractional durations, so they are kept as is.
+    #[serde(default)]
+    pub duration: f64,
+    #[serde(default)]
+    pub position: Option<u64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Api {
+    pub fn list_playlists(&self) -> Result<Vec<Playlist>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!("{V4}/playlists"),
+        )?)?)
+    }
+
+    /// Creates an enabled, non-priority playlist with transitions.
+    pub fn create_playlist(
+        &self,
+        title: &str,
+        predicate: &str,
+    ) -> Result<Vec<Playlist>, CommandError> {
+        Ok(deserialize_rows(commands::post(
+            &self.authentication,
+            &format!("{V4}/playlists"),
+            &json!({
+                "title": title,
+                "predicate": predicate,
+                "priority": false,
+                "is_enabled": true,
+                "transitions": true
+            }),
+        )?)?)
+    }
+
+    pub fn get_playlist_predicate(&self, id: &str) -> Result<Option<String>, CommandError> {
+        let playlists: Vec<Playlist> = deserialize_rows(commands::get(
+            &self.authentication,
+            &format!("{V4}/playlists?{}&select=predicate", eq_filter("id", id)),
+        )?)?;
+        Ok(playlists
+            .into_iter()
+            .next()
+            .and_then(|playlist| playlist.predicate))
+    }
+
+    pub fn update_playlist(
+        &self,
+        id: &str,
+        payload: &serde_json::Value,
+    ) -> Result<(), CommandError> {
+        commands::patch(
+            &self.authentication,
+            &format!("{V4}/playlists?{}", eq_filter("id", id)),
+            payload,
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_playlist(&self, id: &str) -> Result<(), CommandError> {
+        commands::delete(
+            &self.authentication,
+            &format!("{V4}/playlists?{}", eq_filter("id", id)),
+        )
+    }
+
+    /// Returns the asset and duration of every item of a playlist, in playing order.
+    pub fn get_playlist_items(&self, playlist_id: &str) -> Result<Vec<PlaylistItem>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!(
+                "{V4}/playlist-items?select=asset_id,duration&{}&order=position.asc",
+                eq_filter("playlist_id", playlist_id)
+            ),
+        )?)?)
+    }
+
+    /// Returns the item with the highest position, if the playlist has any.
+    pub fn get_last_playlist_item(
+        &self,
+        playlist_id: &str,
+    ) -> Result<Vec<PlaylistItem>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!(
+                "{V4}/playlist-items?select=position,asset_id,duration&{}&order=position.desc&limit=1",
+                eq_filter("playlist_id", playlist_id)
+            ),
+        )?)?)
+    }
+
+    pub fn create_playlist_items(
+        &self,
+        items: &serde_json::Value,
+    ) -> Result<Vec<PlaylistItem>, CommandError> {
+        Ok(deserialize_rows(commands::post(
+            &self.authentication,
+            &format!("{V4}/playlist-items"),
+            items,
+        )?)?)
+    }
+
+    pub fn delete_playlist_items(&self, playlist_id: &str) -> Result<(), CommandError> {
+        commands::delete(
+            &self.authentication,
+            &format!(
+                "{V4}/playlist-items?{}",
+                eq_filter("playlist_id", playlist_id)
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playlist_item_should_round_trip_fractional_duration_and_nulls() {
+        let value = json!({
+            "id": "item-1",
+            "playlist_id": null,
+            "asset_id": "asset-1",
+            "duration": 7.5,
+            "position": 100000,
+            "conditions": {},
+        });
+
+        let item: PlaylistItem = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(item.duration, 7.5);
+        assert_eq!(serde_json::to_value(&item).unwrap(), value);
+    }
+}
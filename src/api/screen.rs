@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::api::{collect_field, deserialize_rows, eq_filter, Api, V4};
+use crate::commands;
+use crate::commands::CommandError;
+
+/// A screen as returned by the `v4/screens` endpoint.
+///
+/// Fields the CLI doesn't use are kept in `extra` and `null`s are kept as they are, so JSON
+/// output contains everything the API sent.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Screen {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub is_enabled: Option<bool>,
+    #[serde(default)]
+    pub in_sync: Option<bool>,
+    #[serde(default)]
+    pub last_ping: Option<String>,
+    /// Seconds since boot. The API reports it either as a number or as a numeric string.
+    #[serde(default)]
+    pub uptime: Option<serde_json::Value>,
+    #[serde(default)]
+    pub priority: Option<bool>,
+    #[serde(default)]
+    pub hardware_version: Option<String>,
+    #[serde(default)]
+    pub software_version: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Screen {
+    /// Time elapsed since the last ping, if the screen has ever pinged.
+    pub fn last_ping_age(&self) -> Option<Duration> {
+        let last_ping = chrono::DateTime::parse_from_rfc3339(self.last_ping.as_deref()?).ok()?;
+        (chrono::Utc::now() - last_ping.with_timezone(&chrono::Utc))
+            .to_std()
+            .ok()
+    }
+
+    /// Returns true if the screen hasn't pinged within `threshold`.
+    pub fn is_stale(&self, threshold: Duration) -> bool {
+        self.last_ping_age().is_none_or(|age| age > threshold)
+    }
+
+    pub fn uptime(&self) -> Option<Duration> {
+        let seconds = match self.uptime.as_ref()? {
+            serde_json::Value::Number(n) => n.as_f64()?,
+            serde_json::Value::String(s) => s.parse::<f64>().ok()?,
+            _ => return None,
+        };
+        Some(Duration::from_secs(seconds as u64))
+    }
+}
+
+impl Api {
+    pub fn list_screens(&self) -> Result<Vec<Screen>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!("{V4}/screens"),
+        )?)?)
+    }
+
+    pub fn get_screen(&self, id: &str) -> Result<Vec<Screen>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!("{V4}/screens?id=eq.{id}"),
+        )?)?)
+    }
+
+    pub fn get_screens(&self, ids: &[String]) -> Result<Vec<Screen>, CommandError> {
+        Ok(serde_json::from_value(commands::get(
+            &self.authentication,
+            &format!("{V4}/screens?id=in.({})", ids.join(",")),
+        )?)?)
+    }
+
+    pub fn find_screen_ids_by_name(&self, name: &str) -> Result<Vec<String>, CommandError> {
+        let response = commands::get(
+            &self.authentication,
+            &format!("{V4}/screens?select=id&{}", eq_filter("name", name)),
+        )?;
+        Ok(collect_field(&response, "id"))
+    }
+
+    /// Registers a screen using the pin shown on the player.
+    pub fn register_screen(
+        &self,
+        pin: &str,
+        name: Option<&str>,
+    ) -> Result<Vec<Screen>, CommandError> {
+        let mut payload = json!({ "pin": pin });
+        if let Some(name) = name {
+            payload["name"] = json!(name);
+        }
+        Ok(deserialize_rows(commands::post(
+            &self.authentication,
+            &format!("{V4}/screens"),
+            &payload,
+        )?)?)
+    }
+
+    pub fn update_screen(
+        &self,
+        id: &str,
+        payload: &serde_json::Value,
+    ) -> Result<Vec<Screen>, CommandError> {
+        Ok(deserialize_rows(commands::patch(
+            &self.authentication,
+            &format!("{V4}/screens?id=eq.{id}"),
+            payload,
+        )?)?)
+    }
+
+    pub fn update_screens(
+        &self,
+        ids: &[String],
+        payload: &serde_json::Value,
+    ) -> Result<Vec<Screen>, CommandError> {
+        Ok(deserialize_rows(commands::patch(
+            &self.authentication,
+            &format!("{V4}/screens?id=in.({})", ids.join(",")),
+            payload,
+        )?)?)
+    }
+
+    pub fn delete_screen(&self, id: &str) -> Result<(), CommandError> {
+        commands::delete(&self.authentication, &format!("{V4}/screens?id=eq.{id}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen_should_round_trip_nulls_and_unknown_fields() {
+        let value = json!({
+            "id": "screen-1",
+            "name": null,
+            "is_enabled": true,
+            "in_sync": null,
+            "last_ping": null,
+            "uptime": "42",
+            "hardware_version": null,
+            "software_version": "1.0",
+            "priority": false,
+        });
+
+        let screen: Screen = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(serde_json::to_value(&screen).unwrap(), value);
+    }
+}
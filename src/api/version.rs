@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::api::Api;
+use crate::api::{Api, V4_1};
 use crate::commands;
 use crate::commands::CommandError;
 
@@ -32,7 +32,7 @@ impl Api {
         let response = commands::get(
             &self.authentication,
             &format!(
-                "v4.1/edge-apps/versions?select=user_version,description,icon,author,homepage_url,categories,revision,ready_signal&app_id=eq.{app_id}&order=revision.desc&limit=1"
+                "{V4_1}/edge-apps/versions?select=user_version,description,icon,author,homepage_url,categories,revision,ready_signal&app_id=eq.{app_id}&order=revision.desc&limit=1"
             ),
         )?;
 
@@ -44,4 +44,20 @@ impl Api {
         }
         Ok(versions.first().cloned())
     }
+
+    pub fn get_revision(
+        &self,
+        app_id: &str,
+        revision: u32,
+    ) -> Result<Option<EdgeAppVersion>, CommandError> {
+        let response = commands::get(
+            &self.authentication,
+            &format!(
+                "{V4_1}/edge-apps/versions?select=user_version,description,icon,author,homepage_url,categories,revision,ready_signal&app_id=eq.{app_id}&revision=eq.{revision}"
+            ),
+        )?;
+
+        let versions = serde_json::from_value::<Vec<EdgeAppVersion>>(response)?;
+        Ok(versions.into_iter().next())
+    }
 }
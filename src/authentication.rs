@@ -1,19 +1,54 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 use std::{env, fs};
 
+use openssl::hash::MessageDigest;
+use openssl::symm::Cipher;
 use reqwest::header::{HeaderMap, InvalidHeaderValue};
 use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::api::V4;
+
 // For compatability reasons - let's leave build env as well.
 include!(concat!(env!("OUT_DIR"), "/config.rs"));
-// for local development
-// also uncomment unsafe certificate lines "danger_accept_invalid_certs(true)".
 
+// The token is kept in the OS keyring. Shared, headless build boxes usually have no keyring
+// service to unlock, so the passphrase-protected file is used there instead. Plain token
+// files written by older versions are still read.
+/// Service name of the token in the OS keyring.
+pub const KEYRING_SERVICE: &str = "screenly-cli";
+const KEYRING_USER: &str = "api-token";
+const TOKEN_FILE: &str = ".screenly";
+const ENCRYPTED_TOKEN_FILE: &str = ".screenly.enc";
+/// Unlocks the encrypted token file without an interactive prompt.
+pub const PASSPHRASE_ENV: &str = "SCREENLY_TOKEN_PASSPHRASE";
+const PBKDF2_ITERATIONS: usize = 600_000;
+
+/// API endpoint and network settings used to build HTTP clients.
+///
+/// Besides the explicit proxy, the standard `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY`
+/// environment variables are honoured.
+#[derive(Clone)]
+#[non_exhaustive]
 pub struct Config {
     pub url: String,
+    /// Proxy URL for all requests, e.g. `http://proxy.example.com:3128`.
+    pub proxy: Option<String>,
+    /// PEM bundle of additional root certificates to trust.
+    pub ca_cert: Option<PathBuf>,
+    /// PEM client certificate for mutual TLS. Requires `client_key`.
+    pub client_cert: Option<PathBuf>,
+    /// PEM (PKCS#8) private key of `client_cert`.
+    pub client_key: Option<PathBuf>,
+    /// Skips certificate verification. Only allowed in `local` builds.
+    pub insecure: bool,
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum AuthenticationError {
     #[error("wrong credentials error")]
     WrongCredentials,
@@ -29,137 +64,681 @@ pub enum AuthenticationError {
     MissingHomeDir(),
     #[error("invalid header error")]
     InvalidHeader(#[from] InvalidHeaderValue),
+    /// No longer returned; verification failures use the specific variants below.
     #[error("unknown error")]
     Unknown,
+    #[error("invalid network configuration: {0}")]
+    InvalidNetworkConfig(String),
+    #[error("a passphrase is required to unlock the stored token")]
+    PassphraseRequired,
+    #[error("wrong passphrase")]
+    WrongPassphrase,
+    #[error("invalid token file: {0}")]
+    InvalidTokenFile(String),
+    #[error("encryption error: {0}")]
+    Encryption(#[from] openssl::error::ErrorStack),
+    #[error("cannot reach {0}: {1}")]
+    Unreachable(String, reqwest::Error),
+    #[error("{0} does not look like a Screenly API, check API_BASE_URL")]
+    WrongApiUrl(String),
+    #[error("unexpected response status {0}")]
+    UnexpectedStatus(u16),
+    #[error("the OS keyring is unavailable: {0}")]
+    KeyringUnavailable(String),
 }
 
+#[derive(Clone)]
 pub struct Authentication {
     pub config: Config,
     pub token: String,
+    source: TokenSource,
+    clients: Arc<Clients>,
 }
 
-impl Config {
-    pub fn default() -> Self {
+/// Where the token of an [`Authentication`] came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenSource {
+    /// The `API_TOKEN` environment variable.
+    Env,
+    /// The OS keyring, written by `screenly login`.
+    Keyring,
+    /// The plain token file written by older versions of `screenly login`.
+    File(PathBuf),
+    /// The passphrase-protected token file written by `screenly login --encrypt`.
+    EncryptedFile(PathBuf),
+    /// Passed in directly through [`Authentication::new_with_config`].
+    Explicit,
+}
+
+/// On-disk format of the encrypted token file. Binary fields are hex encoded.
+#[derive(Serialize, Deserialize)]
+struct EncryptedToken {
+    version: u32,
+    iterations: usize,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    tag: String,
+}
+
+/// HTTP clients shared by all clones of an [`Authentication`], so that requests reuse
+/// pooled connections. Each client is built on first use.
+#[derive(Default)]
+struct Clients {
+    client: OnceLock<reqwest::Client>,
+    blocking: OnceLock<reqwest::blocking::Client>,
+    download: OnceLock<reqwest::blocking::Client>,
+}
+
+impl Default for Config {
+    /// Reads the configuration from the environment.
+    ///
+    /// `API_BASE_URL` overrides the API endpoint of the build. Network settings come from
+    /// `SCREENLY_PROXY`, `SCREENLY_CA_CERT`, `SCREENLY_CLIENT_CERT` and `SCREENLY_CLIENT_KEY`.
+    fn default() -> Self {
         Self {
-            url: {
-                if let Ok(url) = env::var("API_BASE_URL") {
-                    url
-                } else {
-                    API_BASE_URL.to_string()
-                }
-            },
+            url: env::var("API_BASE_URL").unwrap_or(API_BASE_URL.to_string()),
+            proxy: env::var("SCREENLY_PROXY").ok(),
+            ca_cert: env::var_os("SCREENLY_CA_CERT").map(PathBuf::from),
+            client_cert: env::var_os("SCREENLY_CLIENT_CERT").map(PathBuf::from),
+            client_key: env::var_os("SCREENLY_CLIENT_KEY").map(PathBuf::from),
+            insecure: false,
         }
     }
+}
 
-    #[cfg(test)]
+impl Config {
+    /// Creates a configuration for `url` without any network settings.
     pub fn new(url: String) -> Self {
-        Self { url }
+        Self {
+            url,
+            proxy: None,
+            ca_cert: None,
+            client_cert: None,
+            client_key: None,
+            insecure: false,
+        }
     }
+
+    /// Checks settings that can be rejected before any request is made.
+    pub fn validate(&self) -> Result<(), AuthenticationError> {
+        if self.insecure && !INSECURE_ALLOWED {
+            return Err(AuthenticationError::InvalidNetworkConfig(
+                "insecure connections are only allowed in local builds".to_owned(),
+            ));
+        }
+        if self.client_cert.is_some() != self.client_key.is_some() {
+            return Err(AuthenticationError::InvalidNetworkConfig(
+                "a client certificate and a client key must be given together".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn client_options(&self) -> Result<ClientOptions, AuthenticationError> {
+        self.validate()?;
+
+        let proxy = self.proxy.as_deref().map(reqwest::Proxy::all).transpose()?;
+        let root_certificates = match &self.ca_cert {
+            Some(path) => reqwest::Certificate::from_pem_bundle(&fs::read(path)?)?,
+            None => Vec::new(),
+        };
+        let identity = match (&self.client_cert, &self.client_key) {
+            (Some(cert), Some(key)) => Some(reqwest::Identity::from_pkcs8_pem(
+                &fs::read(cert)?,
+                &fs::read(key)?,
+            )?),
+            _ => None,
+        };
+
+        Ok(ClientOptions {
+            proxy,
+            root_certificates,
+            identity,
+        })
+    }
+}
+
+/// Network settings resolved from a [`Config`], shared by the async and blocking builders.
+struct ClientOptions {
+    proxy: Option<reqwest::Proxy>,
+    root_certificates: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
 }
 
+/// The builder methods [`ClientOptions`] are applied with, shared by the async and the
+/// blocking client builders.
+trait ClientBuilder: Sized {
+    fn default_headers(self, headers: HeaderMap) -> Self;
+    fn danger_accept_invalid_certs(self, accept: bool) -> Self;
+    fn proxy(self, proxy: reqwest::Proxy) -> Self;
+    fn add_root_certificate(self, certificate: reqwest::Certificate) -> Self;
+    fn identity(self, identity: reqwest::Identity) -> Self;
+}
+
+macro_rules! impl_client_builder {
+    ($builder:ty) => {
+        impl ClientBuilder for $builder {
+            fn default_headers(self, headers: HeaderMap) -> Self {
+                <$builder>::default_headers(self, headers)
+            }
+            fn danger_accept_invalid_certs(self, accept: bool) -> Self {
+                <$builder>::danger_accept_invalid_certs(self, accept)
+            }
+            fn proxy(self, proxy: reqwest::Proxy) -> Self {
+                <$builder>::proxy(self, proxy)
+            }
+            fn add_root_certificate(self, certificate: reqwest::Certificate) -> Self {
+                <$builder>::add_root_certificate(self, certificate)
+            }
+            fn identity(self, identity: reqwest::Identity) -> Self {
+                <$builder>::identity(self, identity)
+            }
+        }
+    };
+}
+
+impl_client_builder!(reqwest::ClientBuilder);
+impl_client_builder!(reqwest::blocking::ClientBuilder);
+
 impl Authentication {
+    /// Reads the token from `API_TOKEN` or the token file stored by `screenly login`.
     pub fn new() -> Result<Self, AuthenticationError> {
+        Self::from_config(Config::default())
+    }
+
+    /// Like [`Authentication::new`], with the given endpoint and network settings.
+    ///
+    /// An encrypted token file is unlocked with `SCREENLY_TOKEN_PASSPHRASE`; without it,
+    /// [`AuthenticationError::PassphraseRequired`] is returned.
+    pub fn from_config(config: Config) -> Result<Self, AuthenticationError> {
+        Self::read(config, None)
+    }
+
+    /// Like [`Authentication::from_config`], unlocking an encrypted token file with `passphrase`.
+    pub fn from_config_with_passphrase(
+        config: Config,
+        passphrase: &str,
+    ) -> Result<Self, AuthenticationError> {
+        Self::read(config, Some(passphrase))
+    }
+
+    fn read(config: Config, passphrase: Option<&str>) -> Result<Self, AuthenticationError> {
+        let (token, source) = Self::read_token(passphrase)?;
         Ok(Self {
-            config: Config::default(),
-            token: Self::read_token()?,
+            source,
+            ..Self::new_with_config(config, &token)
         })
     }
 
+    /// Removes the stored token from the OS keyring and the token files.
     pub fn remove_token() -> Result<(), AuthenticationError> {
-        match dirs::home_dir() {
-            Some(home) => {
-                fs::remove_file(home.join(".screenly"))?;
-                Ok(())
-            }
-            None => Err(AuthenticationError::MissingHomeDir()),
+        let home = dirs::home_dir().ok_or(AuthenticationError::MissingHomeDir())?;
+        let removed_keyring = delete_keyring_token();
+        let removed_plain = remove_if_exists(&home.join(TOKEN_FILE))?;
+        let removed_encrypted = remove_if_exists(&home.join(ENCRYPTED_TOKEN_FILE))?;
+        if !removed_keyring && !removed_plain && !removed_encrypted {
+            return Err(std::io::Error::from(std::io::ErrorKind::NotFound).into());
         }
+        Ok(())
     }
 
-    fn read_token() -> Result<String, AuthenticationError> {
+    fn read_token(passphrase: Option<&str>) -> Result<(String, TokenSource), AuthenticationError> {
         if let Ok(token) = env::var("API_TOKEN") {
-            return Ok(token);
+            return Ok((token, TokenSource::Env));
         }
 
-        match dirs::home_dir() {
-            Some(path) => {
-                fs::read_to_string(path.join(".screenly")).map_err(AuthenticationError::Io)
-            }
-            None => Err(AuthenticationError::NoCredentials),
+        match keyring_entry().and_then(|entry| entry.get_password()) {
+            Ok(token) => return Ok((token, TokenSource::Keyring)),
+            Err(keyring::Error::NoEntry) => {}
+            Err(e) => log::debug!("Not reading the token from the OS keyring: {e}"),
         }
+
+        let home = dirs::home_dir().ok_or(AuthenticationError::NoCredentials)?;
+        let path = home.join(TOKEN_FILE);
+        let encrypted_path = home.join(ENCRYPTED_TOKEN_FILE);
+
+        if !path.exists() && encrypted_path.exists() {
+            let passphrase = match passphrase {
+                Some(passphrase) => passphrase.to_owned(),
+                None => {
+                    env::var(PASSPHRASE_ENV).map_err(|_| AuthenticationError::PassphraseRequired)?
+                }
+            };
+            let token = decrypt_token(&fs::read_to_string(&encrypted_path)?, &passphrase)?;
+            return Ok((token, TokenSource::EncryptedFile(encrypted_path)));
+        }
+
+        let token = fs::read_to_string(&path)?;
+        Ok((token, TokenSource::File(path)))
     }
 
-    #[cfg(test)]
+    /// Creates credentials for a given API endpoint without touching the token file.
     pub fn new_with_config(config: Config, token: &str) -> Self {
         Self {
             config,
             token: token.to_string(),
+            source: TokenSource::Explicit,
+            clients: Arc::default(),
+        }
+    }
+
+    pub fn token_source(&self) -> &TokenSource {
+        &self.source
+    }
+
+    /// Returns the token with all but its first and last four characters hidden.
+    pub fn masked_token(&self) -> String {
+        let token = self.token.trim();
+        let chars: Vec<char> = token.chars().collect();
+        if chars.len() < 12 {
+            return "*".repeat(chars.len());
+        }
+        let head: String = chars[..4].iter().collect();
+        let tail: String = chars[chars.len() - 4..].iter().collect();
+        format!("{head}...{tail}")
+    }
+
+    /// Returns the shared async client. Cloning it is cheap and keeps the connection pool.
+    pub fn client(&self) -> Result<reqwest::Client, AuthenticationError> {
+        if let Some(client) = self.clients.client.get() {
+            return Ok(client.clone());
+        }
+
+        let client = self
+            .configure(reqwest::Client::builder(), self.default_headers()?)?
+            .build()?;
+        Ok(self.clients.client.get_or_init(|| client).clone())
+    }
+
+    /// Returns the shared blocking client for simple, sequential CLI commands.
+    ///
+    /// It must not be used from within an async runtime; use [`Authentication::client`] there.
+    pub fn blocking_client(&self) -> Result<reqwest::blocking::Client, AuthenticationError> {
+        if let Some(client) = self.clients.blocking.get() {
+            return Ok(client.clone());
+        }
+
+        let client = self
+            .configure(
+                reqwest::blocking::Client::builder(),
+                self.default_headers()?,
+            )?
+            .build()?;
+        Ok(self.clients.blocking.get_or_init(|| client).clone())
+    }
+
+    /// Returns a blocking client without the API token, for downloading files from
+    /// storage URLs returned by the API. Network options still apply.
+    pub fn download_client(&self) -> Result<reqwest::blocking::Client, AuthenticationError> {
+        if let Some(client) = self.clients.download.get() {
+            return Ok(client.clone());
+        }
+
+        let mut headers = self.default_headers()?;
+        headers.remove(header::AUTHORIZATION);
+        let client = self
+            .configure(reqwest::blocking::Client::builder(), headers)?
+            .build()?;
+        Ok(self.clients.download.get_or_init(|| client).clone())
+    }
+
+    /// Applies the default headers and the network settings of the config. Both the
+    /// async and the blocking clients are built through here.
+    fn configure<B: ClientBuilder>(
+        &self,
+        builder: B,
+        headers: HeaderMap,
+    ) -> Result<B, AuthenticationError> {
+        let options = self.config.client_options()?;
+        let mut builder = builder
+            .default_headers(headers)
+            .danger_accept_invalid_certs(self.config.insecure);
+        if let Some(proxy) = options.proxy {
+            builder = builder.proxy(proxy);
         }
+        for certificate in options.root_certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(identity) = options.identity {
+            builder = builder.identity(identity);
+        }
+        Ok(builder)
     }
 
-    pub fn build_client(&self) -> Result<reqwest::blocking::Client, AuthenticationError> {
-        let token = self.token.clone();
-        let secret = format!("Token {token}");
+    fn default_headers(&self) -> Result<HeaderMap, AuthenticationError> {
+        let secret = format!("Token {}", self.token);
         let mut default_headers = HeaderMap::new();
         default_headers.insert(header::AUTHORIZATION, secret.parse()?);
         default_headers.insert(
             header::USER_AGENT,
             format!("screenly-cli {}", env!("CARGO_PKG_VERSION")).parse()?,
         );
-
-        reqwest::blocking::Client::builder()
-            .default_headers(default_headers)
-            .build()
-            .map_err(AuthenticationError::Request)
+        Ok(default_headers)
     }
 }
 
+/// Verifies the token and stores it in the OS keyring.
+///
+/// Returns [`AuthenticationError::KeyringUnavailable`] when there is no keyring to store it
+/// in; [`store_encrypted_token`] can store the verified token instead.
 pub fn verify_and_store_token(
     token: &str,
-    api_url: &str,
+    config: &Config,
 ) -> anyhow::Result<(), AuthenticationError> {
-    verify_token(token, api_url)?;
+    verify_token(token, config)?;
+    store_token(token, None)
+}
 
-    match dirs::home_dir() {
-        Some(home) => {
-            fs::write(home.join(".screenly"), token)?;
-            Ok(())
+/// Like [`verify_and_store_token`], encrypting the token at rest with `passphrase`.
+pub fn verify_and_store_encrypted_token(
+    token: &str,
+    config: &Config,
+    passphrase: &str,
+) -> Result<(), AuthenticationError> {
+    verify_token(token, config)?;
+    store_token(token, Some(passphrase))
+}
+
+/// Stores an already verified token encrypted with `passphrase`, for example when the
+/// OS keyring is unavailable.
+pub fn store_encrypted_token(token: &str, passphrase: &str) -> Result<(), AuthenticationError> {
+    store_token(token, Some(passphrase))
+}
+
+/// Stores the token in the OS keyring or, with a passphrase, in an encrypted file readable
+/// by the current user only. Tokens stored before in other places are removed.
+fn store_token(token: &str, passphrase: Option<&str>) -> Result<(), AuthenticationError> {
+    let home = dirs::home_dir().ok_or(AuthenticationError::MissingHomeDir())?;
+    let path = home.join(TOKEN_FILE);
+    let encrypted_path = home.join(ENCRYPTED_TOKEN_FILE);
+
+    match passphrase {
+        Some(passphrase) => {
+            write_private_file(
+                &encrypted_path,
+                encrypt_token(token, passphrase)?.as_bytes(),
+            )?;
+            delete_keyring_token();
+        }
+        None => {
+            keyring_entry()
+                .and_then(|entry| entry.set_password(token))
+                .map_err(|e| AuthenticationError::KeyringUnavailable(e.to_string()))?;
+            remove_if_exists(&encrypted_path)?;
         }
-        None => Err(AuthenticationError::MissingHomeDir()),
     }
+    remove_if_exists(&path)?;
+    Ok(())
 }
 
-fn verify_token(token: &str, api_url: &str) -> anyhow::Result<(), AuthenticationError> {
-    // Using uuid of non existing playlist. If we get 404 it means we authenticated successfully.
-    let url = format!("{api_url}/v3/groups/11CF9Z3GZR0005XXKH00F8V20R/");
-    let secret = format!("Token {token}");
-    let client = reqwest::blocking::Client::builder().build()?;
+fn keyring_entry() -> keyring::Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+}
 
-    let res = client
+/// Removes the token from the OS keyring. Returns whether there was one.
+fn delete_keyring_token() -> bool {
+    match keyring_entry().and_then(|entry| entry.delete_credential()) {
+        Ok(()) => true,
+        Err(keyring::Error::NoEntry) => false,
+        Err(e) => {
+            log::debug!("Not removing the token from the OS keyring: {e}");
+            false
+        }
+    }
+}
+
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), AuthenticationError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    // The mode only applies to new files, so tighten files written by older versions as well.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents)?;
+    Ok(())
+}
+
+fn remove_if_exists(path: &Path) -> Result<bool, AuthenticationError> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    iterations: usize,
+) -> Result<[u8; 32], AuthenticationError> {
+    let mut key = [0; 32];
+    openssl::pkcs5::pbkdf2_hmac(
+        passphrase.as_bytes(),
+        salt,
+        iterations,
+        MessageDigest::sha256(),
+        &mut key,
+    )?;
+    Ok(key)
+}
+
+/// Encrypts the token with AES-256-GCM under a PBKDF2-SHA256 key derived from `passphrase`.
+fn encrypt_token(token: &str, passphrase: &str) -> Result<String, AuthenticationError> {
+    let mut salt = [0; 16];
+    let mut nonce = [0; 12];
+    openssl::rand::rand_bytes(&mut salt)?;
+    openssl::rand::rand_bytes(&mut nonce)?;
+
+    let key = derive_key(passphrase, &salt, PBKDF2_ITERATIONS)?;
+    let mut tag = [0; 16];
+    let ciphertext = openssl::symm::encrypt_aead(
+        Cipher::aes_256_gcm(),
+        &key,
+        Some(&nonce),
+        &[],
+        token.as_bytes(),
+        &mut tag,
+    )?;
+
+    serde_json::to_string(&EncryptedToken {
+        version: 1,
+        iterations: PBKDF2_ITERATIONS,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+        tag: hex::encode(tag),
+    })
+    .map_err(|e| AuthenticationError::InvalidTokenFile(e.to_string()))
+}
+
+fn decrypt_token(data: &str, passphrase: &str) -> Result<String, AuthenticationError> {
+    let invalid = |e: &dyn std::fmt::Display| AuthenticationError::InvalidTokenFile(e.to_string());
+    let encrypted: EncryptedToken = serde_json::from_str(data).map_err(|e| invalid(&e))?;
+    if encrypted.version != 1 {
+        return Err(invalid(&format!(
+            "unsupported version {}",
+            encrypted.version
+        )));
+    }
+    // The iteration count is only stored for future migrations. Trusting it would let a
+    // tampered file make every command spin in PBKDF2.
+    if encrypted.iterations != PBKDF2_ITERATIONS {
+        return Err(invalid(&format!(
+            "unsupported iteration count {}",
+            encrypted.iterations
+        )));
+    }
+    let salt = hex::decode(&encrypted.salt).map_err(|e| invalid(&e))?;
+    let nonce = hex::decode(&encrypted.nonce).map_err(|e| invalid(&e))?;
+    let ciphertext = hex::decode(&encrypted.ciphertext).map_err(|e| invalid(&e))?;
+    let tag = hex::decode(&encrypted.tag).map_err(|e| invalid(&e))?;
+
+    let key = derive_key(passphrase, &salt, encrypted.iterations)?;
+    let token = openssl::symm::decrypt_aead(
+        Cipher::aes_256_gcm(),
+        &key,
+        Some(&nonce),
+        &[],
+        &ciphertext,
+        &tag,
+    )
+    .map_err(|_| AuthenticationError::WrongPassphrase)?;
+
+    String::from_utf8(token).map_err(|e| invalid(&e))
+}
+
+/// Checks a token against the API.
+///
+/// Tells an invalid token ([`AuthenticationError::WrongCredentials`]) apart from an API that
+/// can't be reached ([`AuthenticationError::Unreachable`]) and a base URL that doesn't point
+/// to the Screenly API ([`AuthenticationError::WrongApiUrl`]).
+pub fn verify_token(token: &str, config: &Config) -> Result<(), AuthenticationError> {
+    // Any authenticated read works. Listing at most one screen is cheap for every team.
+    let url = format!("{}/{V4}/screens?select=id&limit=1", config.url);
+    let response = Authentication::new_with_config(config.clone(), token)
+        .blocking_client()?
         .get(url)
-        .header(header::AUTHORIZATION, &secret)
-        .send()?;
+        .send()
+        .map_err(|e| {
+            if e.is_connect() || e.is_timeout() {
+                AuthenticationError::Unreachable(config.url.clone(), e)
+            } else {
+                AuthenticationError::Request(e)
+            }
+        })?;
 
-    match res.status() {
-        StatusCode::UNAUTHORIZED => Err(AuthenticationError::WrongCredentials),
-        StatusCode::NOT_FOUND => Ok(()),
-        _ => Err(AuthenticationError::Unknown),
+    match response.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(AuthenticationError::WrongCredentials)
+        }
+        StatusCode::NOT_FOUND => Err(AuthenticationError::WrongApiUrl(config.url.clone())),
+        StatusCode::OK => {
+            // A proxy or web server answering in place of the API usually returns HTML.
+            match response.json::<serde_json::Value>() {
+                Ok(serde_json::Value::Array(_)) => Ok(()),
+                _ => Err(AuthenticationError::WrongApiUrl(config.url.clone())),
+            }
+        }
+        status => Err(AuthenticationError::UnexpectedStatus(status.as_u16())),
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::any::Any;
+    use std::collections::HashMap;
     use std::ffi::OsString;
     use std::fs;
+    use std::sync::{Mutex, MutexGuard};
 
     use envtestkit::lock::lock_test;
     use envtestkit::set_env;
     use httpmock::Method::GET;
     use httpmock::MockServer;
+    use keyring::credential::{Credential, CredentialApi, CredentialBuilderApi};
     use simple_logger::SimpleLogger;
     use tempfile::tempdir;
 
     use super::*;
 
+    type Secrets = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// Keyring keeping secrets in memory, shared by all the entries it builds.
+    #[derive(Debug, Default)]
+    struct MemoryKeyring {
+        secrets: Secrets,
+        available: bool,
+    }
+
+    #[derive(Debug)]
+    struct MemoryCredential {
+        key: String,
+        secrets: Secrets,
+        available: bool,
+    }
+
+    impl MemoryCredential {
+        fn secrets(&self) -> keyring::Result<MutexGuard<'_, HashMap<String, Vec<u8>>>> {
+            if !self.available {
+                return Err(keyring::Error::NoStorageAccess("locked".into()));
+            }
+            Ok(self.secrets.lock().unwrap())
+        }
+    }
+
+    impl CredentialApi for MemoryCredential {
+        fn set_secret(&self, secret: &[u8]) -> keyring::Result<()> {
+            self.secrets()?.insert(self.key.clone(), secret.to_vec());
+            Ok(())
+        }
+
+        fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+            self.secrets()?
+                .get(&self.key)
+                .cloned()
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn delete_credential(&self) -> keyring::Result<()> {
+            self.secrets()?
+                .remove(&self.key)
+                .map(|_| ())
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    impl CredentialBuilderApi for MemoryKeyring {
+        fn build(
+            &self,
+            _target: Option<&str>,
+            service: &str,
+            user: &str,
+        ) -> keyring::Result<Box<Credential>> {
+            Ok(Box::new(MemoryCredential {
+                key: format!("{service}/{user}"),
+                secrets: self.secrets.clone(),
+                available: self.available,
+            }))
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    /// Replaces the OS keyring with an empty one. Tests must hold the env lock while using it.
+    fn use_memory_keyring(available: bool) -> Secrets {
+        let keyring = MemoryKeyring {
+            available,
+            ..Default::default()
+        };
+        let secrets = keyring.secrets.clone();
+        keyring::set_default_credential_builder(Box::new(keyring));
+        secrets
+    }
+
+    fn keyring_token(secrets: &Secrets) -> Option<String> {
+        secrets
+            .lock()
+            .unwrap()
+            .get(&format!("{KEYRING_SERVICE}/{KEYRING_USER}"))
+            .map(|secret| String::from_utf8(secret.clone()).unwrap())
+    }
+
     #[test]
     fn test_verify_and_store_token_when_token_is_valid() {
         SimpleLogger::new()
@@ -169,22 +748,27 @@ mod tests {
         let tmp_dir = tempdir().unwrap();
         let _lock = lock_test();
         let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        let secrets = use_memory_keyring(true);
+        fs::write(tmp_dir.path().join(".screenly"), "old_token").unwrap();
 
         let mock_server = MockServer::start();
         mock_server.mock(|when, then| {
             when.method(GET)
-                .path("/v3/groups/11CF9Z3GZR0005XXKH00F8V20R/")
-                .header("Authorization", "Token token");
-            then.status(404);
+                .path("/v4/screens")
+                .query_param("limit", "1")
+                .header("Authorization", "Token correct_token");
+            then.status(200).json_body(serde_json::json!([]));
         });
 
         let config = Config::new(mock_server.base_url());
         let authentication = Authentication::new_with_config(config, "");
-        assert!(verify_and_store_token("correct_token", &authentication.config.url).is_ok());
-        let path = tmp_dir.path().join(".screenly");
-        assert!(path.exists());
-        let contents = fs::read_to_string(path).unwrap();
-        assert!(contents.eq("correct_token"));
+        assert!(verify_and_store_token("correct_token", &authentication.config).is_ok());
+        assert_eq!(keyring_token(&secrets).as_deref(), Some("correct_token"));
+        assert!(!tmp_dir.path().join(".screenly").exists());
+        assert_eq!(
+            Authentication::read_token(None).unwrap(),
+            ("correct_token".to_owned(), TokenSource::Keyring)
+        );
     }
 
     #[test]
@@ -193,19 +777,20 @@ mod tests {
 
         let _lock = lock_test();
         let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        let secrets = use_memory_keyring(true);
 
         let mock_server = MockServer::start();
         mock_server.mock(|when, then| {
-            when.method(GET)
-                .path("/v3/groups/11CF9Z3GZR0005XXKH00F8V20R/");
+            when.method(GET).path("/v4/screens");
             then.status(401);
         });
 
         let config = Config::new(mock_server.base_url());
-        assert!(verify_and_store_token("wrong_token", &config.url).is_err());
+        assert!(verify_and_store_token("wrong_token", &config).is_err());
         let path = tmp_dir.path().join(".screenly");
 
         assert!(!path.exists());
+        assert_eq!(keyring_token(&secrets), None);
     }
 
     #[test]
@@ -214,9 +799,10 @@ mod tests {
         let _lock = lock_test();
         let _token = set_env(OsString::from("API_TOKEN"), "env_token");
         let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        use_memory_keyring(true);
         println!("{}", tmp_dir.path().join(".screenly").to_str().unwrap());
         fs::write(tmp_dir.path().join(".screenly").to_str().unwrap(), "token").unwrap();
-        assert_eq!(Authentication::read_token().unwrap(), "env_token");
+        assert_eq!(Authentication::read_token(None).unwrap().0, "env_token");
     }
 
     #[test]
@@ -224,9 +810,10 @@ mod tests {
         let tmp_dir = tempdir().unwrap();
         let _lock = lock_test();
         let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        use_memory_keyring(true);
         fs::write(tmp_dir.path().join(".screenly").to_str().unwrap(), "token").unwrap();
 
-        assert_eq!(Authentication::read_token().unwrap(), "token");
+        assert_eq!(Authentication::read_token(None).unwrap().0, "token");
     }
 
     #[test]
@@ -234,10 +821,41 @@ mod tests {
         let tmp_dir = tempdir().unwrap();
         let _lock = lock_test();
         let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        let secrets = use_memory_keyring(true);
         fs::write(tmp_dir.path().join(".screenly").to_str().unwrap(), "token").unwrap();
 
         Authentication::remove_token().unwrap();
         assert!(!tmp_dir.path().join(".screenly").exists());
+
+        store_token("keyring_token", None).unwrap();
+        Authentication::remove_token().unwrap();
+        assert_eq!(keyring_token(&secrets), None);
+        assert!(Authentication::remove_token().is_err());
+    }
+
+    #[test]
+    fn test_store_token_when_keyring_is_unavailable_should_fail_without_writing_files() {
+        let tmp_dir = tempdir().unwrap();
+        let _lock = lock_test();
+        let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        use_memory_keyring(false);
+        fs::write(tmp_dir.path().join(".screenly"), "old_token").unwrap();
+
+        assert!(matches!(
+            store_token("token", None),
+            Err(AuthenticationError::KeyringUnavailable(_))
+        ));
+        // The token stored before is still read.
+        assert_eq!(Authentication::read_token(None).unwrap().0, "old_token");
+
+        store_encrypted_token("token", "passphrase").unwrap();
+        assert_eq!(
+            Authentication::read_token(Some("passphrase")).unwrap(),
+            (
+                "token".to_owned(),
+                TokenSource::EncryptedFile(tmp_dir.path().join(".screenly.enc"))
+            )
+        );
     }
 
     #[test]
@@ -246,22 +864,190 @@ mod tests {
         let tmp_dir = tempdir().unwrap();
         let _lock = lock_test();
         let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        let secrets = use_memory_keyring(true);
 
         let mock_server = MockServer::start();
-        let group_call_mock = mock_server.mock(|when, then| {
+        let verify_call_mock = mock_server.mock(|when, then| {
             when.method(GET)
-                .path("/v3/groups/11CF9Z3GZR0005XXKH00F8V20R/")
+                .path("/v4/screens")
                 .header("Authorization", "Token correct_token");
-            then.status(404);
+            then.status(200).json_body(serde_json::json!([]));
         });
 
         let config = Config::new(mock_server.base_url());
         let authentication = Authentication::new_with_config(config, "");
-        assert!(verify_and_store_token("correct_token", &authentication.config.url).is_ok());
-        let path = tmp_dir.path().join(".screenly");
-        assert!(path.exists());
-        let contents = fs::read_to_string(path).unwrap();
-        group_call_mock.assert();
-        assert!(contents.eq("correct_token"));
+        assert!(verify_and_store_token("correct_token", &authentication.config).is_ok());
+        verify_call_mock.assert();
+        assert_eq!(keyring_token(&secrets).as_deref(), Some("correct_token"));
+    }
+
+    #[test]
+    fn test_clones_share_http_clients() {
+        let authentication =
+            Authentication::new_with_config(Config::new("http://localhost".to_owned()), "token");
+        let clone = authentication.clone();
+
+        authentication.client().unwrap();
+        authentication.blocking_client().unwrap();
+
+        assert!(Arc::ptr_eq(&authentication.clients, &clone.clients));
+        assert!(clone.clients.client.get().is_some());
+        assert!(clone.clients.blocking.get().is_some());
+    }
+
+    #[test]
+    fn test_validate_rejects_insecure_outside_local_builds() {
+        let mut config = Config::new("http://localhost".to_owned());
+        config.insecure = true;
+
+        assert_eq!(config.validate().is_err(), !INSECURE_ALLOWED);
+    }
+
+    #[test]
+    fn test_validate_requires_client_certificate_and_key_together() {
+        let mut config = Config::new("http://localhost".to_owned());
+        config.client_cert = Some(PathBuf::from("client.pem"));
+
+        assert!(matches!(
+            config.validate(),
+            Err(AuthenticationError::InvalidNetworkConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_client_with_unreadable_ca_cert_should_fail() {
+        let tmp_dir = tempdir().unwrap();
+        let mut config = Config::new("http://localhost".to_owned());
+        config.ca_cert = Some(tmp_dir.path().join("missing.pem"));
+        let authentication = Authentication::new_with_config(config, "token");
+
+        assert!(matches!(
+            authentication.blocking_client(),
+            Err(AuthenticationError::Io(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_store_token_should_restrict_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempdir().unwrap();
+        let _lock = lock_test();
+        let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        use_memory_keyring(true);
+        let path = tmp_dir.path().join(".screenly.enc");
+        fs::write(&path, "old_token").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        store_token("token", Some("passphrase")).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(
+            decrypt_token(&fs::read_to_string(&path).unwrap(), "passphrase").unwrap(),
+            "token"
+        );
+    }
+
+    #[test]
+    fn test_encrypted_token_should_require_the_right_passphrase() {
+        let tmp_dir = tempdir().unwrap();
+        let _lock = lock_test();
+        let _test = set_env(OsString::from("HOME"), tmp_dir.path().to_str().unwrap());
+        let secrets = use_memory_keyring(true);
+        fs::write(tmp_dir.path().join(".screenly"), "plain_token").unwrap();
+        store_token("keyring_token", None).unwrap();
+
+        store_token("secret_token", Some("correct horse")).unwrap();
+
+        let encrypted = fs::read_to_string(tmp_dir.path().join(".screenly.enc")).unwrap();
+        assert!(!encrypted.contains("secret_token"));
+        assert!(!tmp_dir.path().join(".screenly").exists());
+        assert_eq!(keyring_token(&secrets), None);
+
+        assert!(matches!(
+            Authentication::read_token(None),
+            Err(AuthenticationError::PassphraseRequired)
+        ));
+        assert!(matches!(
+            Authentication::read_token(Some("wrong")),
+            Err(AuthenticationError::WrongPassphrase)
+        ));
+        let (token, source) = Authentication::read_token(Some("correct horse")).unwrap();
+        assert_eq!(token, "secret_token");
+        assert_eq!(
+            source,
+            TokenSource::EncryptedFile(tmp_dir.path().join(".screenly.enc"))
+        );
+
+        Authentication::remove_token().unwrap();
+        assert!(!tmp_dir.path().join(".screenly.enc").exists());
+    }
+
+    #[test]
+    fn test_decrypt_token_should_reject_tampered_iteration_count() {
+        let encrypted = encrypt_token("secret_token", "passphrase").unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&encrypted).unwrap();
+        value["iterations"] = serde_json::json!(usize::MAX);
+
+        assert!(matches!(
+            decrypt_token(&value.to_string(), "passphrase"),
+            Err(AuthenticationError::InvalidTokenFile(_))
+        ));
+        assert_eq!(
+            decrypt_token(&encrypted, "passphrase").unwrap(),
+            "secret_token"
+        );
+    }
+
+    #[test]
+    fn test_masked_token_should_only_show_the_edges() {
+        let config = Config::new("http://localhost".to_owned());
+        let authentication = Authentication::new_with_config(config.clone(), "abcd1234efgh5678\n");
+        assert_eq!(authentication.masked_token(), "abcd...5678");
+
+        let authentication = Authentication::new_with_config(config, "short");
+        assert_eq!(authentication.masked_token(), "*****");
+    }
+
+    #[test]
+    fn test_verify_token_should_distinguish_failures() {
+        let mock_server = MockServer::start();
+        let config = Config::new(mock_server.base_url());
+        let mut not_found = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/screens");
+            then.status(404);
+        });
+        assert!(matches!(
+            verify_token("token", &config),
+            Err(AuthenticationError::WrongApiUrl(_))
+        ));
+        not_found.delete();
+
+        let mut html = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/screens");
+            then.status(200).body("<html></html>");
+        });
+        assert!(matches!(
+            verify_token("token", &config),
+            Err(AuthenticationError::WrongApiUrl(_))
+        ));
+        html.delete();
+
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/screens");
+            then.status(500);
+        });
+        assert!(matches!(
+            verify_token("token", &config),
+            Err(AuthenticationError::UnexpectedStatus(500))
+        ));
+
+        let unreachable = Config::new("http://127.0.0.1:1".to_owned());
+        assert!(matches!(
+            verify_token("token", &unreachable),
+            Err(AuthenticationError::Unreachable(_, _))
+        ));
     }
 }
@@ -1,25 +1,36 @@
 use std::io::{Read, Write};
 use std::path::PathBuf;
-use std::{env, fs, io};
+use std::time::Duration;
+use std::{env, io};
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use http_auth_basic::Credentials;
-use log::{error, info};
-use reqwest::StatusCode;
-use rpassword::read_password;
+use log::{error, info, warn};
+use rpassword::{prompt_password, read_password};
 use thiserror::Error;
 
-use crate::authentication::{verify_and_store_token, Authentication, AuthenticationError, Config};
-use crate::commands;
-use crate::commands::edge_app::instance_manifest::InstanceManifest;
-use crate::commands::edge_app::manifest::EdgeAppManifest;
-use crate::commands::edge_app::server::MOCK_DATA_FILENAME;
-use crate::commands::edge_app::utils::{
+use crate::cli_config::{self, CliConfig};
+
+use screenly::api::Api;
+use screenly::authentication::{
+    store_encrypted_token, verify_and_store_encrypted_token, verify_and_store_token, verify_token,
+    Authentication, AuthenticationError, Config, PASSPHRASE_ENV,
+};
+use screenly::commands;
+use screenly::commands::asset::{bundle_js_injection, AssetOptions};
+use screenly::commands::auth::AuthStatus;
+use screenly::commands::edge_app::app::DEFAULT_CHANNEL;
+use screenly::commands::edge_app::instance_manifest::InstanceManifest;
+use screenly::commands::edge_app::manifest::EdgeAppManifest;
+use screenly::commands::edge_app::server::MOCK_DATA_FILENAME;
+use screenly::commands::edge_app::utils::{
     transform_edge_app_path_to_manifest, transform_instance_path_to_instance_manifest,
     validate_manifests_dependacies,
 };
-use crate::commands::playlist::PlaylistCommand;
-use crate::commands::{CommandError, Formatter, OutputType, PlaylistFile};
+use screenly::commands::playlist::PlaylistCommand;
+use screenly::commands::screen::ScreenSelector;
+use screenly::commands::screen_monitor::{poll_screens, run_event_hook, ScreenMonitor};
+use screenly::commands::{CommandError, Formatter, OutputType, PlaylistFile};
 const DEFAULT_ASSET_DURATION: u32 = 15;
 
 /// Returns a user-friendly error message for authentication errors.
@@ -28,6 +39,9 @@ fn get_authentication_error_message(e: &AuthenticationError) -> String {
         AuthenticationError::Io(io_err) if io_err.kind() == std::io::ErrorKind::NotFound => {
             "Not logged in. Please run `screenly login` first to authenticate.".to_string()
         }
+        AuthenticationError::WrongPassphrase => {
+            "Wrong passphrase for the stored token.".to_string()
+        }
         _ => {
             format!("Authentication error: {e}. Please run `screenly login` to authenticate.")
         }
@@ -35,8 +49,22 @@ fn get_authentication_error_message(e: &AuthenticationError) -> String {
 }
 
 /// Creates an Authentication instance or exits with a user-friendly error message.
-fn get_authentication() -> Authentication {
-    match Authentication::new() {
+///
+/// Prompts for the passphrase when the stored token is encrypted and
+/// SCREENLY_TOKEN_PASSPHRASE is not set.
+fn get_authentication(config: &Config) -> Authentication {
+    let result = match Authentication::from_config(config.clone()) {
+        Err(AuthenticationError::PassphraseRequired) => {
+            let passphrase = prompt_password("Enter the token passphrase: ").unwrap_or_else(|e| {
+                error!("Failed to read the passphrase: {e}");
+                std::process::exit(1);
+            });
+            Authentication::from_config_with_passphrase(config.clone(), &passphrase)
+        }
+        result => result,
+    };
+
+    match result {
         Ok(auth) => auth,
         Err(e) => {
             error!("{}", get_authentication_error_message(&e));
@@ -56,6 +84,73 @@ fn parse_key_val(s: &str) -> Result<(String, String), ParseError> {
     Ok((s[..pos].to_string(), s[pos + 1..].to_string()))
 }
 
+/// Parses durations such as `90`, `90s`, `10m`, `1h` or `1d`. Bare numbers are seconds.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (value, multiplier) = match s.char_indices().last() {
+        Some((pos, 's')) => (&s[..pos], 1),
+        Some((pos, 'm')) => (&s[..pos], 60),
+        Some((pos, 'h')) => (&s[..pos], 60 * 60),
+        Some((pos, 'd')) => (&s[..pos], 24 * 60 * 60),
+        _ => (s, 1),
+    };
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration \"{s}\", expected e.g. 30s, 10m or 1h"))?;
+    Ok(Duration::from_secs(value * multiplier))
+}
+
+fn parse_user_credentials(s: &str) -> Result<(String, Option<String>), ParseError> {
+    match s.find('=') {
+        Some(pos) => Ok((s[..pos].to_string(), Some(s[pos + 1..].to_string()))),
+        None => Ok((s.to_string(), None)),
+    }
+}
+
+#[derive(Error, Debug)]
+enum SecretError {
+    #[error("environment variable {0} is not set")]
+    MissingEnv(String),
+    #[error("the secret is empty")]
+    Empty,
+    #[error("the secret can only be provided from one source")]
+    MultipleSources,
+    #[error("failed to read the secret: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Resolves a secret from exactly one source: the command line, stdin, an environment variable
+/// or an interactive prompt (used when no other source is given).
+fn read_secret(
+    inline: Option<&str>,
+    from_stdin: bool,
+    from_env: Option<&str>,
+    prompt: &str,
+) -> Result<String, SecretError> {
+    let sources = [inline.is_some(), from_stdin, from_env.is_some()];
+    if sources.iter().filter(|source| **source).count() > 1 {
+        return Err(SecretError::MultipleSources);
+    }
+
+    let secret = if let Some(secret) = inline {
+        warn!("Passing secrets on the command line exposes them in shell history and process listings. Consider using stdin, an environment variable or the interactive prompt instead.");
+        secret.to_string()
+    } else if from_stdin {
+        let mut secret = String::new();
+        io::stdin().read_line(&mut secret)?;
+        secret.trim_end_matches(['\r', '\n']).to_string()
+    } else if let Some(var) = from_env {
+        env::var(var).map_err(|_| SecretError::MissingEnv(var.to_string()))?
+    } else {
+        prompt_password(prompt)?
+    };
+
+    if secret.is_empty() {
+        return Err(SecretError::Empty);
+    }
+    Ok(secret)
+}
+
 #[derive(Parser)]
 #[command(
     version,
@@ -68,16 +163,71 @@ pub struct Cli {
     #[arg(short, long, action = clap::ArgAction::SetTrue)]
     json: Option<bool>,
 
+    #[command(flatten)]
+    network: NetworkArgs,
+
     #[command(subcommand)]
     pub(crate) command: Commands,
 }
 
+/// Network options. Each one falls back to its `SCREENLY_*` environment variable.
+#[derive(Args, Clone, Debug, Default)]
+pub struct NetworkArgs {
+    /// Sends all requests through the given proxy (e.g. "http://proxy:3128") [env: SCREENLY_PROXY].
+    #[arg(long, global = true)]
+    proxy: Option<String>,
+    /// Trusts the root certificates in the given PEM bundle [env: SCREENLY_CA_CERT].
+    #[arg(long, global = true)]
+    ca_cert: Option<PathBuf>,
+    /// Client certificate (PEM) for mutual TLS [env: SCREENLY_CLIENT_CERT].
+    #[arg(long, global = true, requires = "client_key")]
+    client_cert: Option<PathBuf>,
+    /// Private key (PEM, PKCS#8) of the client certificate [env: SCREENLY_CLIENT_KEY].
+    #[arg(long, global = true, requires = "client_cert")]
+    client_key: Option<PathBuf>,
+    /// Skips TLS certificate verification. Only available in local builds.
+    #[arg(long, global = true, action = clap::ArgAction::SetTrue)]
+    insecure: Option<bool>,
+}
+
+impl NetworkArgs {
+    /// Applies the options on top of the configuration read from the environment.
+    fn config(&self) -> Config {
+        let mut config = Config::default();
+        if let Some(proxy) = &self.proxy {
+            config.proxy = Some(proxy.clone());
+        }
+        if let Some(ca_cert) = &self.ca_cert {
+            config.ca_cert = Some(ca_cert.clone());
+        }
+        if let Some(client_cert) = &self.client_cert {
+            config.client_cert = Some(client_cert.clone());
+            config.client_key = self.client_key.clone();
+        }
+        config.insecure = self.insecure.unwrap_or(false);
+        config
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Logs in with the provided token and stores it for further use if valid. You can set the API_TOKEN environment variable to override the stored token.
-    Login {},
+    Login {
+        /// Stores the token in a file encrypted with a passphrase instead of the OS keyring. The passphrase is read from SCREENLY_TOKEN_PASSPHRASE or prompted for.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        encrypt: Option<bool>,
+    },
     /// Logs out and removes the stored token.
     Logout {},
+    /// Authentication related commands.
+    #[command(subcommand)]
+    Auth(AuthCommands),
+    /// Verifies the current token and shows the team and user it belongs to.
+    Whoami {
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+    },
     /// Screen related commands.
     #[command(subcommand)]
     Screen(ScreenCommands),
@@ -90,6 +240,9 @@ pub enum Commands {
     /// Edge App related commands.
     #[command(subcommand)]
     EdgeApp(EdgeAppCommands),
+    /// Manages default values for command line options.
+    #[command(subcommand)]
+    Config(ConfigCommands),
     /// Starts the MCP (Model Context Protocol) server on stdio for AI assistant integration.
     Mcp {},
     /// For generating `docs/CommandLineHelp.md`.
@@ -97,6 +250,53 @@ pub enum Commands {
     PrintHelpMarkdown {},
 }
 
+/// Keys are `json`, `asset.duration`, `playlist.predicate` and `edge_app.path`.
+/// Flags take precedence over `SCREENLY_*` environment variables (e.g. `SCREENLY_ASSET_DURATION`),
+/// which take precedence over the project file (`.screenly.toml`) and the user file
+/// (`~/.config/screenly/config.toml`).
+#[derive(Subcommand, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigCommands {
+    /// Prints the effective value of a key.
+    Get {
+        /// Key to look up, e.g. asset.duration.
+        key: String,
+    },
+    /// Sets a key in the user configuration file.
+    Set {
+        /// Key to set, e.g. asset.duration.
+        key: String,
+        /// Value to store.
+        value: String,
+        /// Writes to .screenly.toml in the current project instead of the user configuration file.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        project: Option<bool>,
+    },
+    /// Removes a key from the user configuration file.
+    Unset {
+        /// Key to remove.
+        key: String,
+        /// Removes the key from .screenly.toml in the current project instead.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        project: Option<bool>,
+    },
+    /// Lists the effective values and where they come from.
+    List {
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+    },
+}
+
+#[derive(Subcommand, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AuthCommands {
+    /// Shows where the current token comes from and a masked preview of it.
+    Status {
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+    },
+}
+
 #[derive(Subcommand, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ScreenCommands {
     /// Lists your screens.
@@ -105,7 +305,7 @@ pub enum ScreenCommands {
         #[arg(short, long, action = clap::ArgAction::SetTrue)]
         json: Option<bool>,
     },
-    /// Gets a single screen by id.
+    /// Gets a single screen by id, including its labels, targeting playlists and health.
     Get {
         /// Enables JSON output.
         #[arg(short, long, action = clap::ArgAction::SetTrue)]
@@ -123,11 +323,107 @@ pub enum ScreenCommands {
         /// Optional name of the new screen.
         name: Option<String>,
     },
-    /// Deletes a screen. This cannot be undone.
-    Delete {
-        /// UUID of the screen to be deleted.
+    /// Registers screens from a CSV file with `pin`, `name` and `labels` columns.
+    ///
+    /// Every row needs a name. Multiple labels are separated with ";". Rows whose name
+    /// matches an existing screen are not registered again, so the file can be re-run safely.
+    AddBatch {
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+        /// Path to the CSV file.
+        path: PathBuf,
+    },
+    /// Renames a screen.
+    Rename {
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+        /// UUID of the screen.
         uuid: String,
+        /// New name of the screen.
+        name: String,
+    },
+    /// Enables the selected screens.
+    Enable {
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+        #[command(flatten)]
+        selector: ScreenSelectorArgs,
+    },
+    /// Disables the selected screens.
+    Disable {
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+        #[command(flatten)]
+        selector: ScreenSelectorArgs,
+        /// Skips the confirmation prompt.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        yes: Option<bool>,
+    },
+    /// Deletes the selected screens. This cannot be undone.
+    Delete {
+        #[command(flatten)]
+        selector: ScreenSelectorArgs,
+        /// Skips the confirmation prompt.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        yes: Option<bool>,
     },
+    /// Polls your screens and reports when they stop pinging, go out of sync, reboot or are removed.
+    Monitor {
+        /// Emits events as newline-delimited JSON.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+        /// Polling interval in seconds.
+        #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// Time without a ping after which a screen is considered stale (e.g. 90s, 10m, 1h).
+        #[arg(long, default_value = "10m", value_parser = parse_duration)]
+        stale_after: Duration,
+        /// Command to run for every event. It receives the event as JSON on stdin and
+        /// in the SCREENLY_EVENT, SCREENLY_SCREEN_ID and SCREENLY_SCREEN_NAME variables.
+        #[arg(long, value_name = "COMMAND")]
+        exec: Option<String>,
+    },
+}
+
+/// Selects the screens a command acts on. All given criteria must match.
+#[derive(Args, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[group(required = true, multiple = true)]
+pub struct ScreenSelectorArgs {
+    /// UUIDs of the screens.
+    uuids: Vec<String>,
+    /// Selects screens with the given label.
+    #[arg(long)]
+    label: Option<String>,
+    /// Selects screens whose name matches the glob pattern (e.g. "Lobby*").
+    #[arg(long)]
+    name_match: Option<String>,
+    /// Selects screens that haven't pinged in the last 10 minutes.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    all_stale: Option<bool>,
+}
+
+impl From<&ScreenSelectorArgs> for ScreenSelector {
+    fn from(args: &ScreenSelectorArgs) -> Self {
+        Self {
+            ids: args.uuids.clone(),
+            label: args.label.clone(),
+            name_match: args.name_match.clone(),
+            all_stale: args.all_stale.unwrap_or(false),
+        }
+    }
+}
+
+impl ScreenSelectorArgs {
+    fn single_uuid(&self) -> Option<&str> {
+        match (self.uuids.as_slice(), &self.label, &self.name_match) {
+            ([uuid], None, None) if !self.all_stale.unwrap_or(false) => Some(uuid),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Subcommand, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -287,6 +583,21 @@ pub enum AssetCommands {
         path: String,
         /// Asset title.
         title: String,
+        /// Duration of the asset in seconds.
+        #[arg(long)]
+        duration: Option<u32>,
+        /// HTTP header in the form `header=value` sent when the web asset is loaded. Can be specified multiple times.
+        #[arg(long = "header", value_name = "HEADER", value_parser = parse_key_val)]
+        headers: Vec<(String, String)>,
+        /// Basic authentication user name for the web asset. The "user=password" form is accepted but discouraged; without a password you are prompted for it.
+        #[arg(long, value_parser = parse_user_credentials, conflicts_with = "bearer")]
+        basic_auth: Option<(String, Option<String>)>,
+        /// Bearer token for the web asset. Passing the token inline is discouraged; use `asset bearer-auth` to read it from stdin or an environment variable.
+        #[arg(long)]
+        bearer: Option<String>,
+        /// Path to local file or URL for remote file with JavaScript code to inject into the web asset.
+        #[arg(long)]
+        inject_js: Option<String>,
     },
 
     /// Deletes an asset. This cannot be undone.
@@ -300,8 +611,23 @@ pub enum AssetCommands {
         /// UUID of the web asset to inject with JavaScript.
         uuid: String,
 
-        /// Path to local file or URL for remote file.
-        path: String,
+        /// Paths to local files or URLs for remote files. Multiple sources are bundled in the given order.
+        #[arg(required_unless_present = "clear", conflicts_with = "clear")]
+        paths: Vec<String>,
+
+        /// Skips the local JavaScript syntax check.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        no_lint: Option<bool>,
+
+        /// Removes the injected JavaScript code from the web asset.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        clear: Option<bool>,
+    },
+
+    /// Shows the JavaScript code currently injected into the web asset.
+    ShowJs {
+        /// UUID of the web asset.
+        uuid: String,
     },
 
     /// Sets HTTP headers for a web asset.
@@ -329,16 +655,29 @@ pub enum AssetCommands {
     BasicAuth {
         /// UUID of the web asset.
         uuid: String,
-        /// Basic authentication credentials in "user=password" form.
-        #[arg(value_parser = parse_key_val)]
-        credentials: (String, String),
+        /// Basic authentication user name. The "user=password" form is accepted but discouraged, as the password leaks into shell history. Without a password source you are prompted for it.
+        #[arg(value_parser = parse_user_credentials)]
+        credentials: (String, Option<String>),
+        /// Reads the password from stdin.
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "from_env")]
+        password_stdin: Option<bool>,
+        /// Reads the password from the given environment variable.
+        #[arg(long, value_name = "VAR")]
+        from_env: Option<String>,
     },
     /// Sets up bearer authentication headers for a web asset.
     BearerAuth {
         /// UUID of the web asset.
         uuid: String,
-        /// Bearer token.
-        token: String,
+        /// Bearer token. Passing the token inline is discouraged, as it leaks into shell history. Without a token source you are prompted for it.
+        #[arg(conflicts_with_all = ["token_stdin", "from_env"])]
+        token: Option<String>,
+        /// Reads the token from stdin.
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "from_env")]
+        token_stdin: Option<bool>,
+        /// Reads the token from the given environment variable.
+        #[arg(long, value_name = "VAR")]
+        from_env: Option<String>,
     },
 }
 
@@ -387,6 +726,18 @@ pub enum EdgeAppCommands {
         /// Generates mock data for use with the Edge App emulator.
         #[arg(short, long, action = clap::ArgAction::SetTrue)]
         generate_mock_data: Option<bool>,
+
+        /// Skips the build section of the manifest and uses its output directory as is.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        skip_build: Option<bool>,
+
+        /// Also skips files matched by .gitignore files.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        respect_gitignore: Option<bool>,
+
+        /// Disables reloading the page when the app's files, manifest or mock data change.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        no_live_reload: Option<bool>,
     },
 
     /// Edge App setting commands.
@@ -397,6 +748,28 @@ pub enum EdgeAppCommands {
     #[command(subcommand)]
     Instance(EdgeAppInstanceCommands),
 
+    /// Edge App release channel commands.
+    #[command(subcommand)]
+    Channel(EdgeAppChannelCommands),
+
+    /// Edge App revision history commands.
+    #[command(subcommand)]
+    Version(EdgeAppVersionCommands),
+
+    /// Points a channel back at an earlier revision.
+    Rollback {
+        /// Revision to roll back to.
+        revision: u32,
+
+        /// Channel to roll back.
+        #[arg(short, long, default_value = DEFAULT_CHANNEL)]
+        channel: String,
+
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+
     /// Deploys assets and settings of the Edge App and releases it.
     Deploy {
         /// Path to the directory with the manifest. Defaults to the current working directory.
@@ -406,6 +779,139 @@ pub enum EdgeAppCommands {
         /// Delete settings that exist on the server but not in the manifest.
         #[arg(short, long)]
         delete_missing_settings: Option<bool>,
+
+        /// Channel to promote the new revision to.
+        #[arg(short, long, default_value = DEFAULT_CHANNEL)]
+        channel: String,
+
+        /// Publishes the new revision without promoting it to any channel.
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "channel")]
+        no_promote: Option<bool>,
+
+        /// Shows what would be deployed without changing anything on the server.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        dry_run: Option<bool>,
+
+        /// Enables JSON output for --dry-run.
+        #[arg(short, long, action = clap::ArgAction::SetTrue, requires = "dry_run")]
+        json: Option<bool>,
+
+        /// Hashes every file instead of reusing signatures from the local cache.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        no_cache: Option<bool>,
+
+        /// Uploads changed files whole, even if the server accepts changed chunks only.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        no_delta: Option<bool>,
+
+        /// Skips the build section of the manifest and uses its output directory as is.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        skip_build: Option<bool>,
+
+        /// Also skips files matched by .gitignore files.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        respect_gitignore: Option<bool>,
+    },
+    /// Downloads a deployed Edge App into a local directory and recreates its manifest.
+    Pull {
+        /// Id of the Edge App to pull.
+        #[arg(long)]
+        app_id: String,
+
+        /// Revision to pull. Defaults to the latest revision.
+        #[arg(short, long)]
+        revision: Option<u32>,
+
+        /// Directory to write the Edge App to. It must be empty unless --force is given.
+        dir: PathBuf,
+
+        /// Writes into a non-empty directory, overwriting files of the same name.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        force: Option<bool>,
+    },
+    /// Shows how the local Edge App differs from the latest deployed revision.
+    Status {
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+
+        /// Hashes every file instead of reusing signatures from the local cache.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        no_cache: Option<bool>,
+
+        /// Skips the build section of the manifest and uses its output directory as is.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        skip_build: Option<bool>,
+
+        /// Also skips files matched by .gitignore files.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        respect_gitignore: Option<bool>,
+    },
+    /// Lists added, removed, modified and renamed files, setting and metadata changes compared with a revision.
+    Diff {
+        /// Revision to compare with. Defaults to the latest revision.
+        #[arg(short, long)]
+        revision: Option<u32>,
+
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+
+        /// Hashes every file instead of reusing signatures from the local cache.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        no_cache: Option<bool>,
+
+        /// Skips the build section of the manifest and uses its output directory as is.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        skip_build: Option<bool>,
+
+        /// Also skips files matched by .gitignore files.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        respect_gitignore: Option<bool>,
+    },
+    /// Lists the files a deploy would upload, after applying ignore files.
+    LsFiles {
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+
+        /// Also skips files matched by .gitignore files.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        respect_gitignore: Option<bool>,
+    },
+    /// Shows the files, settings and metadata a deploy would change, without changing anything.
+    Plan {
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+
+        /// Hashes every file instead of reusing signatures from the local cache.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        no_cache: Option<bool>,
+
+        /// Skips the build section of the manifest and uses its output directory as is.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        skip_build: Option<bool>,
+
+        /// Also skips files matched by .gitignore files.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        respect_gitignore: Option<bool>,
     },
     /// Deletes an Edge App. This cannot be undone.
     Delete {
@@ -445,6 +951,60 @@ pub enum EdgeAppSettingsCommands {
     },
 }
 
+#[derive(Subcommand, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EdgeAppChannelCommands {
+    /// Lists the release channels of the Edge App and the revisions they point to.
+    List {
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+    },
+    /// Points a channel at an existing revision.
+    Promote {
+        /// Revision to promote.
+        revision: u32,
+
+        /// Channel to promote the revision to.
+        #[arg(long, default_value = DEFAULT_CHANNEL)]
+        to: String,
+
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EdgeAppVersionCommands {
+    /// Lists the revisions of the Edge App, newest first.
+    List {
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+    },
+    /// Shows a revision and its file tree.
+    Show {
+        /// Revision to show.
+        revision: u32,
+
+        /// Path to the directory with the manifest. Defaults to the current working directory.
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Enables JSON output.
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        json: Option<bool>,
+    },
+}
+
 #[derive(Subcommand, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum EdgeAppInstanceCommands {
     /// Lists Edge App instances.
@@ -516,20 +1076,11 @@ pub fn get_screen_name(
 ) -> Result<String, CommandError> {
     let target_screen = screen_command.get(id)?;
 
-    if let Some(screens) = target_screen.value.as_array() {
-        if screens.is_empty() {
-            error!("Screen could not be found.");
-            return Err(CommandError::MissingField);
-        }
-
-        return if let Some(name) = screens[0]["name"].as_str() {
-            Ok(name.to_string())
-        } else {
-            Err(CommandError::MissingField)
-        };
-    }
-
-    Err(CommandError::MissingField)
+    let Some(screen) = target_screen.screens.first() else {
+        error!("Screen could not be found.");
+        return Err(CommandError::MissingField);
+    };
+    screen.name.clone().ok_or(CommandError::MissingField)
 }
 
 pub fn get_asset_title(
@@ -538,57 +1089,64 @@ pub fn get_asset_title(
 ) -> Result<String, CommandError> {
     let target_asset = asset_command.get(id)?;
 
-    if let Some(assets) = target_asset.value.as_array() {
-        if assets.is_empty() {
-            error!("Asset could not be found.");
-            return Err(CommandError::MissingField);
-        }
-
-        return if let Some(name) = assets[0]["title"].as_str() {
-            Ok(name.to_string())
-        } else {
-            Err(CommandError::MissingField)
-        };
-    }
-
-    Err(CommandError::MissingField)
+    let Some(asset) = target_asset.assets.first() else {
+        error!("Asset could not be found.");
+        return Err(CommandError::MissingField);
+    };
+    asset.title.clone().ok_or(CommandError::MissingField)
 }
 
 pub fn handle_cli(cli: &Cli) {
+    let config = cli.network.config();
+    if let Err(e) = config.validate() {
+        error!("{e}");
+        std::process::exit(1);
+    }
+    let cli_config = CliConfig::load(exit_on_config_error);
+
     match &cli.command {
-        Commands::Login {} => {
+        Commands::Login { encrypt } => {
             print!("Enter your API Token: ");
             std::io::stdout().flush().unwrap();
             let token = read_password().unwrap();
-            match verify_and_store_token(&token, &Config::default().url) {
+            let result = if encrypt.unwrap_or(false) {
+                let passphrase = read_new_passphrase_or_exit();
+                verify_and_store_encrypted_token(&token, &config, &passphrase)
+            } else {
+                match verify_and_store_token(&token, &config) {
+                    Err(AuthenticationError::KeyringUnavailable(e)) => {
+                        warn!("The OS keyring is unavailable ({e}), encrypting the token in a file instead.");
+                        let passphrase = read_new_passphrase_or_exit();
+                        store_encrypted_token(&token, &passphrase)
+                    }
+                    result => result,
+                }
+            };
+            match result {
                 Ok(()) => {
                     info!("Login credentials have been saved.");
                     std::process::exit(0);
                 }
-
-                Err(e) => match e {
-                    AuthenticationError::WrongCredentials => {
-                        error!("Token verification failed.");
-                        std::process::exit(1);
-                    }
-                    _ => {
-                        error!("Error occurred: {e:?}");
-                        std::process::exit(1);
-                    }
-                },
+                Err(e) => {
+                    error!("{}", get_verification_error_message(&e));
+                    std::process::exit(1);
+                }
             }
         }
-        Commands::Screen(command) => handle_cli_screen_command(command),
-        Commands::Asset(command) => handle_cli_asset_command(command),
-        Commands::EdgeApp(command) => handle_cli_edge_app_command(command),
-        Commands::Playlist(command) => handle_cli_playlist_command(command),
+        Commands::Auth(command) => handle_cli_auth_command(command, &config, &cli_config),
+        Commands::Whoami { json } => handle_cli_whoami_command(json, &config, &cli_config),
+        Commands::Screen(command) => handle_cli_screen_command(command, &config, &cli_config),
+        Commands::Asset(command) => handle_cli_asset_command(command, &config, &cli_config),
+        Commands::EdgeApp(command) => handle_cli_edge_app_command(command, &config, &cli_config),
+        Commands::Playlist(command) => handle_cli_playlist_command(command, &config, &cli_config),
+        Commands::Config(command) => handle_cli_config_command(command, &cli_config),
         Commands::Logout {} => {
             Authentication::remove_token().expect("Failed to remove token.");
             info!("Logout successful.");
             std::process::exit(0);
         }
         Commands::Mcp {} => {
-            handle_cli_mcp_command();
+            handle_cli_mcp_command(&config);
         }
         Commands::PrintHelpMarkdown {} => {
             clap_markdown::print_help_markdown::<Cli>();
@@ -596,20 +1154,154 @@ pub fn handle_cli(cli: &Cli) {
     }
 }
 
-pub fn handle_cli_mcp_command() {
-    use crate::mcp::ScreenlyMcpServer;
+fn exit_on_config_error(e: &cli_config::CliConfigError) -> ! {
+    error!("Invalid configuration: {e}");
+    std::process::exit(1);
+}
 
-    let server = match ScreenlyMcpServer::new() {
-        Ok(s) => s,
-        Err(e) => {
-            error!("Failed to initialize MCP server: {}", e);
+/// Reads a new passphrase from SCREENLY_TOKEN_PASSPHRASE, or prompts for it twice.
+fn read_new_passphrase_or_exit() -> String {
+    if let Ok(passphrase) = env::var(PASSPHRASE_ENV) {
+        return passphrase;
+    }
+
+    let read = |prompt: &str| {
+        prompt_password(prompt).unwrap_or_else(|e| {
+            error!("Failed to read the passphrase: {e}");
             std::process::exit(1);
-        }
+        })
     };
-
-    let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
-    if let Err(e) = rt.block_on(server.run()) {
-        error!("MCP server error: {}", e);
+    let passphrase = read("Choose a passphrase: ");
+    if passphrase.is_empty() {
+        error!("The passphrase must not be empty.");
+        std::process::exit(1);
+    }
+    if read("Repeat the passphrase: ") != passphrase {
+        error!("The passphrases don't match.");
+        std::process::exit(1);
+    }
+    passphrase
+}
+
+pub fn handle_cli_auth_command(command: &AuthCommands, config: &Config, cli_config: &CliConfig) {
+    match command {
+        AuthCommands::Status { json } => {
+            let status = AuthStatus::new(&get_authentication(config));
+            if status.is_exposed() {
+                warn!(
+                    "The token file {} is readable by other users. Run `screenly login` again to restrict it.",
+                    status.location
+                );
+            }
+            handle_command_execution_result(Ok(status), &cli_config.json(json));
+        }
+    }
+}
+
+/// Returns the configuration file that `config set` and `config unset` modify.
+fn config_file_path(project: &Option<bool>) -> Result<PathBuf, cli_config::CliConfigError> {
+    if project.unwrap_or(false) {
+        Ok(env::current_dir()?.join(cli_config::PROJECT_CONFIG_FILENAME))
+    } else {
+        cli_config::user_config_path()
+    }
+}
+
+pub fn handle_cli_config_command(command: &ConfigCommands, cli_config: &CliConfig) {
+    let result = match command {
+        ConfigCommands::Get { key } => match cli_config.get(key) {
+            Ok(Some((value, _))) => {
+                println!("{value}");
+                Ok(())
+            }
+            Ok(None) => {
+                error!("\"{key}\" is not set.");
+                std::process::exit(1);
+            }
+            Err(e) => Err(e),
+        },
+        ConfigCommands::Set {
+            key,
+            value,
+            project,
+        } => config_file_path(project).and_then(|path| {
+            cli_config::set_value(&path, key, value)?;
+            info!("Set \"{key}\" in {}.", path.display());
+            Ok(())
+        }),
+        ConfigCommands::Unset { key, project } => config_file_path(project).and_then(|path| {
+            if cli_config::unset_value(&path, key)? {
+                info!("Removed \"{key}\" from {}.", path.display());
+            } else {
+                warn!("\"{key}\" is not set in {}.", path.display());
+            }
+            Ok(())
+        }),
+        ConfigCommands::List { json } => cli_config.list().map(|entries| {
+            handle_command_execution_result(Ok(entries), &cli_config.json(json));
+        }),
+    };
+
+    if let Err(e) = result {
+        error!("{e}");
+        std::process::exit(1);
+    }
+}
+
+/// Returns a user-friendly message for a failed token verification.
+fn get_verification_error_message(e: &AuthenticationError) -> String {
+    match e {
+        AuthenticationError::WrongCredentials => {
+            "Token verification failed: the token is invalid or has been revoked.".to_string()
+        }
+        AuthenticationError::Unreachable(url, _) => {
+            format!("Token verification failed: {e}. Check your network connection and proxy settings (API: {url}).")
+        }
+        _ => format!("Token verification failed: {e}."),
+    }
+}
+
+pub fn handle_cli_whoami_command(json: &Option<bool>, config: &Config, cli_config: &CliConfig) {
+    let authentication = get_authentication(config);
+    let status = AuthStatus::new(&authentication);
+
+    match verify_token(&authentication.token, &authentication.config) {
+        Ok(()) => {
+            handle_command_execution_result(
+                Api::new(authentication)
+                    .get_token_owner()
+                    .map(|owner| status.with_validity(true).with_owner(owner)),
+                &cli_config.json(json),
+            );
+        }
+        Err(AuthenticationError::WrongCredentials) => {
+            handle_command_execution_result(
+                Ok(status.with_validity(false)),
+                &cli_config.json(json),
+            );
+            std::process::exit(1);
+        }
+        Err(e) => {
+            error!("{}", get_verification_error_message(&e));
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn handle_cli_mcp_command(config: &Config) {
+    use screenly::mcp::ScreenlyMcpServer;
+
+    let server = match Authentication::from_config(config.clone()) {
+        Ok(auth) => ScreenlyMcpServer::with_authentication(auth),
+        Err(e) => {
+            error!("Failed to initialize MCP server: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
+    if let Err(e) = rt.block_on(server.run()) {
+        error!("MCP server error: {}", e);
         std::process::exit(1);
     }
 }
@@ -628,52 +1320,181 @@ fn get_user_input() -> String {
     user_input.trim().to_string()
 }
 
-pub fn handle_cli_screen_command(command: &ScreenCommands) {
-    let authentication = get_authentication();
+fn prompt_setting_deletion(setting_name: &str) -> String {
+    println!("It seems like the setting \"{setting_name}\" is absent in the YAML file, but it exists on the server. If you wish to skip deletion, you can leave the input blank. Warning, deleting the setting will drop all the associated values. To proceed with deletion, please confirm the setting name by writing it down: ");
+    get_user_input()
+}
+
+fn select_screens_or_exit(
+    screen_command: &commands::screen::ScreenCommand,
+    selector: &ScreenSelectorArgs,
+) -> commands::Screens {
+    match screen_command.select(&selector.into()) {
+        Ok(screens) if screens.is_empty() => {
+            error!("No screens match the selection.");
+            std::process::exit(1);
+        }
+        Ok(screens) => screens,
+        Err(e) => {
+            error!("Error occurred: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn confirm_bulk_screen_action_or_exit(action: &str, screens: &commands::Screens) {
+    info!(
+        "You are about to {action} the following {} screen(s):",
+        screens.ids().len()
+    );
+    println!("{}", screens.format(OutputType::HumanReadable));
+    info!("Type \"yes\" to continue: ");
+    if get_user_input() != "yes" {
+        error!("Aborting.");
+        std::process::exit(1);
+    }
+}
+
+pub fn handle_cli_screen_command(
+    command: &ScreenCommands,
+    config: &Config,
+    cli_config: &CliConfig,
+) {
+    let authentication = get_authentication(config);
     let screen_command = commands::screen::ScreenCommand::new(authentication);
 
     match command {
         ScreenCommands::List { json } => {
-            handle_command_execution_result(screen_command.list(), json);
+            handle_command_execution_result(screen_command.list(), &cli_config.json(json));
         }
         ScreenCommands::Get { uuid, json } => {
-            handle_command_execution_result(screen_command.get(uuid), json);
+            handle_command_execution_result(
+                screen_command.get_details(uuid),
+                &cli_config.json(json),
+            );
         }
         ScreenCommands::Add { pin, name, json } => {
-            handle_command_execution_result(screen_command.add(pin, name.clone()), json);
+            handle_command_execution_result(
+                screen_command.add(pin, name.clone()),
+                &cli_config.json(json),
+            );
         }
-        ScreenCommands::Delete { uuid } => {
-            match get_screen_name(uuid, &screen_command) {
-                Ok(name) => {
-                    info!("You are about to delete the screen named \"{name}\".  This operation cannot be reversed.");
-                    info!("Enter the screen name to confirm the screen deletion: ");
-                    if name != get_user_input() {
-                        error!("The name you entered is incorrect. Aborting.");
+        ScreenCommands::AddBatch { path, json } => match screen_command.add_batch(path) {
+            Ok(results) => {
+                let failed = results.has_failures();
+                handle_command_execution_result(Ok(results), &cli_config.json(json));
+                if failed {
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => handle_command_execution_result::<commands::ScreenBatchResults>(
+                Err(e),
+                &cli_config.json(json),
+            ),
+        },
+        ScreenCommands::Rename { uuid, name, json } => {
+            handle_command_execution_result(
+                screen_command.rename(uuid, name),
+                &cli_config.json(json),
+            );
+        }
+        ScreenCommands::Enable { selector, json } => {
+            let screens = select_screens_or_exit(&screen_command, selector);
+            handle_command_execution_result(
+                screen_command.set_enabled(&screens.ids(), true),
+                &cli_config.json(json),
+            );
+        }
+        ScreenCommands::Disable {
+            selector,
+            yes,
+            json,
+        } => {
+            let screens = select_screens_or_exit(&screen_command, selector);
+            if !yes.unwrap_or(false) {
+                confirm_bulk_screen_action_or_exit("disable", &screens);
+            }
+            handle_command_execution_result(
+                screen_command.set_enabled(&screens.ids(), false),
+                &cli_config.json(json),
+            );
+        }
+        ScreenCommands::Delete { selector, yes } => {
+            if let (Some(uuid), false) = (selector.single_uuid(), yes.unwrap_or(false)) {
+                match get_screen_name(uuid, &screen_command) {
+                    Ok(name) => {
+                        info!("You are about to delete the screen named \"{name}\".  This operation cannot be reversed.");
+                        info!("Enter the screen name to confirm the screen deletion: ");
+                        if name != get_user_input() {
+                            error!("The name you entered is incorrect. Aborting.");
+                            std::process::exit(1);
+                        }
+                    }
+                    Err(e) => {
+                        error!("Error occurred: {e}");
                         std::process::exit(1);
                     }
                 }
-                Err(e) => {
-                    error!("Error occurred: {e}");
-                    std::process::exit(1);
-                }
             }
 
-            match screen_command.delete(uuid) {
-                Ok(()) => {
-                    info!("Screen deleted successfully.");
-                    std::process::exit(0);
+            let screens = select_screens_or_exit(&screen_command, selector);
+            if selector.single_uuid().is_none() && !yes.unwrap_or(false) {
+                confirm_bulk_screen_action_or_exit("delete", &screens);
+            }
+
+            let mut failed = false;
+            for uuid in screens.ids() {
+                match screen_command.delete(&uuid) {
+                    Ok(()) => info!("Screen {uuid} deleted successfully."),
+                    Err(e) => {
+                        error!("Failed to delete screen {uuid}: {e:?}");
+                        failed = true;
+                    }
                 }
-                Err(e) => {
-                    error!("Error occurred: {e:?}");
-                    std::process::exit(1);
+            }
+            std::process::exit(if failed { 1 } else { 0 });
+        }
+        ScreenCommands::Monitor {
+            json,
+            interval,
+            stale_after,
+            exec,
+        } => {
+            let mut monitor = ScreenMonitor::new(*stale_after);
+            loop {
+                match poll_screens(&screen_command) {
+                    Ok(screens) => {
+                        for event in monitor.observe(&screens) {
+                            if cli_config.json(json).unwrap_or(false) {
+                                println!("{}", serde_json::to_string(&event).unwrap());
+                            } else {
+                                println!("{event}");
+                            }
+                            if let Some(command) = exec {
+                                if let Err(e) = run_event_hook(command, &event) {
+                                    error!("{e}");
+                                }
+                            }
+                        }
+                    }
+                    Err(CommandError::Authentication(_)) => {
+                        error!("Authentication error occurred. Please use login command to authenticate.");
+                        std::process::exit(1);
+                    }
+                    Err(e) => error!("Failed to fetch screens: {e}"),
                 }
+                std::thread::sleep(Duration::from_secs(*interval));
             }
         }
     }
 }
 
-pub fn handle_cli_playlist_command(command: &PlaylistCommands) {
-    let playlist_command = PlaylistCommand::new(get_authentication());
+pub fn handle_cli_playlist_command(
+    command: &PlaylistCommands,
+    config: &Config,
+    cli_config: &CliConfig,
+) {
+    let playlist_command = PlaylistCommand::new(get_authentication(config));
     match command {
         PlaylistCommands::Create {
             json,
@@ -681,12 +1502,18 @@ pub fn handle_cli_playlist_command(command: &PlaylistCommands) {
             predicate,
         } => {
             handle_command_execution_result(
-                playlist_command.create(title, &predicate.clone().unwrap_or("TRUE".to_owned())),
-                json,
+                playlist_command.create(
+                    title,
+                    &predicate
+                        .clone()
+                        .or_else(|| cli_config.playlist_predicate())
+                        .unwrap_or("TRUE".to_owned()),
+                ),
+                &cli_config.json(json),
             );
         }
         PlaylistCommands::List { json } => {
-            handle_command_execution_result(playlist_command.list(), json);
+            handle_command_execution_result(playlist_command.list(), &cli_config.json(json));
         }
         PlaylistCommands::Get { uuid } => {
             let playlist_file = playlist_command.get_playlist_file(uuid);
@@ -718,9 +1545,11 @@ pub fn handle_cli_playlist_command(command: &PlaylistCommands) {
                 playlist_command.append_asset(
                     uuid,
                     asset_uuid,
-                    (*duration).unwrap_or(DEFAULT_ASSET_DURATION),
+                    duration
+                        .or(cli_config.asset_duration())
+                        .unwrap_or(DEFAULT_ASSET_DURATION),
                 ),
-                json,
+                &cli_config.json(json),
             );
         }
         PlaylistCommands::Prepend {
@@ -733,9 +1562,11 @@ pub fn handle_cli_playlist_command(command: &PlaylistCommands) {
                 playlist_command.prepend_asset(
                     uuid,
                     asset_uuid,
-                    (*duration).unwrap_or(DEFAULT_ASSET_DURATION),
+                    duration
+                        .or(cli_config.asset_duration())
+                        .unwrap_or(DEFAULT_ASSET_DURATION),
                 ),
-                json,
+                &cli_config.json(json),
             );
         }
         PlaylistCommands::Update {} => {
@@ -758,19 +1589,73 @@ pub fn handle_cli_playlist_command(command: &PlaylistCommands) {
     }
 }
 
-pub fn handle_cli_asset_command(command: &AssetCommands) {
-    let authentication = get_authentication();
+fn read_secret_or_exit(
+    inline: Option<&str>,
+    from_stdin: bool,
+    from_env: Option<&str>,
+    prompt: &str,
+) -> String {
+    match read_secret(inline, from_stdin, from_env, prompt) {
+        Ok(secret) => secret,
+        Err(e) => {
+            error!("Failed to read the secret: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn handle_cli_asset_command(command: &AssetCommands, config: &Config, cli_config: &CliConfig) {
+    let authentication = get_authentication(config);
     let asset_command = commands::asset::AssetCommand::new(authentication);
 
     match command {
         AssetCommands::List { json } => {
-            handle_command_execution_result(asset_command.list(), json);
+            handle_command_execution_result(asset_command.list(), &cli_config.json(json));
         }
         AssetCommands::Get { uuid, json } => {
-            handle_command_execution_result(asset_command.get(uuid), json);
+            handle_command_execution_result(asset_command.get(uuid), &cli_config.json(json));
         }
-        AssetCommands::Add { path, title, json } => {
-            handle_command_execution_result(asset_command.add(path, title), json);
+        AssetCommands::Add {
+            path,
+            title,
+            json,
+            duration,
+            headers,
+            basic_auth,
+            bearer,
+            inject_js,
+        } => {
+            let mut headers = headers.clone();
+            if let Some((user, password)) = basic_auth {
+                let password = read_secret_or_exit(password.as_deref(), false, None, "Password: ");
+                let basic_auth = Credentials::new(user, &password);
+                headers.push(("Authorization".to_owned(), basic_auth.as_http_header()));
+            }
+            if let Some(token) = bearer {
+                let token = read_secret_or_exit(Some(token), false, None, "Bearer token: ");
+                headers.push(("Authorization".to_owned(), format!("Bearer {token}")));
+            }
+
+            let js_injection = match inject_js {
+                Some(js_path) => match bundle_js_injection(std::slice::from_ref(js_path), true) {
+                    Ok(js_code) => Some(js_code),
+                    Err(e) => {
+                        error!("Failed to retrieve JS injection code. Error: {e}");
+                        std::process::exit(1);
+                    }
+                },
+                None => None,
+            };
+
+            let options = AssetOptions {
+                duration: duration.or(cli_config.asset_duration()),
+                headers,
+                js_injection,
+            };
+            handle_command_execution_result(
+                asset_command.add(path, title, &options),
+                &cli_config.json(json),
+            );
         }
         AssetCommands::Delete { uuid } => {
             match get_asset_title(uuid, &asset_command) {
@@ -810,32 +1695,26 @@ pub fn handle_cli_asset_command(command: &AssetCommands) {
                 }
             }
         }
-        AssetCommands::InjectJs { uuid, path } => {
-            let js_code = if path.starts_with("http://") || path.starts_with("https://") {
-                match reqwest::blocking::get(path) {
-                    Ok(response) => match response.status() {
-                        StatusCode::OK => response.text().unwrap_or_default(),
-                        status => {
-                            error!("Failed to retrieve JS injection code. Wrong response status: {status}");
-                            std::process::exit(1);
-                        }
-                    },
-                    Err(e) => {
-                        error!("Failed to retrieve JS injection code. Error: {e}");
-                        std::process::exit(1);
-                    }
-                }
+        AssetCommands::InjectJs {
+            uuid,
+            paths,
+            no_lint,
+            clear,
+        } => {
+            let result = if clear.unwrap_or(false) {
+                asset_command.clear_js_injection(uuid)
             } else {
-                match fs::read_to_string(path) {
-                    Ok(text) => text,
+                let js_code = match bundle_js_injection(paths, !no_lint.unwrap_or(false)) {
+                    Ok(js_code) => js_code,
                     Err(e) => {
-                        error!("Failed to read file with JS injection code. Error: {e}");
+                        error!("Failed to retrieve JS injection code. Error: {e}");
                         std::process::exit(1);
                     }
-                }
+                };
+                asset_command.inject_js(uuid, &js_code)
             };
 
-            match asset_command.inject_js(uuid, &js_code) {
+            match result {
                 Ok(()) => {
                     info!("Asset updated successfully.");
                 }
@@ -845,6 +1724,18 @@ pub fn handle_cli_asset_command(command: &AssetCommands) {
                 }
             }
         }
+        AssetCommands::ShowJs { uuid } => match asset_command.get_js_injection(uuid) {
+            Ok(Some(js_code)) => {
+                println!("{js_code}");
+            }
+            Ok(None) => {
+                info!("No JavaScript code is injected into the asset.");
+            }
+            Err(e) => {
+                error!("Error occurred: {e:?}");
+                std::process::exit(1);
+            }
+        },
         AssetCommands::SetHeaders { uuid, headers } => {
             match asset_command.set_web_asset_headers(uuid, headers.headers.clone()) {
                 Ok(()) => {
@@ -856,8 +1747,19 @@ pub fn handle_cli_asset_command(command: &AssetCommands) {
                 }
             }
         }
-        AssetCommands::BasicAuth { uuid, credentials } => {
-            let basic_auth = Credentials::new(&credentials.0, &credentials.1);
+        AssetCommands::BasicAuth {
+            uuid,
+            credentials,
+            password_stdin,
+            from_env,
+        } => {
+            let password = read_secret_or_exit(
+                credentials.1.as_deref(),
+                password_stdin.unwrap_or(false),
+                from_env.as_deref(),
+                "Password: ",
+            );
+            let basic_auth = Credentials::new(&credentials.0, &password);
             match asset_command.update_web_asset_headers(
                 uuid,
                 vec![("Authorization".to_owned(), basic_auth.as_http_header())],
@@ -882,7 +1784,18 @@ pub fn handle_cli_asset_command(command: &AssetCommands) {
                 }
             }
         }
-        AssetCommands::BearerAuth { uuid, token } => {
+        AssetCommands::BearerAuth {
+            uuid,
+            token,
+            token_stdin,
+            from_env,
+        } => {
+            let token = read_secret_or_exit(
+                token.as_deref(),
+                token_stdin.unwrap_or(false),
+                from_env.as_deref(),
+                "Bearer token: ",
+            );
             match asset_command.update_web_asset_headers(
                 uuid,
                 vec![("Authorization".to_owned(), format!("Bearer {token}"))],
@@ -899,9 +1812,107 @@ pub fn handle_cli_asset_command(command: &AssetCommands) {
     }
 }
 
-pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
-    let authentication = get_authentication();
-    let edge_app_command = commands::edge_app::EdgeAppCommand::new(authentication);
+/// Deploys an Edge App, uploading its files on a runtime owned by the CLI. Returns `None`
+/// when only settings changed.
+fn deploy_edge_app(
+    edge_app_command: &commands::edge_app::EdgeAppCommand,
+    authentication: &Authentication,
+    path: Option<String>,
+    delete_missing_settings: Option<bool>,
+    channel: Option<&str>,
+) -> Result<Option<u32>, CommandError> {
+    let Some(deploy) = edge_app_command.prepare_deploy(path, delete_missing_settings)? else {
+        return Ok(None);
+    };
+    let client = authentication.client()?;
+    tokio::runtime::Runtime::new()?.block_on(edge_app_command.upload_files(&client, &deploy))?;
+    edge_app_command.finish_deploy(&deploy, channel).map(Some)
+}
+
+fn get_app_id_or_exit(
+    edge_app_command: &commands::edge_app::EdgeAppCommand,
+    path: Option<String>,
+) -> String {
+    match edge_app_command.get_app_id(path) {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Failed to read the Edge App id: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn handle_cli_edge_app_command(
+    command: &EdgeAppCommands,
+    config: &Config,
+    cli_config: &CliConfig,
+) {
+    let authentication = get_authentication(config);
+    let no_cache = match command {
+        EdgeAppCommands::Deploy { no_cache, .. }
+        | EdgeAppCommands::Plan { no_cache, .. }
+        | EdgeAppCommands::Status { no_cache, .. }
+        | EdgeAppCommands::Diff { no_cache, .. } => no_cache.unwrap_or(false),
+        _ => false,
+    };
+    let respect_gitignore = match command {
+        EdgeAppCommands::Deploy {
+            respect_gitignore, ..
+        }
+        | EdgeAppCommands::Plan {
+            respect_gitignore, ..
+        }
+        | EdgeAppCommands::Status {
+            respect_gitignore, ..
+        }
+        | EdgeAppCommands::Diff {
+            respect_gitignore, ..
+        }
+        | EdgeAppCommands::LsFiles {
+            respect_gitignore, ..
+        }
+        | EdgeAppCommands::Run {
+            respect_gitignore, ..
+        } => respect_gitignore.unwrap_or(false),
+        _ => false,
+    };
+    let edge_app_command = commands::edge_app::EdgeAppCommand::new(authentication.clone())
+        .with_setting_deletion_prompt(Box::new(prompt_setting_deletion))
+        .with_signature_cache(!no_cache)
+        .with_gitignore(respect_gitignore)
+        .with_build(!matches!(
+            command,
+            EdgeAppCommands::Deploy {
+                skip_build: Some(true),
+                ..
+            } | EdgeAppCommands::Plan {
+                skip_build: Some(true),
+                ..
+            } | EdgeAppCommands::Status {
+                skip_build: Some(true),
+                ..
+            } | EdgeAppCommands::Diff {
+                skip_build: Some(true),
+                ..
+            } | EdgeAppCommands::Run {
+                skip_build: Some(true),
+                ..
+            }
+        ))
+        .with_delta_uploads(!matches!(
+            command,
+            EdgeAppCommands::Deploy {
+                no_delta: Some(true),
+                ..
+            }
+        ))
+        .with_live_reload(!matches!(
+            command,
+            EdgeAppCommands::Run {
+                no_live_reload: Some(true),
+                ..
+            }
+        ));
 
     match command {
         EdgeAppCommands::Create {
@@ -915,13 +1926,14 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
                 commands::edge_app::EdgeAppCommand::create
             };
 
-            let manifest_path = match transform_edge_app_path_to_manifest(path) {
-                Ok(path) => path,
-                Err(e) => {
-                    eprintln!("Failed to create Edge App: {e}.");
-                    std::process::exit(1);
-                }
-            };
+            let manifest_path =
+                match transform_edge_app_path_to_manifest(&cli_config.edge_app_path(path)) {
+                    Ok(path) => path,
+                    Err(e) => {
+                        eprintln!("Failed to create Edge App: {e}.");
+                        std::process::exit(1);
+                    }
+                };
 
             match create_func(&edge_app_command, name, manifest_path.as_path()) {
                 Ok(()) => {
@@ -935,26 +1947,167 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
         }
 
         EdgeAppCommands::List { json } => {
-            handle_command_execution_result(edge_app_command.list(), json);
+            handle_command_execution_result(edge_app_command.list(), &cli_config.json(json));
+        }
+        EdgeAppCommands::Deploy {
+            path,
+            dry_run: Some(true),
+            json,
+            ..
+        }
+        | EdgeAppCommands::Plan { path, json, .. } => {
+            handle_command_execution_result(
+                edge_app_command.plan(cli_config.edge_app_path(path)),
+                &cli_config.json(json),
+            );
         }
         EdgeAppCommands::Deploy {
             path,
             delete_missing_settings,
-        } => match edge_app_command.deploy(path.clone(), *delete_missing_settings) {
+            channel,
+            no_promote,
+            ..
+        } => {
+            let channel = (!no_promote.unwrap_or(false)).then_some(channel.as_str());
+            match deploy_edge_app(
+                &edge_app_command,
+                &authentication,
+                cli_config.edge_app_path(path),
+                *delete_missing_settings,
+                channel,
+            ) {
+                Ok(None) => {
+                    println!("Edge App settings updated. No new revision was needed.")
+                }
+                Ok(Some(revision)) => match channel {
+                    Some(channel) => println!(
+                        "Edge App successfully deployed. Revision: {revision}, channel: {channel}."
+                    ),
+                    None => println!(
+                        "Edge App successfully deployed. Revision: {revision} (not promoted)."
+                    ),
+                },
+                Err(e) => {
+                    eprintln!("Failed to upload Edge App: {e}.");
+                    std::process::exit(1);
+                }
+            }
+        }
+        EdgeAppCommands::Channel(command) => match command {
+            EdgeAppChannelCommands::List { path, json } => {
+                let app_id = get_app_id_or_exit(&edge_app_command, cli_config.edge_app_path(path));
+                handle_command_execution_result(
+                    edge_app_command.list_channels(&app_id),
+                    &cli_config.json(json),
+                );
+            }
+            EdgeAppChannelCommands::Promote { revision, to, path } => {
+                let app_id = get_app_id_or_exit(&edge_app_command, cli_config.edge_app_path(path));
+                match edge_app_command.promote_version(&app_id, *revision, to) {
+                    Ok(()) => {
+                        println!("Revision {revision} promoted to channel {to}.");
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to promote revision: {e}.");
+                        std::process::exit(1);
+                    }
+                }
+            }
+        },
+        EdgeAppCommands::Pull {
+            app_id,
+            revision,
+            dir,
+            force,
+        } => match edge_app_command.pull(app_id, *revision, dir, force.unwrap_or(false)) {
             Ok(revision) => {
-                println!("Edge App successfully deployed. Revision: {revision}.");
+                println!(
+                    "Edge App revision {revision} pulled into {}.",
+                    dir.display()
+                );
             }
             Err(e) => {
-                eprintln!("Failed to upload Edge App: {e}.");
+                eprintln!("Failed to pull Edge App: {e}.");
                 std::process::exit(1);
             }
         },
+        EdgeAppCommands::LsFiles { path, json, .. } => {
+            handle_command_execution_result(
+                edge_app_command.list_files(cli_config.edge_app_path(path)),
+                &cli_config.json(json),
+            );
+        }
+        EdgeAppCommands::Status { path, json, .. } => {
+            match edge_app_command.diff(cli_config.edge_app_path(path), None) {
+                Ok(diff) if diff.is_empty() && !cli_config.json(json).unwrap_or(false) => {
+                    match diff.revision {
+                        Some(revision) => println!("Up to date with revision {revision}."),
+                        None => println!("The Edge App has no deployed revisions."),
+                    }
+                }
+                result => handle_command_execution_result(result, &cli_config.json(json)),
+            }
+        }
+        EdgeAppCommands::Diff {
+            revision,
+            path,
+            json,
+            ..
+        } => {
+            handle_command_execution_result(
+                edge_app_command.diff(cli_config.edge_app_path(path), *revision),
+                &cli_config.json(json),
+            );
+        }
+        EdgeAppCommands::Version(command) => match command {
+            EdgeAppVersionCommands::List { path, json } => {
+                let app_id = get_app_id_or_exit(&edge_app_command, cli_config.edge_app_path(path));
+                handle_command_execution_result(
+                    edge_app_command.list_versions(&app_id),
+                    &cli_config.json(json),
+                );
+            }
+            EdgeAppVersionCommands::Show {
+                revision,
+                path,
+                json,
+            } => {
+                let app_id = get_app_id_or_exit(&edge_app_command, cli_config.edge_app_path(path));
+                handle_command_execution_result(
+                    edge_app_command.show_version(&app_id, *revision),
+                    &cli_config.json(json),
+                );
+            }
+        },
+        EdgeAppCommands::Rollback {
+            revision,
+            channel,
+            path,
+        } => {
+            let app_id = get_app_id_or_exit(&edge_app_command, cli_config.edge_app_path(path));
+            match edge_app_command.promote_version(&app_id, *revision, channel) {
+                Ok(()) => {
+                    println!("Channel {channel} rolled back to revision {revision}.");
+                }
+                Err(e) => {
+                    eprintln!("Failed to roll back: {e}.");
+                    std::process::exit(1);
+                }
+            }
+        }
         EdgeAppCommands::Setting(command) => match command {
             EdgeAppSettingsCommands::List { path, json } => {
-                handle_command_execution_result(edge_app_command.list_settings(path.clone()), json);
+                handle_command_execution_result(
+                    edge_app_command.list_settings(cli_config.edge_app_path(path)),
+                    &cli_config.json(json),
+                );
             }
             EdgeAppSettingsCommands::Set { setting_pair, path } => {
-                match edge_app_command.set_setting(path.clone(), &setting_pair.0, &setting_pair.1) {
+                match edge_app_command.set_setting(
+                    cli_config.edge_app_path(path),
+                    &setting_pair.0,
+                    &setting_pair.1,
+                ) {
                     Ok(()) => {
                         println!("Edge App setting successfully set.");
                     }
@@ -966,7 +2119,7 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
             }
         },
         EdgeAppCommands::Delete { path } => {
-            let actual_app_id = match edge_app_command.get_app_id(path.clone()) {
+            let actual_app_id = match edge_app_command.get_app_id(cli_config.edge_app_path(path)) {
                 Ok(id) => id,
                 Err(e) => {
                     error!("Error calling delete Edge App: {e}");
@@ -992,7 +2145,9 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
                 Ok(()) => {
                     println!("Edge App Deletion in Progress.\nRequest to delete the Edge App has been received and is now being processed. The deletion is marked for asynchronous handling, so it won't happen instantly.");
 
-                    let manifest_path = match transform_edge_app_path_to_manifest(path) {
+                    let manifest_path = match transform_edge_app_path_to_manifest(
+                        &cli_config.edge_app_path(path),
+                    ) {
                         Ok(path) => path,
                         Err(e) => {
                             eprintln!("Failed to delete Edge App: {e}.");
@@ -1019,7 +2174,7 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
             }
         }
         EdgeAppCommands::Rename { path, name } => {
-            let actual_app_id = match edge_app_command.get_app_id(path.clone()) {
+            let actual_app_id = match edge_app_command.get_app_id(cli_config.edge_app_path(path)) {
                 Ok(id) => id,
                 Err(e) => {
                     error!("Error renaming Edge App: {e}");
@@ -1040,6 +2195,7 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
             path,
             secrets,
             generate_mock_data,
+            ..
         } => {
             let secrets = if let Some(secret_pairs) = secrets {
                 secret_pairs.secrets.clone()
@@ -1048,13 +2204,14 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
             };
 
             if generate_mock_data.unwrap_or(false) {
-                let manifest_path = match transform_edge_app_path_to_manifest(path) {
-                    Ok(path) => path,
-                    Err(e) => {
-                        eprintln!("Failed to generate mock data: {e}.");
-                        std::process::exit(1);
-                    }
-                };
+                let manifest_path =
+                    match transform_edge_app_path_to_manifest(&cli_config.edge_app_path(path)) {
+                        Ok(path) => path,
+                        Err(e) => {
+                            eprintln!("Failed to generate mock data: {e}.");
+                            std::process::exit(1);
+                        }
+                    };
 
                 match edge_app_command.generate_mock_data(&manifest_path) {
                     Ok(_) => std::process::exit(0),
@@ -1065,7 +2222,7 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
                 }
             }
 
-            let path = match path {
+            let path = match cli_config.edge_app_path(path) {
                 Some(path) => PathBuf::from(path),
                 None => env::current_dir().unwrap(),
             };
@@ -1078,13 +2235,14 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
             edge_app_command.run(path.as_path(), secrets).unwrap();
         }
         EdgeAppCommands::Validate { path } => {
-            let manifest_path = match transform_edge_app_path_to_manifest(path) {
-                Ok(path) => path,
-                Err(e) => {
-                    eprintln!("Failed to validate manifest file: {e}.");
-                    std::process::exit(1);
-                }
-            };
+            let manifest_path =
+                match transform_edge_app_path_to_manifest(&cli_config.edge_app_path(path)) {
+                    Ok(path) => path,
+                    Err(e) => {
+                        eprintln!("Failed to validate manifest file: {e}.");
+                        std::process::exit(1);
+                    }
+                };
             match EdgeAppManifest::ensure_manifest_is_valid(&manifest_path) {
                 Ok(()) => {
                     println!("Manifest file is valid.");
@@ -1094,13 +2252,15 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
                     std::process::exit(1);
                 }
             }
-            let instance_manifest_path = match transform_instance_path_to_instance_manifest(path) {
-                Ok(path) => path,
-                Err(e) => {
-                    eprintln!("Failed to build instance manifest filepath: {e}.");
-                    std::process::exit(1);
-                }
-            };
+            let instance_manifest_path =
+                match transform_instance_path_to_instance_manifest(&cli_config.edge_app_path(path))
+                {
+                    Ok(path) => path,
+                    Err(e) => {
+                        eprintln!("Failed to build instance manifest filepath: {e}.");
+                        std::process::exit(1);
+                    }
+                };
 
             if !instance_manifest_path.exists() {
                 println!("Instance manifest file does not exist.");
@@ -1144,39 +2304,42 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
         }
         EdgeAppCommands::Instance(command) => match command {
             EdgeAppInstanceCommands::List { path, json } => {
-                let actual_app_id = match edge_app_command.get_app_id(path.clone()) {
-                    Ok(id) => id,
-                    Err(e) => {
-                        error!("Error calling list instances: {e}");
-                        std::process::exit(1);
-                    }
-                };
+                let actual_app_id =
+                    match edge_app_command.get_app_id(cli_config.edge_app_path(path)) {
+                        Ok(id) => id,
+                        Err(e) => {
+                            error!("Error calling list instances: {e}");
+                            std::process::exit(1);
+                        }
+                    };
                 handle_command_execution_result(
                     edge_app_command.list_instances(&actual_app_id),
-                    json,
+                    &cli_config.json(json),
                 );
             }
             EdgeAppInstanceCommands::Create { path, name } => {
-                let actual_app_id = match edge_app_command.get_app_id(path.clone()) {
-                    Ok(id) => id,
-                    Err(e) => {
-                        error!("Error calling create instance: {e}");
-                        std::process::exit(1);
-                    }
-                };
+                let actual_app_id =
+                    match edge_app_command.get_app_id(cli_config.edge_app_path(path)) {
+                        Ok(id) => id,
+                        Err(e) => {
+                            error!("Error calling create instance: {e}");
+                            std::process::exit(1);
+                        }
+                    };
                 let new_name = match name {
                     Some(name) => name,
                     None => "New Edge App instance",
                 };
 
-                let instance_manifest_path =
-                    match transform_instance_path_to_instance_manifest(path) {
-                        Ok(path) => path,
-                        Err(e) => {
-                            eprintln!("Failed to create Edge App instance: {e}.");
-                            std::process::exit(1);
-                        }
-                    };
+                let instance_manifest_path = match transform_instance_path_to_instance_manifest(
+                    &cli_config.edge_app_path(path),
+                ) {
+                    Ok(path) => path,
+                    Err(e) => {
+                        eprintln!("Failed to create Edge App instance: {e}.");
+                        std::process::exit(1);
+                    }
+                };
 
                 match edge_app_command.create_instance(
                     &instance_manifest_path,
@@ -1194,7 +2357,7 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
             }
             EdgeAppInstanceCommands::Delete { path } => {
                 let actual_installation_id =
-                    match edge_app_command.get_installation_id(path.clone()) {
+                    match edge_app_command.get_installation_id(cli_config.edge_app_path(path)) {
                         Ok(_installation_id) => _installation_id,
                         Err(e) => {
                             error!("Error calling delete setting: {e}");
@@ -1202,20 +2365,21 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
                         }
                     };
 
-                let instance_manifest_path =
-                    match transform_instance_path_to_instance_manifest(path) {
-                        Ok(path) => match path.to_str() {
-                            Some(path) => path.to_string(),
-                            None => {
-                                eprintln!("Failed to delete Edge App instance: invalid path.");
-                                std::process::exit(1);
-                            }
-                        },
-                        Err(e) => {
-                            eprintln!("Failed to delete Edge App instance: {e:?}");
+                let instance_manifest_path = match transform_instance_path_to_instance_manifest(
+                    &cli_config.edge_app_path(path),
+                ) {
+                    Ok(path) => match path.to_str() {
+                        Some(path) => path.to_string(),
+                        None => {
+                            eprintln!("Failed to delete Edge App instance: invalid path.");
                             std::process::exit(1);
                         }
-                    };
+                    },
+                    Err(e) => {
+                        eprintln!("Failed to delete Edge App instance: {e:?}");
+                        std::process::exit(1);
+                    }
+                };
 
                 match edge_app_command
                     .delete_instance(&actual_installation_id, instance_manifest_path)
@@ -1230,7 +2394,7 @@ pub fn handle_cli_edge_app_command(command: &EdgeAppCommands) {
                 }
             }
             EdgeAppInstanceCommands::Update { path } => {
-                match edge_app_command.update_instance(path.clone()) {
+                match edge_app_command.update_instance(cli_config.edge_app_path(path)) {
                     Ok(()) => {
                         println!("Edge App instance successfully updated.");
                     }
@@ -1252,7 +2416,7 @@ mod tests {
     use tempfile::tempdir;
 
     use super::*;
-    use crate::authentication::Config;
+    use screenly::authentication::Config;
 
     #[test]
     fn test_get_screen_name_should_return_correct_screen_name() {
@@ -1303,6 +2467,83 @@ mod tests {
         assert_eq!(new_path, dir_path.join("screenly.yml"));
     }
 
+    #[test]
+    fn test_parse_user_credentials_should_split_optional_password() {
+        assert_eq!(
+            parse_user_credentials("user").unwrap(),
+            ("user".to_string(), None)
+        );
+        assert_eq!(
+            parse_user_credentials("user=pass=word").unwrap(),
+            ("user".to_string(), Some("pass=word".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_should_support_units() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("10m").unwrap(), Duration::from_secs(600));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_duration("1d").unwrap(), Duration::from_secs(86400));
+        assert!(parse_duration("ten minutes").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn test_network_args_should_override_environment() {
+        temp_env::with_var("SCREENLY_PROXY", Some("http://env-proxy:3128"), || {
+            let cli = Cli::try_parse_from([
+                "screenly",
+                "screen",
+                "list",
+                "--proxy",
+                "http://proxy:3128",
+                "--ca-cert",
+                "ca.pem",
+            ])
+            .unwrap();
+            let config = cli.network.config();
+            assert_eq!(config.proxy.as_deref(), Some("http://proxy:3128"));
+            assert_eq!(config.ca_cert, Some(PathBuf::from("ca.pem")));
+
+            let cli = Cli::try_parse_from(["screenly", "screen", "list"]).unwrap();
+            assert_eq!(
+                cli.network.config().proxy.as_deref(),
+                Some("http://env-proxy:3128")
+            );
+        });
+    }
+
+    #[test]
+    fn test_client_cert_without_key_should_be_rejected() {
+        let result =
+            Cli::try_parse_from(["screenly", "--client-cert", "client.pem", "screen", "list"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_secret_from_env_should_return_env_value() {
+        temp_env::with_var("SCREENLY_TEST_SECRET", Some("s3cret"), || {
+            let secret = read_secret(None, false, Some("SCREENLY_TEST_SECRET"), "").unwrap();
+            assert_eq!(secret, "s3cret");
+        });
+    }
+
+    #[test]
+    fn test_read_secret_when_env_is_missing_should_return_error() {
+        temp_env::with_var_unset("SCREENLY_TEST_MISSING_SECRET", || {
+            let result = read_secret(None, false, Some("SCREENLY_TEST_MISSING_SECRET"), "");
+            assert!(matches!(result, Err(SecretError::MissingEnv(_))));
+        });
+    }
+
+    #[test]
+    fn test_read_secret_when_multiple_sources_given_should_return_error() {
+        let result = read_secret(Some("inline"), false, Some("SOME_VAR"), "");
+        assert!(matches!(result, Err(SecretError::MultipleSources)));
+    }
+
     #[test]
     fn test_get_authentication_error_message_when_not_logged_in() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
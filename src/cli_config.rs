@@ -0,0 +1,692 @@
+//! CLI defaults read from configuration files and the environment.
+//!
+//! Values are looked up in this order: command line flags, `SCREENLY_*` environment
+//! variables, the project file (`.screenly.toml` in the current directory or any parent)
+//! and the user file (`~/.config/screenly/config.toml`).
+//!
+//! A file or environment variable that can't be parsed doesn't stop the CLI; the error is
+//! reported by the commands that read an affected key.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{env, fmt, fs};
+
+use prettytable::{row, Table};
+use serde::Serialize;
+use thiserror::Error;
+use toml_edit::DocumentMut;
+
+use screenly::commands::{Formatter, OutputType};
+
+pub const PROJECT_CONFIG_FILENAME: &str = ".screenly.toml";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ValueType {
+    Boolean,
+    Integer,
+    String,
+}
+
+impl ValueType {
+    fn expected(self) -> &'static str {
+        match self {
+            ValueType::Boolean => "true or false",
+            ValueType::Integer => "a positive integer",
+            ValueType::String => "a string",
+        }
+    }
+}
+
+/// Keys that can be set, with their types.
+const KEYS: &[(&str, ValueType)] = &[
+    ("json", ValueType::Boolean),
+    ("asset.duration", ValueType::Integer),
+    ("playlist.predicate", ValueType::String),
+    ("edge_app.path", ValueType::String),
+];
+
+#[derive(Error, Debug)]
+pub enum CliConfigError {
+    #[error("i/o error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0}: {1}")]
+    Parse(String, String),
+    #[error("unknown configuration key \"{0}\". Known keys: {1}")]
+    UnknownKey(String, String),
+    #[error("invalid value \"{1}\" for \"{0}\": expected {2}")]
+    InvalidValue(String, String, String),
+    #[error("cannot determine the home directory")]
+    MissingHomeDir,
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// Called when a command reads a key whose configured value is invalid.
+pub type ErrorHandler = fn(&CliConfigError) -> !;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Value {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Boolean(value) => write!(f, "{value}"),
+            Value::Integer(value) => write!(f, "{value}"),
+            Value::String(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<&Value> for toml_edit::Item {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Boolean(value) => toml_edit::value(*value),
+            Value::Integer(value) => toml_edit::value(*value),
+            Value::String(value) => toml_edit::value(value.as_str()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Env,
+    Project,
+    User,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            Source::Env => "env",
+            Source::Project => "project",
+            Source::User => "user",
+        };
+        write!(f, "{source}")
+    }
+}
+
+fn value_type(key: &str) -> Result<ValueType, CliConfigError> {
+    KEYS.iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, value_type)| *value_type)
+        .ok_or_else(|| {
+            let known = KEYS.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+            CliConfigError::UnknownKey(key.to_owned(), known.join(", "))
+        })
+}
+
+fn env_var_name(key: &str) -> String {
+    format!("SCREENLY_{}", key.replace('.', "_").to_uppercase())
+}
+
+/// Parses a value given on the command line or in the environment.
+pub fn parse_value(key: &str, raw: &str) -> Result<Value, CliConfigError> {
+    let value_type = value_type(key)?;
+    let value = match value_type {
+        ValueType::Boolean => match raw {
+            "true" | "1" => Some(Value::Boolean(true)),
+            "false" | "0" => Some(Value::Boolean(false)),
+            _ => None,
+        },
+        ValueType::Integer => raw
+            .parse::<u32>()
+            .ok()
+            .map(|value| Value::Integer(value.into())),
+        ValueType::String => Some(Value::String(raw.to_owned())),
+    };
+    value.ok_or_else(|| {
+        CliConfigError::InvalidValue(
+            key.to_owned(),
+            raw.to_owned(),
+            value_type.expected().to_owned(),
+        )
+    })
+}
+
+/// Converts a value read from a file to the type of `key`.
+fn from_toml(key: &str, value_type: ValueType, value: &toml::Value) -> Result<Value, String> {
+    let converted = match (value_type, value) {
+        (ValueType::Boolean, toml::Value::Boolean(value)) => Some(Value::Boolean(*value)),
+        (ValueType::Integer, toml::Value::Integer(value)) => u32::try_from(*value)
+            .ok()
+            .map(|value| Value::Integer(value.into())),
+        (ValueType::String, toml::Value::String(value)) => Some(Value::String(value.clone())),
+        _ => None,
+    };
+    converted.ok_or_else(|| {
+        format!(
+            "invalid value {value} for \"{key}\": expected {}",
+            value_type.expected()
+        )
+    })
+}
+
+/// Flattens nested tables into dotted keys.
+fn flatten(prefix: &str, table: &toml::Table, values: &mut BTreeMap<String, toml::Value>) {
+    for (name, value) in table {
+        let key = match prefix {
+            "" => name.clone(),
+            prefix => format!("{prefix}.{name}"),
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, values),
+            value => {
+                values.insert(key, value.clone());
+            }
+        }
+    }
+}
+
+/// Values from one source. Errors are kept until a command reads an affected key.
+#[derive(Clone, Debug)]
+struct Layer {
+    source: Source,
+    /// Directory of the file the values were read from. Relative paths are resolved
+    /// against it.
+    dir: Option<PathBuf>,
+    /// Set when the whole source couldn't be read, which affects every key.
+    error: Option<String>,
+    values: BTreeMap<String, Result<Value, String>>,
+}
+
+impl Layer {
+    fn empty(source: Source) -> Self {
+        Self {
+            source,
+            dir: None,
+            error: None,
+            values: BTreeMap::new(),
+        }
+    }
+
+    fn failed(source: Source, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::empty(source)
+        }
+    }
+
+    fn read(source: Source, path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Self::empty(source),
+            Err(e) => return Self::failed(source, format!("{}: {e}", path.display())),
+        };
+        let table = match content.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => return Self::failed(source, format!("{}: {e}", path.display())),
+        };
+
+        let mut raw_values = BTreeMap::new();
+        flatten("", &table, &mut raw_values);
+        let mut values = BTreeMap::new();
+        for (key, value) in raw_values {
+            let Ok(value_type) = value_type(&key) else {
+                log::warn!("Ignoring unknown key \"{key}\" in {}.", path.display());
+                continue;
+            };
+            let value =
+                from_toml(&key, value_type, &value).map_err(|e| format!("{}: {e}", path.display()));
+            values.insert(key, value);
+        }
+        Self {
+            values,
+            dir: path.parent().map(Path::to_path_buf),
+            ..Self::empty(source)
+        }
+    }
+
+    /// Reads the nearest project file of `dir`.
+    fn project(dir: &Path) -> Self {
+        match find_project_config(dir) {
+            Some(path) => Self::read(Source::Project, &path),
+            None => Self::empty(Source::Project),
+        }
+    }
+
+    fn from_env() -> Self {
+        let mut values = BTreeMap::new();
+        for (key, _) in KEYS {
+            let name = env_var_name(key);
+            if let Ok(raw) = env::var(&name) {
+                let value = parse_value(key, &raw).map_err(|e| format!("{name}: {e}"));
+                values.insert(key.to_string(), value);
+            }
+        }
+        Self {
+            values,
+            ..Self::empty(Source::Env)
+        }
+    }
+}
+
+/// Defaults for CLI options, loaded once per invocation.
+#[derive(Clone, Debug, Default)]
+pub struct CliConfig {
+    layers: Vec<Layer>,
+    on_error: Option<ErrorHandler>,
+}
+
+/// Path of the user configuration file, honouring `XDG_CONFIG_HOME`.
+pub fn user_config_path() -> Result<PathBuf, CliConfigError> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => dirs::home_dir()
+            .ok_or(CliConfigError::MissingHomeDir)?
+            .join(".config"),
+    };
+    Ok(config_dir.join("screenly").join("config.toml"))
+}
+
+/// Finds the nearest `.screenly.toml` in `dir` or its parents.
+fn find_project_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILENAME))
+        .find(|path| path.is_file())
+}
+
+impl CliConfig {
+    /// Loads every source. `on_error` is called when a command reads a key whose value
+    /// is invalid, so that a broken file only affects the commands that depend on it.
+    pub fn load(on_error: ErrorHandler) -> Self {
+        let project = match env::current_dir() {
+            Ok(dir) => Layer::project(&dir),
+            Err(e) => Layer::failed(Source::Project, e.to_string()),
+        };
+        let user = match user_config_path() {
+            Ok(path) => Layer::read(Source::User, &path),
+            Err(e) => Layer::failed(Source::User, e.to_string()),
+        };
+        Self {
+            layers: vec![Layer::from_env(), project, user],
+            on_error: Some(on_error),
+        }
+    }
+
+    /// Returns the effective value of `key` and where it comes from.
+    pub fn get(&self, key: &str) -> Result<Option<(Value, Source)>, CliConfigError> {
+        Ok(self
+            .lookup(key)?
+            .map(|(value, layer)| (value, layer.source)))
+    }
+
+    /// Returns the effective value of `key` and the layer that supplied it.
+    fn lookup(&self, key: &str) -> Result<Option<(Value, &Layer)>, CliConfigError> {
+        value_type(key)?;
+        for layer in &self.layers {
+            if let Some(error) = &layer.error {
+                return Err(CliConfigError::Invalid(error.clone()));
+            }
+            if let Some(value) = layer.values.get(key) {
+                return match value {
+                    Ok(value) => Ok(Some((value.clone(), layer))),
+                    Err(error) => Err(CliConfigError::Invalid(error.clone())),
+                };
+            }
+        }
+        Ok(None)
+    }
+
+    fn value(&self, key: &str) -> Option<Value> {
+        self.value_with_layer(key).map(|(value, _)| value)
+    }
+
+    fn value_with_layer(&self, key: &str) -> Option<(Value, &Layer)> {
+        match self.lookup(key) {
+            Ok(value) => value,
+            Err(e) => match self.on_error {
+                Some(on_error) => on_error(&e),
+                None => {
+                    log::warn!("{e}");
+                    None
+                }
+            },
+        }
+    }
+
+    /// Resolves a `--json` flag: the flag wins, then the configured default.
+    pub fn json(&self, flag: &Option<bool>) -> Option<bool> {
+        if *flag == Some(true) {
+            return Some(true);
+        }
+        match self.value("json") {
+            Some(Value::Boolean(json)) => Some(json),
+            _ => *flag,
+        }
+    }
+
+    pub fn asset_duration(&self) -> Option<u32> {
+        match self.value("asset.duration") {
+            Some(Value::Integer(duration)) => u32::try_from(duration).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn playlist_predicate(&self) -> Option<String> {
+        match self.value("playlist.predicate") {
+            Some(Value::String(predicate)) => Some(predicate),
+            _ => None,
+        }
+    }
+
+    /// Resolves a `--path` flag of the Edge App commands. A relative path from a
+    /// configuration file is relative to that file, so it works from any subdirectory.
+    pub fn edge_app_path(&self, flag: &Option<String>) -> Option<String> {
+        if flag.is_some() {
+            return flag.clone();
+        }
+        match self.value_with_layer("edge_app.path") {
+            Some((Value::String(path), layer)) => Some(match &layer.dir {
+                Some(dir) => dir.join(path).to_string_lossy().into_owned(),
+                None => path,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Lists the effective value of every key that is set.
+    pub fn list(&self) -> Result<ConfigEntries, CliConfigError> {
+        let mut entries = Vec::new();
+        for (key, _) in KEYS {
+            if let Some((value, source)) = self.get(key)? {
+                entries.push(ConfigEntry {
+                    key: key.to_string(),
+                    value,
+                    source,
+                });
+            }
+        }
+        Ok(ConfigEntries { entries })
+    }
+}
+
+/// Reads the configuration file at `path` for editing. A missing file is empty.
+fn read_document(path: &Path) -> Result<DocumentMut, CliConfigError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    content
+        .parse::<DocumentMut>()
+        .map_err(|e| CliConfigError::Parse(path.display().to_string(), e.to_string()))
+}
+
+/// Sets `key` in the configuration file at `path`, creating the file if needed. Comments
+/// and the layout of the rest of the file are kept.
+pub fn set_value(path: &Path, key: &str, raw: &str) -> Result<(), CliConfigError> {
+    let value = parse_value(key, raw)?;
+    let mut document = read_document(path)?;
+    match key.split_once('.') {
+        Some((section, name)) => {
+            document.entry(section).or_insert_with(toml_edit::table)[name] = (&value).into()
+        }
+        None => document[key] = (&value).into(),
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, document.to_string())?;
+    Ok(())
+}
+
+/// Removes `key` from the configuration file at `path`, along with its section if that
+/// becomes empty.
+pub fn unset_value(path: &Path, key: &str) -> Result<bool, CliConfigError> {
+    value_type(key)?;
+    if !path.exists() {
+        return Ok(false);
+    }
+    let mut document = read_document(path)?;
+    let removed = match key.split_once('.') {
+        Some((section, name)) => {
+            let table = document
+                .get_mut(section)
+                .and_then(|item| item.as_table_like_mut());
+            match table {
+                Some(table) => {
+                    let removed = table.remove(name).is_some();
+                    if table.is_empty() {
+                        document.remove(section);
+                    }
+                    removed
+                }
+                None => false,
+            }
+        }
+        None => document.remove(key).is_some(),
+    };
+    if removed {
+        fs::write(path, document.to_string())?;
+    }
+    Ok(removed)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: Value,
+    pub source: Source,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigEntries {
+    pub entries: Vec<ConfigEntry>,
+}
+
+impl Formatter for ConfigEntries {
+    fn format(&self, output_type: OutputType) -> String {
+        match output_type {
+            OutputType::HumanReadable => {
+                let mut table = Table::new();
+                table.add_row(row!["Key", "Value", "Source"]);
+                for entry in &self.entries {
+                    table.add_row(row![entry.key, entry.value, entry.source]);
+                }
+                table.to_string()
+            }
+            OutputType::Json => serde_json::to_string_pretty(&self.entries).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn layer(source: Source, content: &str) -> Layer {
+        let tmp_dir = tempdir().unwrap();
+        let path = tmp_dir.path().join("config.toml");
+        fs::write(&path, content).unwrap();
+        Layer::read(source, &path)
+    }
+
+    fn config(layers: Vec<Layer>) -> CliConfig {
+        CliConfig {
+            layers,
+            on_error: None,
+        }
+    }
+
+    #[test]
+    fn test_read_should_support_toml_syntax() {
+        let content = r#"
+# Defaults for this machine
+json = true
+asset = { duration = 30 } # seconds
+
+[playlist]
+predicate = '$WEEKDAY IN {1, 2} # not a comment'
+"#;
+        let config = config(vec![layer(Source::User, content)]);
+
+        assert_eq!(config.json(&None), Some(true));
+        assert_eq!(config.asset_duration(), Some(30));
+        assert_eq!(
+            config.playlist_predicate().as_deref(),
+            Some("$WEEKDAY IN {1, 2} # not a comment")
+        );
+    }
+
+    #[test]
+    fn test_invalid_values_should_only_affect_keys_that_are_read() {
+        let config = config(vec![layer(
+            Source::Project,
+            "json = true\n[asset]\nduration = \"soon\"\n",
+        )]);
+
+        assert_eq!(config.json(&None), Some(true));
+        assert!(matches!(
+            config.get("asset.duration"),
+            Err(CliConfigError::Invalid(message)) if message.contains("expected a positive integer")
+        ));
+        assert!(config.list().is_err());
+    }
+
+    #[test]
+    fn test_malformed_file_should_be_reported_when_read() {
+        let config = config(vec![
+            layer(Source::Project, "json = true\nduration 30\n"),
+            layer(Source::User, "json = false\n"),
+        ]);
+
+        let error = config.get("json").unwrap_err().to_string();
+        assert!(error.contains("config.toml"), "{error}");
+        assert!(error.contains("line 2"), "{error}");
+    }
+
+    #[test]
+    fn test_set_value_should_keep_comments_and_layout() {
+        let tmp_dir = tempdir().unwrap();
+        let path = tmp_dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "# My defaults\njson = true # always\n\n[playlist]\npredicate = 'TRUE'\n",
+        )
+        .unwrap();
+
+        set_value(&path, "playlist.predicate", "say \"hi\"").unwrap();
+        set_value(&path, "asset.duration", "20").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# My defaults\njson = true # always\n\n[playlist]\npredicate = 'say \"hi\"'\n\n[asset]\nduration = 20\n"
+        );
+        let config = config(vec![Layer::read(Source::User, &path)]);
+        assert_eq!(config.playlist_predicate().as_deref(), Some("say \"hi\""));
+
+        assert!(unset_value(&path, "asset.duration").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# My defaults\njson = true # always\n\n[playlist]\npredicate = 'say \"hi\"'\n"
+        );
+    }
+
+    #[test]
+    fn test_set_value_should_validate_keys_and_values() {
+        let tmp_dir = tempdir().unwrap();
+        let path = tmp_dir.path().join("screenly").join("config.toml");
+
+        set_value(&path, "asset.duration", "25").unwrap();
+        assert!(matches!(
+            set_value(&path, "asset.duration", "soon"),
+            Err(CliConfigError::InvalidValue(..))
+        ));
+        assert!(matches!(
+            set_value(&path, "color", "blue"),
+            Err(CliConfigError::UnknownKey(..))
+        ));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[asset]\nduration = 25\n"
+        );
+
+        assert!(unset_value(&path, "asset.duration").unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        assert!(!unset_value(&path, "asset.duration").unwrap());
+    }
+
+    #[test]
+    fn test_edge_app_path_from_parent_project_file_should_be_relative_to_that_file() {
+        let tmp_dir = tempdir().unwrap();
+        fs::write(
+            tmp_dir.path().join(PROJECT_CONFIG_FILENAME),
+            "[edge_app]\npath = \"apps/clock\"\n",
+        )
+        .unwrap();
+        let nested_dir = tmp_dir.path().join("docs").join("notes");
+        fs::create_dir_all(&nested_dir).unwrap();
+
+        temp_env::with_var("SCREENLY_EDGE_APP_PATH", Some("from-env"), || {
+            let project_only = config(vec![Layer::project(&nested_dir)]);
+            assert_eq!(
+                project_only.edge_app_path(&None).map(PathBuf::from),
+                Some(tmp_dir.path().join("apps").join("clock"))
+            );
+
+            // Environment variables and flags stay relative to the working directory.
+            let with_env = config(vec![Layer::from_env(), Layer::project(&nested_dir)]);
+            assert_eq!(with_env.edge_app_path(&None).as_deref(), Some("from-env"));
+        });
+    }
+
+    #[test]
+    fn test_precedence_should_be_env_then_project_then_user() {
+        let user = layer(
+            Source::User,
+            "json = true\n[asset]\nduration = 20\n[playlist]\npredicate = \"TRUE\"\n",
+        );
+        let project = layer(Source::Project, "[asset]\nduration = 30\n");
+
+        temp_env::with_vars(
+            [
+                ("SCREENLY_PLAYLIST_PREDICATE", Some("FALSE")),
+                ("SCREENLY_JSON", None),
+                ("SCREENLY_ASSET_DURATION", Some("later")),
+                ("SCREENLY_EDGE_APP_PATH", None),
+            ],
+            || {
+                let mut config = config(vec![Layer::from_env(), project.clone(), user.clone()]);
+
+                // A bad environment variable only breaks the key it sets.
+                assert!(config.get("asset.duration").is_err());
+                config.layers[0].values.remove("asset.duration");
+
+                assert_eq!(config.asset_duration(), Some(30));
+                assert_eq!(config.playlist_predicate().as_deref(), Some("FALSE"));
+                assert_eq!(config.json(&None), Some(true));
+                assert_eq!(config.json(&Some(true)), Some(true));
+                assert_eq!(config.edge_app_path(&None), None);
+                assert_eq!(
+                    config.edge_app_path(&Some("app".to_owned())).as_deref(),
+                    Some("app")
+                );
+
+                let sources: Vec<_> = config
+                    .list()
+                    .unwrap()
+                    .entries
+                    .iter()
+                    .map(|entry| (entry.key.clone(), entry.source))
+                    .collect();
+                assert_eq!(
+                    sources,
+                    vec![
+                        ("json".to_owned(), Source::User),
+                        ("asset.duration".to_owned(), Source::Project),
+                        ("playlist.predicate".to_owned(), Source::Env),
+                    ]
+                );
+            },
+        );
+    }
+}
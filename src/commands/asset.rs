@@ -1,72 +1,148 @@
 use std::collections::HashMap;
 use std::fs::File;
-use std::time::Duration;
 
 use indicatif::{ProgressBar, ProgressStyle};
 use log::{debug, info};
-use reqwest::header::HeaderMap;
 use reqwest::StatusCode;
 use serde_json::json;
+use swc_common::{BytePos, Spanned};
+use swc_ecma_parser::{Parser, StringInput, Syntax};
 
+use crate::api::Api;
 use crate::authentication::Authentication;
-use crate::commands;
 use crate::commands::{Assets, CommandError};
 
+/// Optional properties applied to an asset as part of the creation request.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AssetOptions {
+    pub duration: Option<u32>,
+    pub headers: Vec<(String, String)>,
+    pub js_injection: Option<String>,
+}
+
+impl AssetOptions {
+    fn has_web_only_options(&self) -> bool {
+        !self.headers.is_empty() || self.js_injection.is_some()
+    }
+
+    /// Returns the headers as a map, rejecting names given more than once (e.g. an
+    /// explicit `Authorization` header next to `--bearer`) instead of dropping one.
+    fn headers_map(&self) -> Result<HashMap<String, String>, CommandError> {
+        let mut map = HashMap::new();
+        for (name, value) in &self.headers {
+            if map
+                .keys()
+                .any(|key: &String| key.eq_ignore_ascii_case(name))
+            {
+                return Err(CommandError::DuplicateHeader(name.clone()));
+            }
+            map.insert(name.clone(), value.clone());
+        }
+        Ok(map)
+    }
+}
+
 pub struct AssetCommand {
-    authentication: Authentication,
+    api: Api,
+}
+
+/// Reads JavaScript injection code from a local file or a remote URL.
+pub fn read_js_injection(path: &str) -> Result<String, CommandError> {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        let response = reqwest::blocking::get(path)?;
+        let status = response.status();
+        if status != StatusCode::OK {
+            return Err(CommandError::WrongResponseStatus(status.as_u16()));
+        }
+        return Ok(response.text()?);
+    }
+
+    Ok(std::fs::read_to_string(path)?)
+}
+
+/// Parses JavaScript code as a script and reports the first syntax error with its position.
+pub fn check_js_syntax(js_code: &str) -> Result<(), String> {
+    let input = StringInput::new(js_code, BytePos(1), BytePos(1 + js_code.len() as u32));
+    let mut parser = Parser::new(Syntax::Es(Default::default()), input, None);
+
+    let error = match parser.parse_script() {
+        Ok(_) => parser.take_errors().into_iter().next(),
+        Err(e) => Some(e),
+    };
+
+    match error {
+        Some(e) => {
+            let offset = (e.span().lo.0 as usize)
+                .saturating_sub(1)
+                .min(js_code.len());
+            let preceding = &js_code[..offset];
+            let line = preceding.matches('\n').count() + 1;
+            let column = offset - preceding.rfind('\n').map_or(0, |pos| pos + 1) + 1;
+            Err(format!("{} (line {line}, column {column})", e.kind().msg()))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Reads JavaScript injection code from local files and URLs and bundles it in the given order.
+/// Every source is syntax checked before bundling unless `lint` is disabled.
+pub fn bundle_js_injection(paths: &[String], lint: bool) -> Result<String, CommandError> {
+    let mut sources = Vec::new();
+    for path in paths {
+        let js_code = read_js_injection(path)?;
+        if lint {
+            check_js_syntax(&js_code).map_err(|e| CommandError::JsSyntaxError(path.clone(), e))?;
+        }
+        sources.push(js_code);
+    }
+
+    // Separate the sources with an empty statement so that automatic semicolon insertion
+    // can't merge the end of one file with the beginning of the next.
+    Ok(sources.join("\n;\n"))
 }
 
 impl AssetCommand {
     pub fn new(authentication: Authentication) -> Self {
-        Self { authentication }
+        Self {
+            api: Api::new(authentication),
+        }
     }
 
     pub fn list(&self) -> anyhow::Result<Assets, CommandError> {
-        Ok(Assets::new(commands::get(
-            &self.authentication,
-            "v4/assets?type=neq.edge-app-file",
-        )?))
+        Ok(Assets::new(self.api.list_assets()?))
     }
 
     pub fn get(&self, id: &str) -> anyhow::Result<Assets, CommandError> {
-        let endpoint = format!("v4/assets?id=eq.{id}");
-
-        Ok(Assets::new(commands::get(&self.authentication, &endpoint)?))
+        Ok(Assets::new(self.api.get_asset(id)?))
     }
 
-    fn add_web_asset(
+    pub fn add(
         &self,
-        url: &str,
-        headers: &HeaderMap,
-        payload: &HashMap<&str, &str>,
+        path: &str,
+        title: &str,
+        options: &AssetOptions,
     ) -> anyhow::Result<Assets, CommandError> {
-        let response = self
-            .authentication
-            .build_client()?
-            .post(url)
-            .json(payload)
-            .headers(headers.clone())
-            .send()?;
-
-        if response.status() != StatusCode::CREATED {
-            let status = response.status().as_u16();
-            return Err(CommandError::WrongResponseStatus(status));
+        if path.starts_with("http://") || path.starts_with("https://") {
+            let mut payload = json!({
+                "title": title,
+                "source_url": path,
+            });
+            if let Some(duration) = options.duration {
+                payload["duration"] = json!(duration);
+            }
+            if !options.headers.is_empty() {
+                payload["headers"] = json!(options.headers_map()?);
+            }
+            if let Some(js_injection) = &options.js_injection {
+                payload["js_injection"] = json!(js_injection);
+            }
+            return Ok(Assets::new(self.api.create_web_asset(&payload)?));
         }
 
-        Ok(Assets::new(serde_json::from_str(&response.text()?)?))
-    }
-
-    pub fn add(&self, path: &str, title: &str) -> anyhow::Result<Assets, CommandError> {
-        let url = format!("{}/v4/assets", &self.authentication.config.url);
-
-        let mut headers = HeaderMap::new();
-        headers.insert("Prefer", "return=representation".parse()?);
-
-        if path.starts_with("http://") || path.starts_with("https://") {
-            let mut payload = HashMap::new();
-            payload.insert("title", title);
-            payload.insert("source_url", path);
-            return self.add_web_asset(&url, &headers, &payload);
+        if options.has_web_only_options() {
+            return Err(CommandError::WebAssetOnlyOption(
+                "HTTP headers and JavaScript injection can only be set for web assets".to_owned(),
+            ));
         }
 
         let file = File::open(path)?;
@@ -80,25 +156,13 @@ impl AssetCommand {
         }
 
         let part = reqwest::blocking::multipart::Part::reader(pb.wrap_read(file)).file_name("file");
-        let form = reqwest::blocking::multipart::Form::new()
-            .text("title", title.to_owned())
-            .part("file", part);
-
-        let response = self
-            .authentication
-            .build_client()?
-            .post(url)
-            .multipart(form)
-            .headers(headers)
-            .timeout(Duration::from_secs(3600)) // timeout is equal to server timeout
-            .send()?;
-
-        if response.status() != StatusCode::CREATED {
-            let status = response.status().as_u16();
-            return Err(CommandError::WrongResponseStatus(status));
+        let mut form = reqwest::blocking::multipart::Form::new().text("title", title.to_owned());
+        if let Some(duration) = options.duration {
+            form = form.text("duration", duration.to_string());
         }
+        let form = form.part("file", part);
 
-        Ok(Assets::new(serde_json::from_str(&response.text()?)?))
+        Ok(Assets::new(self.api.upload_asset(form)?))
     }
 
     pub fn set_web_asset_headers(
@@ -106,10 +170,8 @@ impl AssetCommand {
         id: &str,
         headers: Vec<(String, String)>,
     ) -> anyhow::Result<(), CommandError> {
-        let endpoint = format!("v4/assets?id=eq.{id}");
         let map: HashMap<_, _> = headers.into_iter().collect();
-        commands::patch(&self.authentication, &endpoint, &json!({ "headers": map }))?;
-        Ok(())
+        self.api.update_asset(id, &json!({ "headers": map }))
     }
 
     pub fn update_web_asset_headers(
@@ -123,17 +185,16 @@ impl AssetCommand {
         }
 
         let mut new_headers: HashMap<_, _> = headers.into_iter().collect();
-        let asset = self.get(id)?;
-        if let Some(assets) = asset.value.as_array() {
-            if assets.is_empty() {
-                return Err(CommandError::MissingField);
-            }
-            let headers = assets[0].get("headers").ok_or(CommandError::MissingField)?;
-            let old_headers = serde_json::from_value::<HashMap<String, String>>(headers.clone())?;
-            debug!("Old headers {:?}", &old_headers);
-            for (key, value) in old_headers {
-                new_headers.entry(key).or_insert(value);
-            }
+        let assets = self.get(id)?.assets;
+        let asset = assets.first().ok_or(CommandError::MissingField)?;
+        let headers = asset
+            .extra
+            .get("headers")
+            .ok_or(CommandError::MissingField)?;
+        let old_headers = serde_json::from_value::<HashMap<String, String>>(headers.clone())?;
+        debug!("Old headers {:?}", &old_headers);
+        for (key, value) in old_headers {
+            new_headers.entry(key).or_insert(value);
         }
 
         self.set_web_asset_headers(
@@ -143,18 +204,27 @@ impl AssetCommand {
     }
 
     pub fn inject_js(&self, id: &str, js_code: &str) -> anyhow::Result<(), CommandError> {
-        let endpoint = format!("v4/assets?id=eq.{id}");
-        commands::patch(
-            &self.authentication,
-            &endpoint,
-            &json!({ "js_injection": js_code }),
-        )?;
-        Ok(())
+        self.api
+            .update_asset(id, &json!({ "js_injection": js_code }))
+    }
+
+    pub fn clear_js_injection(&self, id: &str) -> anyhow::Result<(), CommandError> {
+        self.api.update_asset(id, &json!({ "js_injection": null }))
+    }
+
+    pub fn get_js_injection(&self, id: &str) -> anyhow::Result<Option<String>, CommandError> {
+        let asset = self
+            .api
+            .get_asset_js_injection(id)?
+            .into_iter()
+            .next()
+            .ok_or(CommandError::MissingField)?;
+
+        Ok(asset.js_injection)
     }
 
     pub fn delete(&self, id: &str) -> anyhow::Result<(), CommandError> {
-        let endpoint = format!("v4/assets?id=eq.{id}");
-        commands::delete(&self.authentication, &endpoint)
+        self.api.delete_asset(id)
     }
 }
 
@@ -167,6 +237,7 @@ mod tests {
     use tempfile::tempdir;
 
     use super::*;
+    use crate::api::asset::Asset;
     use crate::authentication::Config;
     use crate::commands::{Formatter, OutputType};
 
@@ -228,7 +299,10 @@ mod tests {
         let authentication = Authentication::new_with_config(config, "token");
         let asset_command = AssetCommand::new(authentication);
         let v = asset_command.list().unwrap();
-        assert_eq!(v.value, asset_list);
+        assert_eq!(
+            v.assets,
+            serde_json::from_value::<Vec<Asset>>(asset_list).unwrap()
+        );
     }
 
     #[test]
@@ -275,11 +349,18 @@ mod tests {
         let config = Config::new(mock_server.base_url());
         let authentication = Authentication::new_with_config(config, "token");
         let asset_command = AssetCommand::new(authentication);
-        let v = asset_command.add(tmp_dir.path().join("1.html").to_str().unwrap(), "test");
+        let v = asset_command.add(
+            tmp_dir.path().join("1.html").to_str().unwrap(),
+            "test",
+            &AssetOptions::default(),
+        );
         post_mock.assert();
 
         assert!(v.is_ok());
-        assert_eq!(v.unwrap().value, new_asset);
+        assert_eq!(
+            v.unwrap().assets,
+            serde_json::from_value::<Vec<Asset>>(new_asset).unwrap()
+        );
     }
 
     #[test]
@@ -325,10 +406,102 @@ mod tests {
         let config = Config::new(mock_server.base_url());
         let authentication = Authentication::new_with_config(config, "token");
         let asset_command = AssetCommand::new(authentication);
-        let v = asset_command.add("https://google.com", "test");
+        let v = asset_command.add("https://google.com", "test", &AssetOptions::default());
         assert!(v.is_ok());
         post_mock.assert();
-        assert_eq!(v.unwrap().value, new_asset);
+        assert_eq!(
+            v.unwrap().assets,
+            serde_json::from_value::<Vec<Asset>>(new_asset).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add_asset_when_web_asset_with_options_should_send_them_in_creation_payload() {
+        let new_asset = json!([
+          {
+            "duration": 30.0,
+            "headers": {"Authorization": "Bearer token", "k": "v"},
+            "id": "0184f162-585e-6334-8dae-38a80062a6c2",
+            "js_injection": "console.log(1)",
+            "source_url": "https://google.com",
+            "status": "none",
+            "title": "test",
+            "type": null
+          }
+        ]);
+
+        let mock_server = MockServer::start();
+        let post_mock = mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/v4/assets")
+                .header("Authorization", "Token token")
+                .json_body(json!({
+                    "source_url": "https://google.com",
+                    "title": "test",
+                    "duration": 30,
+                    "headers": {"Authorization": "Bearer token", "k": "v"},
+                    "js_injection": "console.log(1)"
+                }));
+            then.status(201).json_body(new_asset.clone());
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let asset_command = AssetCommand::new(authentication);
+        let options = AssetOptions {
+            duration: Some(30),
+            headers: vec![
+                ("k".to_owned(), "v".to_owned()),
+                ("Authorization".to_owned(), "Bearer token".to_owned()),
+            ],
+            js_injection: Some("console.log(1)".to_owned()),
+        };
+        let v = asset_command.add("https://google.com", "test", &options);
+        post_mock.assert();
+        assert_eq!(
+            v.unwrap().assets,
+            serde_json::from_value::<Vec<Asset>>(new_asset).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add_asset_when_header_is_given_twice_should_return_error() {
+        let mock_server = MockServer::start();
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let asset_command = AssetCommand::new(authentication);
+        let options = AssetOptions {
+            headers: vec![
+                ("authorization".to_owned(), "Token abc".to_owned()),
+                ("Authorization".to_owned(), "Bearer token".to_owned()),
+            ],
+            ..Default::default()
+        };
+
+        let v = asset_command.add("https://google.com", "test", &options);
+
+        assert!(matches!(v, Err(CommandError::DuplicateHeader(name)) if name == "Authorization"));
+    }
+
+    #[test]
+    fn test_add_asset_when_local_asset_with_web_only_options_should_return_error() {
+        let tmp_dir = tempdir().unwrap();
+        fs::write(tmp_dir.path().join("1.html").to_str().unwrap(), "dummy").unwrap();
+
+        let mock_server = MockServer::start();
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let asset_command = AssetCommand::new(authentication);
+        let options = AssetOptions {
+            js_injection: Some("console.log(1)".to_owned()),
+            ..Default::default()
+        };
+        let v = asset_command.add(
+            tmp_dir.path().join("1.html").to_str().unwrap(),
+            "test",
+            &options,
+        );
+        assert!(matches!(v, Err(CommandError::WebAssetOnlyOption(_))));
     }
 
     #[test]
@@ -373,7 +546,10 @@ mod tests {
         let v = asset_command.get("017b0187-d887-3c79-7b67-18c94098345d");
         get_mock.assert();
         assert!(v.is_ok());
-        assert_eq!(v.unwrap().value, asset);
+        assert_eq!(
+            v.unwrap().assets,
+            serde_json::from_value::<Vec<Asset>>(asset).unwrap()
+        );
     }
 
     #[test]
@@ -402,7 +578,7 @@ mod tests {
     #[test]
     fn test_format_asset_when_human_readable_output_is_set_should_return_correct_formatted_string()
     {
-        let asset = Assets::new(json!([
+        let asset = Assets::new(serde_json::from_value(json!([
           {
             "asset_group_id": null,
             "asset_url": "",
@@ -422,7 +598,8 @@ mod tests {
             "type": null,
             "width": null
           }
-        ]));
+        ]))
+        .unwrap());
 
         println!("{}", asset.format(OutputType::HumanReadable));
         let expected_output =
@@ -459,6 +636,83 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_clear_js_injection_should_send_null_js_injection() {
+        let mock_server = MockServer::start();
+        let patch_mock = mock_server.mock(|when, then| {
+            when.method(PATCH)
+                .path("/v4/assets")
+                .query_param("id", "eq.test-id")
+                .json_body(json!({"js_injection": null}))
+                .header("Authorization", "Token token");
+            then.status(200);
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let asset_command = AssetCommand::new(authentication);
+        let result = asset_command.clear_js_injection("test-id");
+        patch_mock.assert();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_get_js_injection_should_return_injected_code() {
+        let mock_server = MockServer::start();
+        let get_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/assets")
+                .query_param("id", "eq.test-id")
+                .query_param("select", "js_injection")
+                .header("Authorization", "Token token");
+            then.status(200)
+                .json_body(json!([{"js_injection": "console.log(1)"}]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let asset_command = AssetCommand::new(authentication);
+        let result = asset_command.get_js_injection("test-id");
+        get_mock.assert();
+        assert_eq!(result.unwrap(), Some("console.log(1)".to_owned()));
+    }
+
+    #[test]
+    fn test_bundle_js_injection_should_concatenate_files_in_order() {
+        let tmp_dir = tempdir().unwrap();
+        let first = tmp_dir.path().join("first.js");
+        let second = tmp_dir.path().join("second.js");
+        fs::write(&first, "var a = 1").unwrap();
+        fs::write(&second, "console.log(a);").unwrap();
+
+        let paths = vec![
+            first.to_str().unwrap().to_owned(),
+            second.to_str().unwrap().to_owned(),
+        ];
+        let bundle = bundle_js_injection(&paths, true).unwrap();
+        assert_eq!(bundle, "var a = 1\n;\nconsole.log(a);");
+    }
+
+    #[test]
+    fn test_bundle_js_injection_when_file_has_syntax_error_should_return_error() {
+        let tmp_dir = tempdir().unwrap();
+        let path = tmp_dir.path().join("broken.js");
+        fs::write(&path, "var a = 1;\nfunction broken( {").unwrap();
+
+        let paths = vec![path.to_str().unwrap().to_owned()];
+        let result = bundle_js_injection(&paths, true);
+        assert!(matches!(result, Err(CommandError::JsSyntaxError(_, _))));
+
+        // Linting can be skipped explicitly.
+        assert!(bundle_js_injection(&paths, false).is_ok());
+    }
+
+    #[test]
+    fn test_check_js_syntax_should_report_line_of_error() {
+        let error = check_js_syntax("var a = 1;\nvar b = ;").unwrap_err();
+        assert!(error.contains("line 2"), "{error}");
+    }
+
     #[test]
     fn test_set_headers_should_send_correct_request() {
         let mock_server = MockServer::start();
@@ -0,0 +1,189 @@
+use prettytable::{cell, Cell, Row};
+use serde::Serialize;
+
+use crate::api::account::TokenOwner;
+use crate::authentication::{Authentication, TokenSource, KEYRING_SERVICE};
+use crate::commands::{Formatter, OutputType};
+
+/// Where the current token comes from, as shown by `screenly auth status`.
+///
+/// `screenly whoami` also verifies the token and fills in its owner.
+#[derive(Debug, Serialize)]
+pub struct AuthStatus {
+    pub source: String,
+    pub location: String,
+    pub token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<String>,
+    pub api_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<TokenOwner>,
+}
+
+impl AuthStatus {
+    pub fn new(authentication: &Authentication) -> Self {
+        let (source, location) = match authentication.token_source() {
+            TokenSource::Env => ("env", "API_TOKEN".to_owned()),
+            TokenSource::Keyring => ("keyring", KEYRING_SERVICE.to_owned()),
+            TokenSource::File(path) => ("file", path.display().to_string()),
+            TokenSource::EncryptedFile(path) => ("encrypted file", path.display().to_string()),
+            TokenSource::Explicit => ("explicit", "N/A".to_owned()),
+        };
+        let permissions = match authentication.token_source() {
+            TokenSource::File(path) | TokenSource::EncryptedFile(path) => file_permissions(path),
+            _ => None,
+        };
+
+        Self {
+            source: source.to_owned(),
+            location,
+            token: authentication.masked_token(),
+            permissions,
+            api_url: authentication.config.url.clone(),
+            valid: None,
+            owner: None,
+        }
+    }
+
+    pub fn with_validity(mut self, valid: bool) -> Self {
+        self.valid = Some(valid);
+        self
+    }
+
+    pub fn with_owner(mut self, owner: TokenOwner) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Whether the token file can be read by other users.
+    pub fn is_exposed(&self) -> bool {
+        self.permissions
+            .as_deref()
+            .and_then(|mode| u32::from_str_radix(mode, 8).ok())
+            .is_some_and(|mode| mode & 0o077 != 0)
+    }
+}
+
+#[cfg(unix)]
+fn file_permissions(path: &std::path::Path) -> Option<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path).ok()?.permissions().mode();
+    Some(format!("{:o}", mode & 0o777))
+}
+
+#[cfg(not(unix))]
+fn file_permissions(_path: &std::path::Path) -> Option<String> {
+    None
+}
+
+fn describe(name: Option<&str>, id: &str) -> String {
+    match name {
+        Some(name) => format!("{name} ({id})"),
+        None => id.to_owned(),
+    }
+}
+
+impl Formatter for AuthStatus {
+    fn format(&self, output_type: OutputType) -> String {
+        match output_type {
+            OutputType::HumanReadable => {
+                let mut table = prettytable::Table::new();
+                table.add_row(Row::new(vec![cell!("Source"), Cell::new(&self.source)]));
+                table.add_row(Row::new(vec![cell!("Location"), Cell::new(&self.location)]));
+                table.add_row(Row::new(vec![cell!("Token"), Cell::new(&self.token)]));
+                if let Some(permissions) = &self.permissions {
+                    let style = if self.is_exposed() { "Fr" } else { "Fg" };
+                    table.add_row(Row::new(vec![
+                        cell!("Permissions"),
+                        Cell::new(permissions).style_spec(style),
+                    ]));
+                }
+                table.add_row(Row::new(vec![cell!("API"), Cell::new(&self.api_url)]));
+                if let Some(valid) = self.valid {
+                    let (text, style) = if valid { ("Yes", "Fg") } else { ("No", "Fr") };
+                    table.add_row(Row::new(vec![
+                        cell!("Valid"),
+                        Cell::new(text).style_spec(style),
+                    ]));
+                }
+                if let Some(owner) = &self.owner {
+                    let team = owner
+                        .team
+                        .as_ref()
+                        .map(|team| describe(team.name.as_deref(), &team.id))
+                        .unwrap_or("N/A".to_owned());
+                    let user = owner
+                        .user
+                        .as_ref()
+                        .map(|user| describe(user.email.as_deref(), &user.id))
+                        .unwrap_or("N/A".to_owned());
+                    table.add_row(Row::new(vec![cell!("Team"), cell!(team)]));
+                    table.add_row(Row::new(vec![cell!("User"), cell!(user)]));
+                }
+                table.to_string()
+            }
+            OutputType::Json => serde_json::to_string_pretty(self).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::Config;
+
+    #[test]
+    fn test_auth_status_should_mask_the_token() {
+        let authentication = Authentication::new_with_config(
+            Config::new("https://api.example.com".to_owned()),
+            "abcd1234efgh5678",
+        );
+        let status = AuthStatus::new(&authentication);
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&status.format(OutputType::Json)).unwrap(),
+            serde_json::json!({
+                "source": "explicit",
+                "location": "N/A",
+                "token": "abcd...5678",
+                "api_url": "https://api.example.com"
+            })
+        );
+        assert!(!status.is_exposed());
+        assert!(!status
+            .format(OutputType::HumanReadable)
+            .contains("abcd1234efgh5678"));
+    }
+
+    #[test]
+    fn test_verified_auth_status_should_include_owner() {
+        use crate::api::account::Team;
+
+        let authentication = Authentication::new_with_config(
+            Config::new("https://api.example.com".to_owned()),
+            "abcd1234efgh5678",
+        );
+        let status = AuthStatus::new(&authentication)
+            .with_validity(true)
+            .with_owner(TokenOwner {
+                team: Some(Team {
+                    id: "team-1".to_owned(),
+                    name: Some("Acme".to_owned()),
+                }),
+                user: None,
+            });
+
+        let json: serde_json::Value =
+            serde_json::from_str(&status.format(OutputType::Json)).unwrap();
+        assert_eq!(json["valid"], true);
+        assert_eq!(json["owner"]["team"]["name"], "Acme");
+        assert_eq!(json["owner"]["user"], serde_json::Value::Null);
+
+        let human = status.format(OutputType::HumanReadable);
+        assert!(human.contains("Acme (team-1)"));
+        assert!(human.contains("N/A"));
+    }
+}
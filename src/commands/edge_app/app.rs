@@ -1,33 +1,54 @@
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::Write;
+use std::io::{SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use std::{fs, io, str, thread};
+use std::{fs, str, thread};
 
+use futures::{future, stream, StreamExt, TryStreamExt};
 use indicatif::ProgressBar;
 use log::debug;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use reqwest::header::HeaderMap;
-use reqwest::StatusCode;
+use reqwest::multipart;
+use reqwest::{Body, StatusCode};
 use serde_json::json;
 use serde_yaml;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
 
 use crate::api::edge_app::setting::{Setting, SettingType};
-use crate::api::version::EdgeAppVersion;
+use crate::api::edge_app::version::Version;
+use crate::api::V4;
+use crate::commands::edge_app::build::files_dir;
+use crate::commands::edge_app::delta::Delta;
 use crate::commands::edge_app::instance_manifest::InstanceManifest;
 use crate::commands::edge_app::manifest::{
     EdgeAppManifest, Entrypoint, EntrypointType, MANIFEST_VERSION,
 };
 use crate::commands::edge_app::utils::{
-    collect_paths_for_upload, detect_changed_files, detect_changed_settings,
-    ensure_edge_app_has_all_necessary_files, generate_file_tree,
-    transform_edge_app_path_to_manifest, transform_instance_path_to_instance_manifest, FileChanges,
-    SettingChanges,
+    list_upload_paths, transform_edge_app_path_to_manifest,
+    transform_instance_path_to_instance_manifest, FileChanges, SettingChanges,
 };
 use crate::commands::edge_app::EdgeAppCommand;
-use crate::commands::{CommandError, EdgeApps};
+use crate::commands::ignorer::Ignorer;
+use crate::commands::{
+    CommandError, EdgeAppChannels, EdgeAppFileList, EdgeAppVersionDetails, EdgeAppVersions,
+    EdgeApps,
+};
+
+/// Channel that deployed revisions are promoted to unless told otherwise.
+pub const DEFAULT_CHANNEL: &str = "stable";
+
+/// Number of files uploaded in parallel during a deploy.
+const MAX_CONCURRENT_UPLOADS: usize = 8;
+
+/// A deploy whose version is created but whose files are not uploaded and published yet.
+#[derive(Debug)]
+pub struct PendingDeploy {
+    app_id: String,
+    revision: u32,
+    uploads: Vec<(PathBuf, Option<Delta>)>,
+}
 
 // Edge apps commands
 impl EdgeAppCommand {
@@ -121,88 +142,94 @@ impl EdgeAppCommand {
         self.api.list_apps()
     }
 
-    pub fn deploy(
-        self,
+    /// Starts deploying the Edge App at `path` (a directory or a `screenly.yml`, defaulting
+    /// to the current directory).
+    ///
+    /// The `build` section of the manifest runs first, and files are then taken from its
+    /// output directory. Settings and files are compared with the latest revision and only
+    /// changes are uploaded; [`CommandError::NoChangesToUpload`] is returned when there is nothing
+    /// to deploy. See [`EdgeAppCommand::plan`] to preview the changes.
+    ///
+    /// Setting changes are applied right away. Returns `None` when they were the only
+    /// changes, otherwise a new version is created and returned as a [`PendingDeploy`]. Upload
+    /// its files with [`EdgeAppCommand::upload_files`] from the caller's async runtime, then
+    /// publish it with [`EdgeAppCommand::finish_deploy`].
+    pub fn prepare_deploy(
+        &self,
         path: Option<String>,
         delete_missing_settings: Option<bool>,
-    ) -> Result<u32, CommandError> {
-        let manifest_path = transform_edge_app_path_to_manifest(&path)?;
-
-        EdgeAppManifest::ensure_manifest_is_valid(&manifest_path)?;
-        let manifest = EdgeAppManifest::new(&manifest_path)?;
-
-        let actual_app_id = match self.get_app_id(path.clone()) {
-            Ok(id) => id,
-            Err(_) => return Err(CommandError::MissingAppId),
-        };
-
-        let version_metadata_changed =
-            self.detect_version_metadata_changes(&actual_app_id, &manifest)?;
-
-        let edge_app_dir = manifest_path.parent().ok_or(CommandError::MissingField)?;
-
-        let local_files = collect_paths_for_upload(edge_app_dir)?;
-        ensure_edge_app_has_all_necessary_files(&local_files)?;
-
-        let revision = match self.api.get_latest_revision(&actual_app_id)? {
-            Some(revision) => revision.revision,
-            None => 0,
-        };
-
-        let remote_files = self
-            .api
-            .get_version_asset_signatures(&actual_app_id, revision)?;
-        let changed_files = detect_changed_files(&local_files, &remote_files)?;
-        debug!("Changed files: {:?}", &changed_files);
-
-        let remote_settings = self.api.get_settings(&actual_app_id)?;
+    ) -> Result<Option<PendingDeploy>, CommandError> {
+        let plan = self.plan(path.clone())?;
+        debug!("Changed files: {:?}", &plan.file_changes);
 
-        let changed_settings = detect_changed_settings(&manifest, &remote_settings)?;
-        self.upload_changed_settings(actual_app_id.clone(), &changed_settings)?;
+        debug!("File tree changed: {}", plan.file_tree_changed);
+        if !plan.creates_version && plan.setting_changes.is_empty() {
+            return Err(CommandError::NoChangesToUpload(
+                "No changes detected".to_owned(),
+            ));
+        }
 
+        self.upload_changed_settings(plan.app_id.clone(), &plan.setting_changes)?;
         self.maybe_delete_missing_settings(
             delete_missing_settings,
-            actual_app_id.clone(),
-            changed_settings,
+            plan.app_id.clone(),
+            &plan.setting_changes,
         )?;
 
-        self.update_entrypoint_value(path)?;
-
-        let file_tree = generate_file_tree(&local_files, edge_app_dir);
-
-        let old_file_tree = self.api.get_file_tree(&actual_app_id, revision);
-
-        let file_tree_changed = match old_file_tree {
-            Ok(tree) => file_tree != tree,
-            Err(_) => true,
-        };
+        if let Some(entrypoint) = &plan.entrypoint {
+            self.set_setting(path, "screenly_entrypoint", entrypoint)?;
+        }
 
-        debug!("File tree changed: {file_tree_changed}");
-        if !self.requires_upload(&changed_files) && !file_tree_changed && !version_metadata_changed
-        {
-            return Err(CommandError::NoChangesToUpload(
-                "No changes detected".to_owned(),
-            ));
+        if !plan.creates_version {
+            debug!("Only settings changed, no revision created.");
+            return Ok(None);
         }
 
         // now that we know we have changes, we can create a new version
-        let revision =
-            self.create_version(&manifest, generate_file_tree(&local_files, edge_app_dir))?;
+        let revision = self.create_version(&plan.manifest, plan.file_tree())?;
 
-        self.upload_changed_files(edge_app_dir, &actual_app_id, revision, &changed_files)?;
-        debug!("Files uploaded");
+        let uploads = self.prepare_uploads(
+            &plan.files_dir,
+            &plan.app_id,
+            revision,
+            &plan.file_changes,
+            &plan.remote_file_tree,
+        )?;
+
+        Ok(Some(PendingDeploy {
+            app_id: plan.app_id,
+            revision,
+            uploads,
+        }))
+    }
+
+    /// Waits until the uploaded files are processed, publishes the revision and promotes
+    /// it to `channel`.
+    pub fn finish_deploy(
+        &self,
+        deploy: &PendingDeploy,
+        channel: Option<&str>,
+    ) -> Result<u32, CommandError> {
+        let PendingDeploy {
+            app_id, revision, ..
+        } = deploy;
+        let revision = *revision;
 
-        self.ensure_assets_processing_finished(&actual_app_id, revision)?;
+        self.ensure_assets_processing_finished(app_id, revision)?;
         // now we freeze it by publishing it
-        self.api.publish_version(&actual_app_id, revision)?;
+        self.api.publish_version(app_id, revision)?;
         debug!("Edge App published.");
 
-        self.promote_version(&actual_app_id, revision, "stable")?;
+        if let Some(channel) = channel {
+            self.promote_version(app_id, revision, channel)?;
+            debug!("Revision {revision} promoted to {channel}.");
+        }
 
         Ok(revision)
     }
 
-    fn promote_version(
+    /// Points `channel` of the app at an existing `revision`.
+    pub fn promote_version(
         &self,
         app_id: &str,
         revision: u32,
@@ -218,6 +245,45 @@ impl EdgeAppCommand {
         Ok(())
     }
 
+    pub fn list_channels(&self, app_id: &str) -> Result<EdgeAppChannels, CommandError> {
+        Ok(EdgeAppChannels::new(self.api.list_channels(app_id)?))
+    }
+
+    /// Lists the revisions of the app, newest first, with the channels pointing to them.
+    pub fn list_versions(&self, app_id: &str) -> Result<EdgeAppVersions, CommandError> {
+        let mut versions = self.api.list_versions(app_id)?;
+        self.attach_channels(app_id, &mut versions)?;
+        Ok(EdgeAppVersions::new(versions))
+    }
+
+    pub fn show_version(
+        &self,
+        app_id: &str,
+        revision: u32,
+    ) -> Result<EdgeAppVersionDetails, CommandError> {
+        let version = self
+            .api
+            .get_version(app_id, revision)?
+            .ok_or(CommandError::RevisionNotFound(revision.to_string()))?;
+        let mut versions = vec![version];
+        self.attach_channels(app_id, &mut versions)?;
+        Ok(EdgeAppVersionDetails {
+            version: versions.remove(0),
+        })
+    }
+
+    fn attach_channels(&self, app_id: &str, versions: &mut [Version]) -> Result<(), CommandError> {
+        let channels = self.api.list_channels(app_id)?;
+        for version in versions {
+            version.channels = channels
+                .iter()
+                .filter(|channel| channel.app_revision == Some(version.revision))
+                .map(|channel| channel.channel.clone())
+                .collect();
+        }
+        Ok(())
+    }
+
     pub fn delete_app(&self, app_id: &str) -> Result<(), CommandError> {
         self.api.delete_app(app_id)?;
 
@@ -234,7 +300,7 @@ impl EdgeAppCommand {
         &self,
         delete_missing_settings: Option<bool>,
         actual_app_id: String,
-        changed_settings: SettingChanges,
+        changed_settings: &SettingChanges,
     ) -> Result<(), CommandError> {
         match delete_missing_settings {
             Some(delete) => {
@@ -263,29 +329,9 @@ impl EdgeAppCommand {
 
     pub fn update_entrypoint_value(&self, path: Option<String>) -> Result<(), CommandError> {
         let manifest = EdgeAppManifest::new(&transform_edge_app_path_to_manifest(&path)?)?;
-        let setting_key = "screenly_entrypoint";
-
-        if let Some(entrypoint) = &manifest.entrypoint {
-            match entrypoint.entrypoint_type {
-                EntrypointType::RemoteGlobal => {
-                    let setting_value = match entrypoint.uri {
-                        Some(ref uri) => uri.clone(),
-                        None => "".to_owned(),
-                    };
-                    self.set_setting(path, setting_key, &setting_value)?;
-                }
-                EntrypointType::RemoteLocal => {
-                    let instance_manifest = InstanceManifest::new(
-                        &transform_instance_path_to_instance_manifest(&path)?,
-                    )?;
-                    let setting_value: String = match instance_manifest.entrypoint_uri {
-                        Some(ref uri) => uri.clone(),
-                        None => "".to_owned(),
-                    };
-                    self.set_setting(path, setting_key, &setting_value)?;
-                }
-                _ => {}
-            }
+
+        if let Some(setting_value) = self.entrypoint_setting_value(&manifest, &path)? {
+            self.set_setting(path, "screenly_entrypoint", &setting_value)?;
         }
 
         Ok(())
@@ -348,11 +394,6 @@ impl EdgeAppCommand {
         }
         Ok(())
     }
-
-    // TODO: remove
-    fn requires_upload(&self, changed_files: &FileChanges) -> bool {
-        changed_files.has_changes()
-    }
 }
 
 impl EdgeAppCommand {
@@ -409,13 +450,19 @@ impl EdgeAppCommand {
         Ok(())
     }
 
-    fn upload_changed_files(
+    /// Uploads files that can't be copied from earlier revisions. `base_tree` is the file
+    /// tree of the base revision; files present there under the same path are sent as
+    /// deltas when the server supports it.
+    /// Copies the files the server already has and returns the ones left to upload, with
+    /// a delta against the previous revision where one can be used.
+    fn prepare_uploads(
         &self,
         edge_app_dir: &Path,
         app_id: &str,
         revision: u32,
         changed_files: &FileChanges,
-    ) -> Result<(), CommandError> {
+        base_tree: &HashMap<String, String>,
+    ) -> Result<Vec<(PathBuf, Option<Delta>)>, CommandError> {
         debug!("Changed files: {changed_files:#?}");
 
         let copied_signatures = self.copy_edge_app_assets(
@@ -432,18 +479,30 @@ impl EdgeAppCommand {
         let files_to_upload = changed_files.get_files_to_upload(copied_signatures);
         if files_to_upload.is_empty() {
             debug!("No files to upload");
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         debug!("Uploading Edge App files: {files_to_upload:#?}");
-        let file_paths: Vec<PathBuf> = files_to_upload
+        let mut uploads: Vec<(PathBuf, Option<Delta>)> = files_to_upload
             .iter()
-            .map(|file| edge_app_dir.join(&file.path))
+            .map(|file| {
+                let path = edge_app_dir.join(&file.path);
+                let delta = base_tree
+                    .get(&file.path)
+                    .and_then(|base| Delta::new(&path, base, &file.signature));
+                (path, delta)
+            })
             .collect();
 
-        self.upload_edge_app_assets(app_id, revision, &file_paths)?;
+        let has_deltas = uploads.iter().any(|(_, delta)| delta.is_some());
+        if has_deltas && !(self.delta_uploads && self.api.get_upload_capabilities().delta_uploads) {
+            debug!("Delta uploads are not available, uploading whole files");
+            for (_, delta) in &mut uploads {
+                *delta = None;
+            }
+        }
 
-        Ok(())
+        Ok(uploads)
     }
 
     fn try_delete_setting(
@@ -454,17 +513,14 @@ impl EdgeAppCommand {
     ) -> Result<(), CommandError> {
         debug!("Deleting setting: {:?}", &setting.name);
 
-        let mut input_name = String::new();
-
         if !prompt_user {
             return self.delete_setting(app_id, setting);
         }
 
-        let prompt = format!("It seems like the setting \"{}\" is absent in the YAML file, but it exists on the server. If you wish to skip deletion, you can leave the input blank. Warning, deleting the setting will drop all the associated values. To proceed with deletion, please confirm the setting name by writing it down: ", setting.name);
-        println!("{prompt}");
-        io::stdin()
-            .read_line(&mut input_name)
-            .expect("Failed to read input");
+        let input_name = match &self.setting_deletion_prompt {
+            Some(prompt) => prompt(&setting.name),
+            None => return Ok(()),
+        };
 
         if input_name.trim() == "" {
             return Ok(());
@@ -498,93 +554,165 @@ impl EdgeAppCommand {
         Ok(copied_assets)
     }
 
-    fn upload_edge_app_assets(
+    /// Uploads the files of a prepared deploy over the pooled async `client`, several at a
+    /// time. Changed files are sent as deltas when possible.
+    pub async fn upload_files(
         &self,
-        app_id: &str,
-        revision: u32,
-        paths: &[PathBuf],
+        client: &reqwest::Client,
+        deploy: &PendingDeploy,
     ) -> Result<(), CommandError> {
-        let pb = ProgressBar::new(paths.len() as u64);
+        let PendingDeploy {
+            app_id,
+            revision,
+            uploads,
+        } = deploy;
+        let pb = ProgressBar::new(uploads.len() as u64);
         pb.set_message("Files uploaded:");
-        let shared_pb = Arc::new(Mutex::new(pb));
 
-        paths.par_iter().try_for_each(|path| {
-            let result = self.upload_single_asset(app_id, revision, path, &shared_pb);
-            if result.is_ok() {
-                let locked_pb = shared_pb.lock().unwrap();
-                locked_pb.inc(1);
-            }
-            result
-        })
+        stream::iter(uploads)
+            .map(|(path, delta)| async move {
+                if let Some(delta) = delta {
+                    match self
+                        .upload_asset_delta(client, app_id, *revision, path, delta)
+                        .await
+                    {
+                        Ok(()) => return Ok(()),
+                        Err(e) => {
+                            debug!("Delta upload of {path:?} failed, uploading whole file: {e}")
+                        }
+                    }
+                }
+                self.upload_single_asset(client, app_id, *revision, path)
+                    .await
+            })
+            .buffer_unordered(MAX_CONCURRENT_UPLOADS)
+            .try_for_each(|_| {
+                pb.inc(1);
+                future::ready(Ok(()))
+            })
+            .await?;
+        debug!("Files uploaded");
+
+        Ok(())
     }
 
-    fn upload_single_asset(
+    /// Sends only the chunks of `path` that differ from the base asset. The server rebuilds
+    /// the file and checks it against the new signature.
+    async fn upload_asset_delta(
         &self,
+        client: &reqwest::Client,
         app_id: &str,
         revision: u32,
         path: &Path,
-        _pb: &Arc<Mutex<ProgressBar>>,
+        delta: &Delta,
     ) -> Result<(), CommandError> {
-        let url = format!("{}/v4/assets", &self.api.authentication.config.url);
+        let url = format!("{}/{V4}/assets/delta", &self.api.authentication.config.url);
 
         let mut headers = HeaderMap::new();
         headers.insert("Prefer", "return=representation".parse()?);
 
-        debug!("Uploading file: {path:?}");
-        let form = reqwest::blocking::multipart::Form::new()
-            .text(
-                "title",
-                path.file_name()
-                    .ok_or(CommandError::FileSystemError(
-                        "Can't obtain file name".to_owned(),
-                    ))?
-                    .to_string_lossy()
-                    .to_string(),
-            )
+        let size = tokio::fs::metadata(path).await?.len();
+        let offsets: Vec<u64> = delta.chunks.iter().map(|chunk| chunk.start).collect();
+        debug!(
+            "Uploading {} changed chunks of {path:?}",
+            delta.chunks.len()
+        );
+        let file_name = path
+            .file_name()
+            .ok_or(CommandError::FileSystemError(
+                "Can't obtain file name".to_owned(),
+            ))?
+            .to_string_lossy()
+            .to_string();
+        let mut form = multipart::Form::new()
+            .text("title", file_name)
             .text("app_id", app_id.to_string())
             .text("app_revision", revision.to_string())
-            .file("file", path)?;
+            .text("base_signature", delta.base_signature.clone())
+            .text("signature", delta.signature.clone())
+            .text("size", size.to_string())
+            .text("offsets", serde_json::to_string(&offsets)?);
+        for chunk in &delta.chunks {
+            if chunk.start > chunk.end || chunk.end > size {
+                return Err(CommandError::SignatureMismatch(path.display().to_string()));
+            }
+            // Each part reads only its own range of the file while the request is sent.
+            let mut file = tokio::fs::File::open(path).await?;
+            file.seek(SeekFrom::Start(chunk.start)).await?;
+            let length = chunk.end - chunk.start;
+            form = form.part(
+                format!("chunk_{}", chunk.start),
+                multipart::Part::stream_with_length(
+                    Body::wrap_stream(ReaderStream::new(file.take(length))),
+                    length,
+                ),
+            );
+        }
 
-        let response = self
-            .api
-            .authentication
-            .build_client()?
+        let response = client
             .post(url)
             .multipart(form)
             .headers(headers)
-            .timeout(Duration::from_secs(3600)) // timeout is equal to server timeout
-            .send()?;
+            .timeout(Duration::from_secs(3600))
+            .send()
+            .await?;
 
         let status = response.status();
         if status != StatusCode::CREATED {
-            debug!("Response: {:?}", &response.text());
+            debug!("Response: {:?}", &response.text().await);
             return Err(CommandError::WrongResponseStatus(status.as_u16()));
         }
 
         Ok(())
     }
 
-    pub fn detect_version_metadata_changes(
+    async fn upload_single_asset(
         &self,
+        client: &reqwest::Client,
         app_id: &str,
-        manifest: &EdgeAppManifest,
-    ) -> Result<bool, CommandError> {
-        let version = self.api.get_latest_revision(app_id)?;
-        // TODO: implement entrypoint changes on the backend
-        match version {
-            Some(_version) => Ok(_version
-                != EdgeAppVersion {
-                    ready_signal: manifest.ready_signal.unwrap_or(false),
-                    user_version: manifest.user_version.clone(),
-                    description: manifest.description.clone(),
-                    icon: manifest.icon.clone(),
-                    author: manifest.author.clone(),
-                    homepage_url: manifest.homepage_url.clone(),
-                    categories: manifest.categories.clone(),
-                    revision: _version.revision,
-                }),
-            None => Ok(false),
+        revision: u32,
+        path: &Path,
+    ) -> Result<(), CommandError> {
+        let url = format!("{}/{V4}/assets", &self.api.authentication.config.url);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Prefer", "return=representation".parse()?);
+
+        debug!("Uploading file: {path:?}");
+        let file_name = path
+            .file_name()
+            .ok_or(CommandError::FileSystemError(
+                "Can't obtain file name".to_owned(),
+            ))?
+            .to_string_lossy()
+            .to_string();
+        let file = tokio::fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+        let part =
+            multipart::Part::stream_with_length(Body::wrap_stream(ReaderStream::new(file)), size)
+                .file_name(file_name.clone())
+                .mime_str(mime_guess::from_path(path).first_or_octet_stream().as_ref())?;
+        let form = multipart::Form::new()
+            .text("title", file_name)
+            .text("app_id", app_id.to_string())
+            .text("app_revision", revision.to_string())
+            .part("file", part);
+
+        let response = client
+            .post(url)
+            .multipart(form)
+            .headers(headers)
+            .timeout(Duration::from_secs(3600)) // timeout is equal to server timeout
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status != StatusCode::CREATED {
+            debug!("Response: {:?}", &response.text().await);
+            return Err(CommandError::WrongResponseStatus(status.as_u16()));
         }
+
+        Ok(())
     }
 
     pub fn get_installation_id(&self, path: Option<String>) -> Result<String, CommandError> {
@@ -596,6 +724,30 @@ impl EdgeAppCommand {
         }
     }
 
+    /// Lists the files of the Edge App at `path` that a deploy would upload. The build
+    /// section of the manifest is not run.
+    pub fn list_files(&self, path: Option<String>) -> Result<EdgeAppFileList, CommandError> {
+        let manifest_path = transform_edge_app_path_to_manifest(&path)?;
+        let edge_app_dir = manifest_path.parent().ok_or(CommandError::MissingField)?;
+        let files_dir = files_dir(edge_app_dir, &EdgeAppManifest::new(&manifest_path)?)?;
+        Ok(EdgeAppFileList {
+            paths: list_upload_paths(&files_dir, &self.ignorer(edge_app_dir)?)?,
+        })
+    }
+
+    /// Builds the ignore rules for the Edge App directory `dir`, the one holding the
+    /// manifest. They also apply to the build output directory inside it.
+    pub(crate) fn ignorer(&self, dir: &Path) -> Result<Ignorer, CommandError> {
+        let ignorer = if self.respect_gitignore {
+            Ignorer::with_gitignore(dir)
+        } else {
+            Ignorer::new(dir)
+        };
+        ignorer.map_err(|e| {
+            CommandError::IgnoreError(format!("Failed to initialize ignore module: {e}"))
+        })
+    }
+
     pub fn get_app_id(&self, path: Option<String>) -> Result<String, CommandError> {
         let edge_app_manifest = EdgeAppManifest::new(&transform_edge_app_path_to_manifest(&path)?)?;
         match edge_app_manifest.id {
@@ -610,15 +762,56 @@ mod tests {
     use std::env;
 
     use httpmock::Method::{DELETE, GET, PATCH, POST};
+    use httpmock::MockServer;
     use tempfile::tempdir;
 
     use super::*;
-    use crate::commands::edge_app::manifest::MANIFEST_VERSION;
+    use crate::commands::edge_app::manifest::{Build, MANIFEST_VERSION};
     use crate::commands::edge_app::test_utils::tests::{
         create_edge_app_manifest_for_test, create_instance_manifest_for_test,
         prepare_edge_apps_test,
     };
     use crate::commands::edge_app::utils::EdgeAppFile;
+    use crate::commands::{Formatter, OutputType};
+    use crate::signature::{generate_signature, sig_to_hex};
+
+    /// Runs a whole deploy the way the CLI does, with the upload on a top-level runtime.
+    fn deploy(
+        command: &EdgeAppCommand,
+        path: Option<String>,
+        delete_missing_settings: Option<bool>,
+        channel: Option<&str>,
+    ) -> Result<Option<u32>, CommandError> {
+        let Some(deploy) = command.prepare_deploy(path, delete_missing_settings)? else {
+            return Ok(None);
+        };
+        let client = command.api.authentication.client()?;
+        tokio::runtime::Runtime::new()?.block_on(command.upload_files(&client, &deploy))?;
+        command.finish_deploy(&deploy, channel).map(Some)
+    }
+
+    fn upload_changed_files(
+        command: &EdgeAppCommand,
+        edge_app_dir: &Path,
+        app_id: &str,
+        revision: u32,
+        changed_files: &FileChanges,
+        base_tree: &HashMap<String, String>,
+    ) -> Result<(), CommandError> {
+        let deploy = PendingDeploy {
+            app_id: app_id.to_owned(),
+            revision,
+            uploads: command.prepare_uploads(
+                edge_app_dir,
+                app_id,
+                revision,
+                changed_files,
+                base_tree,
+            )?,
+        };
+        let client = command.api.authentication.client()?;
+        tokio::runtime::Runtime::new()?.block_on(command.upload_files(&client, &deploy))
+    }
 
     #[test]
     fn test_edge_app_create_should_create_app_and_required_files() {
@@ -826,6 +1019,156 @@ mod tests {
             );
     }
 
+    #[test]
+    fn test_list_channels_should_return_channel_revisions() {
+        let (_tmp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+
+        let channels_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/channels")
+                .query_param("select", "channel,app_revision")
+                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
+                .query_param("order", "channel.asc")
+                .header("Authorization", "Token token");
+            then.status(200).json_body(json!([
+                {"channel": "candidate", "app_revision": 9},
+                {"channel": "stable", "app_revision": 8},
+                {"channel": "beta", "app_revision": null},
+            ]));
+        });
+
+        let channels = command.list_channels("01H2QZ6Z8WXWNDC0KQ198XCZEW").unwrap();
+        channels_mock.assert();
+
+        let output = channels.format(OutputType::HumanReadable);
+        assert!(output.contains("| candidate | 9        |"));
+        assert!(output.contains("| stable    | 8        |"));
+        assert!(output.contains("| beta      | N/A      |"));
+    }
+
+    #[test]
+    fn test_list_versions_should_attach_channels() {
+        let (_tmp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+
+        let versions_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/versions")
+                .query_param(
+                    "select",
+                    "revision,user_version,description,published,created_at",
+                )
+                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
+                .query_param("order", "revision.desc");
+            then.status(200).json_body(json!([
+                {"revision": 9, "user_version": "1.1", "published": true, "created_at": "2024-02-01T10:00:00Z"},
+                {"revision": 8, "user_version": "1.0", "published": true, "created_at": "2024-01-01T10:00:00Z"},
+                {"revision": 7, "user_version": null, "published": false, "created_at": "2023-12-01T10:00:00Z"},
+            ]));
+        });
+        let channels_mock = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/edge-apps/channels");
+            then.status(200).json_body(json!([
+                {"channel": "candidate", "app_revision": 9},
+                {"channel": "stable", "app_revision": 8},
+            ]));
+        });
+
+        let versions = command.list_versions("01H2QZ6Z8WXWNDC0KQ198XCZEW").unwrap();
+        versions_mock.assert();
+        channels_mock.assert();
+
+        let channels: Vec<_> = versions
+            .versions
+            .iter()
+            .map(|version| (version.revision, version.channels.clone()))
+            .collect();
+        assert_eq!(
+            channels,
+            vec![
+                (9, vec!["candidate".to_string()]),
+                (8, vec!["stable".to_string()]),
+                (7, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_show_version_should_include_file_tree() {
+        let (_tmp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+
+        let version_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/versions")
+                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
+                .query_param("revision", "eq.8");
+            then.status(200).json_body(json!([{
+                "revision": 8,
+                "user_version": "1.0",
+                "published": true,
+                "created_at": "2024-01-01T10:00:00Z",
+                "file_tree": {"index.html": "0a209f86d081884c7d659a2f", "app.js": "0a20aaaa"},
+            }]));
+        });
+        let missing_version_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/versions")
+                .query_param("revision", "eq.99");
+            then.status(200).json_body(json!([]));
+        });
+        let channels_mock = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/edge-apps/channels");
+            then.status(200)
+                .json_body(json!([{"channel": "stable", "app_revision": 8}]));
+        });
+
+        let details = command
+            .show_version("01H2QZ6Z8WXWNDC0KQ198XCZEW", 8)
+            .unwrap();
+        version_mock.assert();
+        channels_mock.assert();
+        assert_eq!(details.version.channels, vec!["stable".to_string()]);
+
+        let output = details.format(OutputType::HumanReadable);
+        assert!(output.contains("| index.html | 0a209f86d081884c |"));
+        assert!(output.find("app.js") < output.find("index.html"));
+
+        let result = command.show_version("01H2QZ6Z8WXWNDC0KQ198XCZEW", 99);
+        missing_version_mock.assert();
+        assert!(matches!(result, Err(CommandError::RevisionNotFound(_))));
+    }
+
+    #[test]
+    fn test_promote_version_should_update_channel() {
+        let (_tmp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+
+        let version_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/versions")
+                .query_param("select", "revision")
+                .query_param("revision", "eq.9");
+            then.status(200).json_body(json!([{"revision": 9}]));
+        });
+        let promote_mock = mock_server.mock(|when, then| {
+            when.method(PATCH)
+                .path("/v4/edge-apps/channels")
+                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
+                .query_param("channel", "eq.candidate")
+                .json_body(json!({"app_revision": 9}));
+            then.status(200)
+                .json_body(json!([{"channel": "candidate", "app_revision": 9}]));
+        });
+
+        let result = command.promote_version("01H2QZ6Z8WXWNDC0KQ198XCZEW", 9, "candidate");
+
+        version_mock.assert();
+        promote_mock.assert();
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_list_edge_apps_should_send_correct_request() {
         let (_tmp_dir, command, mock_server, _manifest, _instance_manifest) =
@@ -1169,13 +1512,15 @@ mod tests {
         let mut file = File::create(temp_dir.path().join("index.html")).unwrap();
         write!(file, "test").unwrap();
 
-        let result = command.deploy(
+        let result = deploy(
+            &command,
             Some(temp_dir.path().to_str().unwrap().to_string()),
             Some(true),
+            Some(DEFAULT_CHANNEL),
         );
 
         // get_entrypoint_mock.assert();
-        last_versions_mock.assert_calls(2);
+        last_versions_mock.assert();
         assets_mock.assert();
         file_tree_from_version_mock.assert();
         settings_mock.assert();
@@ -1194,7 +1539,7 @@ mod tests {
     }
 
     #[test]
-    fn test_detect_version_metadata_changes_when_no_changes_should_return_false() {
+    fn test_ensure_assets_processing_finished_when_processing_failed_should_return_error() {
         let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
             prepare_edge_apps_test(false, false);
 
@@ -1211,7 +1556,7 @@ mod tests {
             Setting {
                 name: "nsetting".to_string(),
                 type_: SettingType::String,
-                title: Some("ntitle".to_string()),
+                title: Some("atitle".to_string()),
                 optional: false,
                 default_value: Some("".to_string()),
                 is_global: false,
@@ -1219,222 +1564,29 @@ mod tests {
             },
         ]);
 
-        let last_versions_mock = mock_server.mock(|when, then| {
+        // "v4/assets?select=status&app_id=eq.{}&app_revision=eq.{}&status=neq.finished&limit=1",
+        let finished_processing_mock = mock_server.mock(|when, then| {
             when.method(GET)
-                .path("/v4.1/edge-apps/versions")
-                .header("Authorization", "Token token")
-                .header(
-                    "user-agent",
-                    format!("screenly-cli {}", env!("CARGO_PKG_VERSION")),
-                )
-                .query_param(
-                    "select",
-                    "user_version,description,icon,author,homepage_url,categories,revision,ready_signal",
-                )
+                .path("/v4/assets")
+                .query_param("select", "status,processing_error,title")
                 .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
-                .query_param("order", "revision.desc")
-                .query_param("limit", "1");
+                .query_param("app_revision", "eq.8")
+                .query_param("status", "neq.finished");
             then.status(200).json_body(json!([
                 {
-                    "user_version": "1",
-                    "description": "asdf",
-                    "icon": "asdf",
-                    "author": "asdf",
-                    "homepage_url": "asdfasdf",
-                    "categories": ["Utilities", "Dashboards"],
-                    "ready_signal": false,
-                    "revision": 1
+                    "status": "error",
+                    "title": "wrong_file.ext",
+                    "processing_error": "File type not supported."
                 }
             ]));
         });
 
         EdgeAppManifest::save_to_file(&manifest, temp_dir.path().join("screenly.yml").as_path())
             .unwrap();
+        let mut file = File::create(temp_dir.path().join("index.html")).unwrap();
+        write!(file, "test").unwrap();
 
-        let manifest =
-            EdgeAppManifest::new(temp_dir.path().join("screenly.yml").as_path()).unwrap();
-        let result =
-            command.detect_version_metadata_changes(&manifest.id.clone().unwrap(), &manifest);
-
-        assert!(result.is_ok());
-        assert!(!result.unwrap());
-        last_versions_mock.assert();
-    }
-
-    #[test]
-    fn test_detect_version_metadata_changes_when_has_changes_should_return_true() {
-        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
-            prepare_edge_apps_test(false, false);
-
-        let manifest = create_edge_app_manifest_for_test(vec![
-            Setting {
-                name: "asetting".to_string(),
-                type_: SettingType::String,
-                title: Some("atitle".to_string()),
-                optional: false,
-                default_value: Some("".to_string()),
-                is_global: false,
-                help_text: "help text".to_string(),
-            },
-            Setting {
-                name: "nsetting".to_string(),
-                type_: SettingType::String,
-                title: Some("ntitle".to_string()),
-                optional: false,
-                default_value: Some("".to_string()),
-                is_global: false,
-                help_text: "help text".to_string(),
-            },
-        ]);
-
-        let last_versions_mock = mock_server.mock(|when, then| {
-            when.method(GET)
-                .path("/v4.1/edge-apps/versions")
-                .header("Authorization", "Token token")
-                .header(
-                    "user-agent",
-                    format!("screenly-cli {}", env!("CARGO_PKG_VERSION")),
-                )
-                .query_param(
-                    "select",
-                    "user_version,description,icon,author,homepage_url,categories,revision,ready_signal",
-                )
-                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
-                .query_param("order", "revision.desc")
-                .query_param("limit", "1");
-            then.status(200).json_body(json!([
-                {
-                    "user_version": "new_version",
-                    "description": "description",
-                    "icon": "another_icon",
-                    "author": "asdf",
-                    "homepage_url": "asdfasdf",
-                    "categories": [],
-                    "ready_signal": false,
-                    "revision": 1,
-                }
-            ]));
-        });
-
-        EdgeAppManifest::save_to_file(&manifest, temp_dir.path().join("screenly.yml").as_path())
-            .unwrap();
-
-        let manifest =
-            EdgeAppManifest::new(temp_dir.path().join("screenly.yml").as_path()).unwrap();
-        let result =
-            command.detect_version_metadata_changes(&manifest.id.clone().unwrap(), &manifest);
-
-        assert!(result.is_ok());
-        assert!(result.unwrap());
-        last_versions_mock.assert();
-    }
-
-    #[test]
-    fn test_detect_version_metadata_changes_when_no_version_exist_should_return_false() {
-        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
-            prepare_edge_apps_test(false, false);
-
-        let manifest = create_edge_app_manifest_for_test(vec![
-            Setting {
-                name: "asetting".to_string(),
-                type_: SettingType::String,
-                title: Some("atitle".to_string()),
-                optional: false,
-                default_value: Some("".to_string()),
-                is_global: false,
-                help_text: "help text".to_string(),
-            },
-            Setting {
-                name: "nsetting".to_string(),
-                type_: SettingType::String,
-                title: Some("ntitle".to_string()),
-                optional: false,
-                default_value: Some("".to_string()),
-                is_global: false,
-                help_text: "help text".to_string(),
-            },
-        ]);
-
-        let last_versions_mock = mock_server.mock(|when, then| {
-            when.method(GET)
-                .path("/v4.1/edge-apps/versions")
-                .header("Authorization", "Token token")
-                .header(
-                    "user-agent",
-                    format!("screenly-cli {}", env!("CARGO_PKG_VERSION")),
-                )
-                .query_param(
-                    "select",
-                    "user_version,description,icon,author,homepage_url,categories,revision,ready_signal",
-                )
-                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
-                .query_param("order", "revision.desc")
-                .query_param("limit", "1");
-            then.status(200).json_body(json!([]));
-        });
-
-        EdgeAppManifest::save_to_file(&manifest, temp_dir.path().join("screenly.yml").as_path())
-            .unwrap();
-
-        let manifest =
-            EdgeAppManifest::new(temp_dir.path().join("screenly.yml").as_path()).unwrap();
-        let result =
-            command.detect_version_metadata_changes(&manifest.id.clone().unwrap(), &manifest);
-
-        assert!(result.is_ok());
-        assert!(!result.unwrap());
-        last_versions_mock.assert();
-    }
-
-    #[test]
-    fn test_ensure_assets_processing_finished_when_processing_failed_should_return_error() {
-        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
-            prepare_edge_apps_test(false, false);
-
-        let manifest = create_edge_app_manifest_for_test(vec![
-            Setting {
-                name: "asetting".to_string(),
-                type_: SettingType::String,
-                title: Some("atitle".to_string()),
-                optional: false,
-                default_value: Some("".to_string()),
-                is_global: false,
-                help_text: "help text".to_string(),
-            },
-            Setting {
-                name: "nsetting".to_string(),
-                type_: SettingType::String,
-                title: Some("atitle".to_string()),
-                optional: false,
-                default_value: Some("".to_string()),
-                is_global: false,
-                help_text: "help text".to_string(),
-            },
-        ]);
-
-        // "v4/assets?select=status&app_id=eq.{}&app_revision=eq.{}&status=neq.finished&limit=1",
-        let finished_processing_mock = mock_server.mock(|when, then| {
-            when.method(GET)
-                .path("/v4/assets")
-                .query_param("select", "status,processing_error,title")
-                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
-                .query_param("app_revision", "eq.8")
-                .query_param("status", "neq.finished");
-            then.status(200).json_body(json!([
-                {
-                    "status": "error",
-                    "title": "wrong_file.ext",
-                    "processing_error": "File type not supported."
-                }
-            ]));
-        });
-
-        EdgeAppManifest::save_to_file(&manifest, temp_dir.path().join("screenly.yml").as_path())
-            .unwrap();
-        let mut file = File::create(temp_dir.path().join("index.html")).unwrap();
-        write!(file, "test").unwrap();
-
-        let result = command.ensure_assets_processing_finished("01H2QZ6Z8WXWNDC0KQ198XCZEW", 8);
+        let result = command.ensure_assets_processing_finished("01H2QZ6Z8WXWNDC0KQ198XCZEW", 8);
 
         finished_processing_mock.assert();
 
@@ -1525,11 +1677,127 @@ mod tests {
                 uri: None,
             }),
             settings: vec![],
+            build: None,
         };
 
         assert_eq!(new_manifest, expected_manifest);
     }
 
+    /// Writes an app whose files and metadata match revision 7 on the mock server.
+    fn prepare_unchanged_deploy_test(
+        temp_dir: &Path,
+        mock_server: &MockServer,
+        settings: Vec<Setting>,
+    ) {
+        let mut manifest = create_edge_app_manifest_for_test(settings);
+        manifest.user_version = None;
+        manifest.author = None;
+        manifest.entrypoint = None;
+
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4.1/edge-apps/versions")
+                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW");
+            then.status(200).json_body(json!([{
+                "user_version": null,
+                "description": "asdf",
+                "icon": "asdf",
+                "author": null,
+                "homepage_url": "asdfasdf",
+                "categories": ["Utilities", "Dashboards"],
+                "ready_signal": false,
+                "revision": 7,
+            }]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/assets")
+                .query_param("app_revision", "eq.7");
+            then.status(200).json_body(json!([{"signature": "0a209f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08122086cebd0c365d241e32d5b0972c07aae3a8d6499c2a9471aa85943a35577200021a180a14a94a8fe5ccb19ba61c4c0873d391e987982fbbd31000"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/versions")
+                .query_param("revision", "eq.7")
+                .query_param("select", "file_tree");
+            then.status(200)
+                .json_body(json!([{"file_tree": {"index.html": "0a209f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08122086cebd0c365d241e32d5b0972c07aae3a8d6499c2a9471aa85943a35577200021a180a14a94a8fe5ccb19ba61c4c0873d391e987982fbbd31000"}}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4.1/edge-apps/settings");
+            then.status(200).json_body(json!([]));
+        });
+
+        EdgeAppManifest::save_to_file(&manifest, temp_dir.join("screenly.yml").as_path()).unwrap();
+        fs::write(temp_dir.join("index.html"), "test").unwrap();
+    }
+
+    #[test]
+    fn test_deploy_when_only_settings_changed_should_write_settings_without_new_version() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        prepare_unchanged_deploy_test(
+            temp_dir.path(),
+            &mock_server,
+            vec![Setting {
+                name: "asetting".to_string(),
+                type_: SettingType::String,
+                title: Some("atitle".to_string()),
+                optional: false,
+                default_value: Some("".to_string()),
+                is_global: false,
+                help_text: "help text".to_string(),
+            }],
+        );
+        let settings_create_mock = mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/v4.1/edge-apps/settings")
+                .json_body_includes(r#"{"name": "asetting"}"#);
+            then.status(201).json_body(json!([]));
+        });
+        let create_version_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/edge-apps/versions");
+            then.status(201).json_body(json!([{"revision": 8}]));
+        });
+
+        let result = deploy(
+            &command,
+            Some(temp_dir.path().to_str().unwrap().to_string()),
+            Some(true),
+            None,
+        );
+
+        assert!(matches!(result, Ok(None)));
+        settings_create_mock.assert();
+        create_version_mock.assert_calls(0);
+    }
+
+    #[test]
+    fn test_deploy_without_any_changes_should_fail() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        prepare_unchanged_deploy_test(temp_dir.path(), &mock_server, vec![]);
+        let settings_create_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4.1/edge-apps/settings");
+            then.status(201).json_body(json!([]));
+        });
+        let create_version_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/edge-apps/versions");
+            then.status(201).json_body(json!([{"revision": 8}]));
+        });
+
+        let result = deploy(
+            &command,
+            Some(temp_dir.path().to_str().unwrap().to_string()),
+            Some(true),
+            None,
+        );
+
+        assert!(matches!(result, Err(CommandError::NoChangesToUpload(_))));
+        settings_create_mock.assert_calls(0);
+        create_version_mock.assert_calls(0);
+    }
+
     #[test]
     fn test_deploy_without_app_id_should_fail() {
         let (temp_dir, command, _mock_server, _manifest, _instance_manifest) =
@@ -1564,9 +1832,11 @@ mod tests {
         let mut file = File::create(temp_dir.path().join("index.html")).unwrap();
         write!(file, "test").unwrap();
 
-        let result = command.deploy(
+        let result = deploy(
+            &command,
             Some(temp_dir.path().to_str().unwrap().to_string()),
             Some(true),
+            Some(DEFAULT_CHANNEL),
         );
 
         assert!(result.is_err());
@@ -1613,6 +1883,7 @@ mod tests {
                     help_text: "asdf".to_string(),
                 },
             ],
+            build: None,
         };
 
         let copy_assets_mock = mock_server.mock(|when, then| {
@@ -1677,11 +1948,13 @@ mod tests {
             true,
         );
 
-        let result = command.upload_changed_files(
+        let result = upload_changed_files(
+            &command,
             edge_app_dir,
             "01H2QZ6Z8WXWNDC0KQ198XCZEW",
             7,
             &changed_files,
+            &HashMap::new(),
         );
 
         // Twice for somesig1 and somesig2
@@ -1729,6 +2002,7 @@ mod tests {
                     help_text: "asdf".to_string(),
                 },
             ],
+            build: None,
         };
 
         let copy_assets_mock = mock_server.mock(|when, then| {
@@ -1786,11 +2060,13 @@ mod tests {
             true,
         );
 
-        let result = command.upload_changed_files(
+        let result = upload_changed_files(
+            &command,
             edge_app_dir,
             "01H2QZ6Z8WXWNDC0KQ198XCZEW",
             7,
             &changed_files,
+            &HashMap::new(),
         );
 
         upload_assets_mock.assert_calls(0);
@@ -1799,6 +2075,148 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn prepare_delta_upload_test(temp_dir: &Path) -> (FileChanges, HashMap<String, String>) {
+        let path = temp_dir.join("video.mp4");
+        let mut content = vec![b'a'; 512 * 1024 * 3];
+        fs::write(&path, &content).unwrap();
+        let base_signature = sig_to_hex(&generate_signature(&path).unwrap());
+
+        content[512 * 1024 + 7] = b'b';
+        fs::write(&path, &content).unwrap();
+        let signature = sig_to_hex(&generate_signature(&path).unwrap());
+
+        (
+            FileChanges::new(
+                &[EdgeAppFile {
+                    path: "video.mp4".to_owned(),
+                    signature,
+                }],
+                true,
+            ),
+            HashMap::from([("video.mp4".to_owned(), base_signature)]),
+        )
+    }
+
+    #[test]
+    fn test_upload_changed_files_when_server_supports_deltas_should_send_changed_chunks_only() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        let (changed_files, base_tree) = prepare_delta_upload_test(temp_dir.path());
+
+        mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/edge-apps/copy-assets");
+            then.status(201).json_body(json!([]));
+        });
+        let capabilities_mock = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/assets/capabilities");
+            then.status(200).json_body(json!({"delta_uploads": true}));
+        });
+        let delta_mock = mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/v4/assets/delta")
+                .body_includes(&base_tree["video.mp4"])
+                .body_includes("name=\"chunk_524288\"")
+                .body_includes("[524288]")
+                .body_includes("aaaaaaabaaaa")
+                .body_excludes("name=\"chunk_0\"")
+                .body_excludes("name=\"chunk_1048576\"");
+            then.status(201).body("");
+        });
+        let upload_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/assets");
+            then.status(201).body("");
+        });
+
+        let result = upload_changed_files(
+            &command,
+            temp_dir.path(),
+            "01H2QZ6Z8WXWNDC0KQ198XCZEW",
+            7,
+            &changed_files,
+            &base_tree,
+        );
+
+        assert!(result.is_ok());
+        capabilities_mock.assert();
+        delta_mock.assert();
+        upload_mock.assert_calls(0);
+    }
+
+    #[test]
+    fn test_upload_changed_files_when_server_lacks_delta_support_should_upload_whole_file() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        let (changed_files, base_tree) = prepare_delta_upload_test(temp_dir.path());
+
+        mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/edge-apps/copy-assets");
+            then.status(201).json_body(json!([]));
+        });
+        let capabilities_mock = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/assets/capabilities");
+            then.status(404);
+        });
+        let delta_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/assets/delta");
+            then.status(201).body("");
+        });
+        let upload_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/assets");
+            then.status(201).body("");
+        });
+
+        let result = upload_changed_files(
+            &command,
+            temp_dir.path(),
+            "01H2QZ6Z8WXWNDC0KQ198XCZEW",
+            7,
+            &changed_files,
+            &base_tree,
+        );
+
+        assert!(result.is_ok());
+        capabilities_mock.assert();
+        delta_mock.assert_calls(0);
+        upload_mock.assert();
+    }
+
+    #[test]
+    fn test_upload_changed_files_when_delta_is_rejected_should_fall_back_to_full_upload() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        let (changed_files, base_tree) = prepare_delta_upload_test(temp_dir.path());
+
+        mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/edge-apps/copy-assets");
+            then.status(201).json_body(json!([]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/assets/capabilities");
+            then.status(200).json_body(json!({"delta_uploads": true}));
+        });
+        let delta_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/assets/delta");
+            then.status(409).body("base asset missing");
+        });
+        let upload_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/assets");
+            then.status(201).body("");
+        });
+
+        let result = upload_changed_files(
+            &command,
+            temp_dir.path(),
+            "01H2QZ6Z8WXWNDC0KQ198XCZEW",
+            7,
+            &changed_files,
+            &base_tree,
+        );
+
+        assert!(result.is_ok());
+        delta_mock.assert();
+        upload_mock.assert();
+    }
+
     #[test]
     fn test_maybe_delete_missing_settings_when_ci_is_1_and_no_arg_provided_should_ignore_deleting_settings(
     ) {
@@ -1823,12 +2241,67 @@ mod tests {
             let result = command.maybe_delete_missing_settings(
                 None,
                 "01H2QZ6Z8WXWNDC0KQ198XCZEW".to_string(),
-                changed_settings,
+                &changed_settings,
             );
             assert!(result.is_ok());
         });
     }
 
+    #[test]
+    fn test_list_files_should_apply_ignore_files_and_optionally_gitignore() {
+        let (temp_dir, command, _mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(true, false);
+        fs::create_dir(temp_dir.path().join("js")).unwrap();
+        fs::write(temp_dir.path().join("index.html"), "test").unwrap();
+        fs::write(temp_dir.path().join("js/main.js"), "main").unwrap();
+        fs::write(temp_dir.path().join("js/main.js.map"), "map").unwrap();
+        fs::write(temp_dir.path().join("notes.md"), "notes").unwrap();
+        fs::write(temp_dir.path().join(".ignore"), "# docs\n*.md\n").unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "*.map\n").unwrap();
+        let path = Some(temp_dir.path().to_str().unwrap().to_string());
+
+        let files = command.list_files(path.clone()).unwrap();
+        assert_eq!(
+            files.paths,
+            vec![".gitignore", "index.html", "js/main.js", "js/main.js.map"]
+        );
+
+        let files = command.with_gitignore(true).list_files(path).unwrap();
+        assert_eq!(files.paths, vec![".gitignore", "index.html", "js/main.js"]);
+        assert_eq!(
+            files.format(OutputType::HumanReadable),
+            ".gitignore\nindex.html\njs/main.js"
+        );
+    }
+
+    #[test]
+    fn test_list_files_when_manifest_has_output_dir_should_list_build_output() {
+        let (temp_dir, command, _mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        let manifest = EdgeAppManifest {
+            syntax: MANIFEST_VERSION.to_owned(),
+            build: Some(Build {
+                command: "npm run build".to_owned(),
+                output_dir: Some("dist".to_owned()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        EdgeAppManifest::save_to_file(&manifest, &temp_dir.path().join("screenly.yml")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("dist/assets")).unwrap();
+        fs::write(temp_dir.path().join("index.ts"), "source").unwrap();
+        fs::write(temp_dir.path().join("dist/index.html"), "built").unwrap();
+        fs::write(temp_dir.path().join("dist/assets/app.js"), "built").unwrap();
+        fs::write(temp_dir.path().join("dist/assets/app.js.map"), "built").unwrap();
+        fs::write(temp_dir.path().join(".ignore"), "*.map\n").unwrap();
+
+        let files = command
+            .list_files(Some(temp_dir.path().to_str().unwrap().to_string()))
+            .unwrap();
+
+        assert_eq!(files.paths, vec!["assets/app.js", "index.html"]);
+    }
+
     #[test]
     fn test_get_installation_id_when_manifest_has_id_should_return_id() {
         let (temp_dir, command, _mock_server, _manifest, _instance_manifest) =
@@ -0,0 +1,151 @@
+//! The `build` section of the manifest: a command run before deploying or emulating the
+//! app, and the directory its output is taken from.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use log::info;
+
+use crate::commands::edge_app::manifest::{Build, EdgeAppManifest};
+use crate::commands::edge_app::EdgeAppCommand;
+use crate::commands::CommandError;
+
+/// Resolves a directory of the build section relative to `edge_app_dir`, rejecting paths
+/// that leave it.
+fn resolve_dir(edge_app_dir: &Path, dir: Option<&str>) -> Result<PathBuf, CommandError> {
+    let Some(dir) = dir else {
+        return Ok(edge_app_dir.to_path_buf());
+    };
+    let relative = Path::new(dir);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(CommandError::InvalidManifest(format!(
+            "build directory must be inside the Edge App directory: {dir}"
+        )));
+    }
+    Ok(edge_app_dir.join(relative))
+}
+
+/// Returns the directory the app's files are collected from: the build output
+/// directory if the manifest has one, the manifest's directory otherwise.
+pub fn files_dir(edge_app_dir: &Path, manifest: &EdgeAppManifest) -> Result<PathBuf, CommandError> {
+    resolve_dir(
+        edge_app_dir,
+        manifest
+            .build
+            .as_ref()
+            .and_then(|build| build.output_dir.as_deref()),
+    )
+}
+
+/// Runs the build command through the shell. Its output goes straight to the terminal.
+pub fn run_build(edge_app_dir: &Path, build: &Build) -> Result<(), CommandError> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+
+    let working_dir = resolve_dir(edge_app_dir, build.working_dir.as_deref())?;
+    info!("Building Edge App: {}", build.command);
+    let status = Command::new(shell)
+        .arg(flag)
+        .arg(&build.command)
+        .current_dir(&working_dir)
+        .envs(&build.env)
+        .status()?;
+
+    if !status.success() {
+        return Err(CommandError::HookFailed(
+            build.command.clone(),
+            status.to_string(),
+        ));
+    }
+
+    let output_dir = resolve_dir(edge_app_dir, build.output_dir.as_deref())?;
+    if !output_dir.is_dir() {
+        return Err(CommandError::PathIsNotDirError(
+            output_dir.display().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl EdgeAppCommand {
+    /// Runs the build section of `manifest`, unless there is none or building was
+    /// disabled with [`EdgeAppCommand::with_build`].
+    pub(crate) fn build(
+        &self,
+        edge_app_dir: &Path,
+        manifest: &EdgeAppManifest,
+    ) -> Result<(), CommandError> {
+        match &manifest.build {
+            Some(build) if self.run_build => run_build(edge_app_dir, build),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn build(command: &str) -> Build {
+        Build {
+            command: command.to_owned(),
+            working_dir: Some("app".to_owned()),
+            output_dir: Some("app/dist".to_owned()),
+            env: BTreeMap::from([("GREETING".to_owned(), "hello".to_owned())]),
+        }
+    }
+
+    #[test]
+    fn test_run_build_should_run_command_in_working_dir_with_env() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("app")).unwrap();
+
+        run_build(
+            dir.path(),
+            &build("mkdir -p dist && echo $GREETING > dist/index.html"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("app/dist/index.html")).unwrap(),
+            "hello\n"
+        );
+    }
+
+    #[test]
+    fn test_run_build_when_command_fails_should_return_error() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("app")).unwrap();
+
+        let result = run_build(dir.path(), &build("exit 3"));
+
+        assert!(matches!(result, Err(CommandError::HookFailed(command, _)) if command == "exit 3"));
+    }
+
+    #[test]
+    fn test_files_dir_should_use_output_dir_and_reject_paths_outside_app() {
+        let dir = Path::new("/tmp/app");
+        let mut manifest = EdgeAppManifest::default();
+        assert_eq!(files_dir(dir, &manifest).unwrap(), dir);
+
+        manifest.build = Some(build("true"));
+        assert_eq!(
+            files_dir(dir, &manifest).unwrap(),
+            PathBuf::from("/tmp/app/app/dist")
+        );
+
+        manifest.build.as_mut().unwrap().output_dir = Some("../dist".to_owned());
+        assert!(files_dir(dir, &manifest).is_err());
+    }
+}
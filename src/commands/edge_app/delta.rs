@@ -0,0 +1,117 @@
+//! Block-level delta uploads.
+//!
+//! A file signature holds a hash per fixed-size chunk. When a file existed at the same
+//! path in the base revision, only the chunks whose hashes differ need to be sent; the
+//! server rebuilds the file from the base asset and verifies it against the new signature.
+
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use protobuf::Message;
+
+use crate::pb_signature::Signature;
+
+/// The part of a file to send instead of its full content.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Delta {
+    pub base_signature: String,
+    pub signature: String,
+    /// Byte ranges of the chunks that differ from the base asset.
+    pub chunks: Vec<Range<u64>>,
+}
+
+impl Delta {
+    /// Computes the delta of the file at `path` against the asset with `base_signature`.
+    /// Returns `None` if either signature can't be decoded or a delta wouldn't help.
+    pub fn new(path: &Path, base_signature: &str, signature: &str) -> Option<Self> {
+        let size = fs::metadata(path).ok()?.len();
+        let chunks = changed_chunks(
+            &parse_signature(base_signature)?,
+            &parse_signature(signature)?,
+            size,
+        )?;
+        Some(Self {
+            base_signature: base_signature.to_owned(),
+            signature: signature.to_owned(),
+            chunks,
+        })
+    }
+}
+
+/// Decodes a hex signature as stored in file trees.
+pub fn parse_signature(signature: &str) -> Option<Signature> {
+    Signature::parse_from_bytes(&hex::decode(signature).ok()?).ok()
+}
+
+/// Returns the byte ranges of the chunks of a `size` byte file with signature `local`
+/// that differ from `base`, or `None` when a delta wouldn't save anything.
+pub fn changed_chunks(base: &Signature, local: &Signature, size: u64) -> Option<Vec<Range<u64>>> {
+    let mut chunks = Vec::new();
+    for (index, hash) in local.hashes.iter().enumerate() {
+        let unchanged = base
+            .hashes
+            .get(index)
+            .is_some_and(|base| base.offset() == hash.offset() && base.hash() == hash.hash());
+        if unchanged {
+            continue;
+        }
+        let end = local
+            .hashes
+            .get(index + 1)
+            .map_or(size, |next| next.offset() as u64);
+        chunks.push(hash.offset() as u64..end);
+    }
+
+    (chunks.len() < local.hashes.len()).then_some(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::signature::{generate_signature, sig_to_hex};
+
+    const CHUNK: usize = 512 * 1024;
+
+    #[test]
+    fn test_changed_chunks_should_only_return_modified_chunks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("video.mp4");
+        let mut content = vec![b'a'; CHUNK * 2 + 100];
+        fs::write(&path, &content).unwrap();
+        let base = generate_signature(&path).unwrap();
+
+        content[CHUNK + 1] = b'b';
+        content.extend_from_slice(b"tail");
+        fs::write(&path, &content).unwrap();
+        let local = generate_signature(&path).unwrap();
+
+        let chunks = changed_chunks(&base, &local, content.len() as u64).unwrap();
+
+        assert_eq!(
+            chunks,
+            vec![
+                CHUNK as u64..CHUNK as u64 * 2,
+                CHUNK as u64 * 2..content.len() as u64
+            ]
+        );
+        assert_eq!(parse_signature(&sig_to_hex(&local)), Some(local));
+    }
+
+    #[test]
+    fn test_changed_chunks_when_every_chunk_changed_should_return_none() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("index.html");
+        fs::write(&path, "before").unwrap();
+        let base = generate_signature(&path).unwrap();
+        fs::write(&path, "after").unwrap();
+        let local = generate_signature(&path).unwrap();
+
+        assert_eq!(changed_chunks(&base, &local, 5), None);
+        assert_eq!(parse_signature("not hex"), None);
+    }
+}
@@ -0,0 +1,297 @@
+//! Comparison of the local Edge App tree with a deployed revision.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::commands::edge_app::build::files_dir;
+use crate::commands::edge_app::manifest::EdgeAppManifest;
+use crate::commands::edge_app::plan::{
+    format_metadata_lines, format_settings_lines, metadata_changes, MetadataChange, SettingsPlan,
+};
+use crate::commands::edge_app::utils::{
+    collect_paths_for_upload, detect_changed_settings, generate_file_tree,
+    transform_edge_app_path_to_manifest,
+};
+use crate::commands::edge_app::EdgeAppCommand;
+use crate::commands::{CommandError, Formatter, OutputType};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RenamedFile {
+    pub from: String,
+    pub to: String,
+}
+
+/// File changes between two file trees, each sorted by path.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct FileTreeDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+    /// Files with an identical signature at a new path.
+    pub renamed: Vec<RenamedFile>,
+}
+
+impl FileTreeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.renamed.is_empty()
+    }
+}
+
+/// Compares file trees (path to signature) of a revision and the local app.
+pub fn diff_file_trees(
+    remote: &HashMap<String, String>,
+    local: &HashMap<String, String>,
+) -> FileTreeDiff {
+    let remote: BTreeMap<_, _> = remote.iter().collect();
+    let local: BTreeMap<_, _> = local.iter().collect();
+
+    let mut diff = FileTreeDiff::default();
+    let mut removed: Vec<(&String, &String)> = Vec::new();
+
+    for (path, signature) in &remote {
+        match local.get(path) {
+            Some(local_signature) if local_signature != signature => {
+                diff.modified.push(path.to_string())
+            }
+            Some(_) => {}
+            None => removed.push((path, signature)),
+        }
+    }
+
+    for (path, signature) in &local {
+        if remote.contains_key(path) {
+            continue;
+        }
+        match removed.iter().position(|(_, removed)| removed == signature) {
+            Some(index) => {
+                let (from, _) = removed.remove(index);
+                diff.renamed.push(RenamedFile {
+                    from: from.to_string(),
+                    to: path.to_string(),
+                });
+            }
+            None => diff.added.push(path.to_string()),
+        }
+    }
+
+    diff.removed = removed.into_iter().map(|(path, _)| path.clone()).collect();
+    diff
+}
+
+/// Differences between the local Edge App and a deployed revision.
+#[derive(Debug, Serialize)]
+pub struct EdgeAppDiff {
+    pub app_id: String,
+    /// Revision compared against, or `None` if the app has not been deployed yet.
+    pub revision: Option<u32>,
+    pub files: FileTreeDiff,
+    pub settings: SettingsPlan,
+    pub metadata: Vec<MetadataChange>,
+}
+
+impl EdgeAppDiff {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+            && self.settings == SettingsPlan::default()
+            && self.metadata.is_empty()
+    }
+}
+
+impl EdgeAppCommand {
+    /// Compares the Edge App at `path` with `revision`, or the latest revision when `None`.
+    /// The `build` section of the manifest runs first.
+    pub fn diff(
+        &self,
+        path: Option<String>,
+        revision: Option<u32>,
+    ) -> Result<EdgeAppDiff, CommandError> {
+        let manifest_path = transform_edge_app_path_to_manifest(&path)?;
+        let manifest = EdgeAppManifest::new(&manifest_path)?;
+        let app_id = self.get_app_id(path)?;
+        let edge_app_dir = manifest_path.parent().ok_or(CommandError::MissingField)?;
+        self.build(edge_app_dir, &manifest)?;
+        let files_dir = files_dir(edge_app_dir, &manifest)?;
+
+        let version = match revision {
+            Some(revision) => Some(
+                self.api
+                    .get_revision(&app_id, revision)?
+                    .ok_or(CommandError::RevisionNotFound(revision.to_string()))?,
+            ),
+            None => self.api.get_latest_revision(&app_id)?,
+        };
+
+        let local_tree = generate_file_tree(
+            &collect_paths_for_upload(
+                &files_dir,
+                &self.ignorer(edge_app_dir)?,
+                self.signature_cache,
+            )?,
+            &files_dir,
+        );
+        let (files, metadata) = match &version {
+            Some(version) => (
+                diff_file_trees(
+                    &self.api.get_file_tree(&app_id, version.revision)?,
+                    &local_tree,
+                ),
+                metadata_changes(version, &manifest),
+            ),
+            None => (diff_file_trees(&HashMap::new(), &local_tree), Vec::new()),
+        };
+
+        let remote_settings = self.api.get_settings(&app_id)?;
+        let settings = SettingsPlan::from(&detect_changed_settings(&manifest, &remote_settings)?);
+
+        Ok(EdgeAppDiff {
+            app_id,
+            revision: version.map(|version| version.revision),
+            files,
+            settings,
+            metadata,
+        })
+    }
+}
+
+impl Formatter for EdgeAppDiff {
+    fn format(&self, output_type: OutputType) -> String {
+        match output_type {
+            OutputType::HumanReadable => {
+                let mut lines = vec![match self.revision {
+                    Some(revision) => {
+                        format!("Edge App {} compared with revision {revision}", self.app_id)
+                    }
+                    None => format!("Edge App {} has no deployed revisions", self.app_id),
+                }];
+
+                lines.push("Files:".to_owned());
+                let files = &self.files;
+                for path in &files.added {
+                    lines.push(format!("  added:    {path}"));
+                }
+                for path in &files.modified {
+                    lines.push(format!("  modified: {path}"));
+                }
+                for path in &files.removed {
+                    lines.push(format!("  removed:  {path}"));
+                }
+                for renamed in &files.renamed {
+                    lines.push(format!("  renamed:  {} -> {}", renamed.from, renamed.to));
+                }
+                if files.is_empty() {
+                    lines.push("  no changes".to_owned());
+                }
+
+                format_settings_lines(&self.settings, &mut lines);
+                format_metadata_lines(&self.metadata, &mut lines);
+                lines.join("\n")
+            }
+            OutputType::Json => serde_json::to_string_pretty(self).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use httpmock::Method::GET;
+    use serde_json::json;
+
+    use super::*;
+    use crate::commands::edge_app::test_utils::tests::prepare_edge_apps_test;
+
+    fn tree(files: &[(&str, &str)]) -> HashMap<String, String> {
+        files
+            .iter()
+            .map(|(path, signature)| (path.to_string(), signature.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_file_trees_should_detect_all_kinds_of_changes() {
+        let remote = tree(&[
+            ("index.html", "a"),
+            ("style.css", "b"),
+            ("old.js", "c"),
+            ("logo.png", "d"),
+        ]);
+        let local = tree(&[
+            ("index.html", "a"),
+            ("style.css", "b2"),
+            ("assets/logo.png", "d"),
+            ("new.js", "e"),
+        ]);
+
+        let diff = diff_file_trees(&remote, &local);
+
+        assert_eq!(
+            diff,
+            FileTreeDiff {
+                added: vec!["new.js".to_string()],
+                removed: vec!["old.js".to_string()],
+                modified: vec!["style.css".to_string()],
+                renamed: vec![RenamedFile {
+                    from: "logo.png".to_string(),
+                    to: "assets/logo.png".to_string(),
+                }],
+            }
+        );
+        assert!(diff_file_trees(&remote, &remote).is_empty());
+    }
+
+    #[test]
+    fn test_diff_against_revision_should_compare_with_its_file_tree() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(true, false);
+        fs::write(temp_dir.path().join("index.html"), "test").unwrap();
+
+        let revision_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4.1/edge-apps/versions")
+                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
+                .query_param("revision", "eq.5");
+            then.status(200).json_body(json!([{
+                "user_version": "1",
+                "description": "asdf",
+                "icon": "asdf",
+                "author": "asdf",
+                "homepage_url": "asdfasdf",
+                "categories": ["Utilities", "Dashboards"],
+                "ready_signal": false,
+                "revision": 5,
+            }]));
+        });
+        let file_tree_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/versions")
+                .query_param("select", "file_tree")
+                .query_param("revision", "eq.5");
+            then.status(200)
+                .json_body(json!([{"file_tree": {"index.html": "outdated"}}]));
+        });
+        let settings_mock = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4.1/edge-apps/settings");
+            then.status(200).json_body(json!([]));
+        });
+
+        let diff = command
+            .diff(Some(temp_dir.path().to_str().unwrap().to_string()), Some(5))
+            .unwrap();
+
+        revision_mock.assert();
+        file_tree_mock.assert();
+        settings_mock.assert();
+        assert_eq!(diff.revision, Some(5));
+        assert_eq!(diff.files.modified, vec!["index.html".to_string()]);
+        assert!(diff.metadata.is_empty());
+        assert!(!diff.is_empty());
+        assert!(diff
+            .format(OutputType::HumanReadable)
+            .contains("modified: index.html"));
+    }
+}
@@ -1,6 +1,8 @@
 use std::path::Path;
 use std::{fs, str};
 
+use log::{info, warn};
+
 use super::EdgeAppCommand;
 use crate::commands::edge_app::instance_manifest::{InstanceManifest, INSTANCE_MANIFEST_VERSION};
 use crate::commands::edge_app::utils::transform_instance_path_to_instance_manifest;
@@ -46,10 +48,10 @@ impl EdgeAppCommand {
         self.api.delete_installation(installation_id)?;
         match fs::remove_file(manifest_path) {
             Ok(_) => {
-                println!("Instance manifest file removed.")
+                info!("Instance manifest file removed.")
             }
             Err(_) => {
-                println!("Failed to remove instance manifest file.")
+                warn!("Failed to remove instance manifest file.")
             }
         };
         Ok(())
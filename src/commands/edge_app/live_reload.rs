@@ -0,0 +1,227 @@
+//! Live reload for the Edge App emulator.
+//!
+//! The served files (respecting the ignore files) along with `screenly.yml` and
+//! `mock-data.yml` are watched for changes, and connected pages are told to reload
+//! through a server-sent events endpoint. Pages subscribe via a small script that
+//! `screenly.js` loads.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use log::{debug, info, warn};
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+
+use crate::commands::edge_app::utils::is_excluded;
+use crate::commands::ignorer::Ignorer;
+
+/// Path the emulator serves the Edge App from.
+pub const BASE_PATH: &str = "/edge/1";
+/// Name of the script served next to `screenly.js`.
+pub const SCRIPT_FILENAME: &str = "screenly-live-reload.js";
+/// Path of the server-sent events endpoint, below [`BASE_PATH`].
+pub const EVENTS_PATH: &str = "__live-reload";
+
+/// Changes closer together than this cause a single reload.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Appended to `screenly.js` to load the [`script`].
+pub fn loader() -> String {
+    format!(
+        r#"
+(function () {{
+    var script = document.createElement("script");
+    script.src = "{BASE_PATH}/{SCRIPT_FILENAME}";
+    (document.head || document.documentElement).appendChild(script);
+}})();"#
+    )
+}
+
+/// Reloads the page whenever the events endpoint sends a `reload` event.
+pub fn script() -> String {
+    format!(
+        r#"(function () {{
+    var events = new EventSource("{BASE_PATH}/{EVENTS_PATH}");
+    events.addEventListener("reload", function () {{
+        window.location.reload();
+    }});
+}})();"#
+    )
+}
+
+/// Decides which changed paths are worth a reload.
+struct WatchFilter {
+    files_dir: PathBuf,
+    ignorer: Arc<Ignorer>,
+    extra_files: Vec<PathBuf>,
+}
+
+impl WatchFilter {
+    fn matches(&self, path: &Path) -> bool {
+        if self.extra_files.iter().any(|file| file == path) {
+            return true;
+        }
+        match path.strip_prefix(&self.files_dir) {
+            Ok(relative_path) => {
+                !relative_path.as_os_str().is_empty()
+                    && !is_excluded(relative_path)
+                    && !self.ignorer.is_ignored(path)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Pages connected to the events endpoint.
+#[derive(Default)]
+pub struct LiveReload {
+    clients: Mutex<Vec<UnboundedSender<()>>>,
+    watcher: Mutex<Option<Debouncer<RecommendedWatcher>>>,
+}
+
+impl LiveReload {
+    /// Registers a page. The receiver yields an item whenever the page should reload.
+    pub fn subscribe(&self) -> UnboundedReceiver<()> {
+        let (sender, receiver) = unbounded();
+        self.clients.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Tells every connected page to reload, dropping pages that went away.
+    pub fn notify(&self) {
+        self.clients
+            .lock()
+            .unwrap()
+            .retain(|client| client.unbounded_send(()).is_ok());
+    }
+
+    /// Watches the files in `files_dir` that aren't ignored, plus `extra_files`, and
+    /// notifies pages when any of them is added, removed or modified. Watching stops
+    /// once `self` is dropped.
+    pub fn watch(
+        self: &Arc<Self>,
+        files_dir: PathBuf,
+        ignorer: Arc<Ignorer>,
+        extra_files: Vec<PathBuf>,
+    ) -> Result<(), notify_debouncer_mini::notify::Error> {
+        let live_reload = Arc::downgrade(self);
+        // Extra files may not exist yet, so their directories are watched instead.
+        let mut extra_dirs: Vec<PathBuf> = extra_files
+            .iter()
+            .filter_map(|file| file.parent())
+            .filter(|dir| !dir.starts_with(&files_dir))
+            .map(Path::to_path_buf)
+            .collect();
+        extra_dirs.sort();
+        extra_dirs.dedup();
+
+        let filter = WatchFilter {
+            files_dir: files_dir.clone(),
+            ignorer,
+            extra_files,
+        };
+        let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, move |result: DebounceEventResult| {
+            let events = match result {
+                Ok(events) => events,
+                Err(e) => {
+                    warn!("Live reload watcher error: {e}");
+                    return;
+                }
+            };
+            let Some(live_reload) = live_reload.upgrade() else {
+                return;
+            };
+            if let Some(event) = events.iter().find(|event| filter.matches(&event.path)) {
+                info!(
+                    "Change detected in {}, reloading the Edge App",
+                    event.path.display()
+                );
+                live_reload.notify();
+            }
+        })?;
+
+        debouncer
+            .watcher()
+            .watch(&files_dir, RecursiveMode::Recursive)?;
+        for dir in &extra_dirs {
+            debouncer
+                .watcher()
+                .watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        debug!("Watching {} for live reload", files_dir.display());
+        *self.watcher.lock().unwrap() = Some(debouncer);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::thread;
+
+    use futures::StreamExt;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn test_watch_filter_should_skip_ignored_files_and_include_extra_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".ignore"), "*.log\n").unwrap();
+        let filter = WatchFilter {
+            files_dir: dir.path().join("dist"),
+            ignorer: Arc::new(Ignorer::new(dir.path()).unwrap()),
+            extra_files: vec![
+                dir.path().join("screenly.yml"),
+                dir.path().join("mock-data.yml"),
+            ],
+        };
+
+        assert!(filter.matches(&dir.path().join("dist/index.html")));
+        assert!(filter.matches(&dir.path().join("dist/js/app.js")));
+        assert!(filter.matches(&dir.path().join("screenly.yml")));
+        assert!(filter.matches(&dir.path().join("mock-data.yml")));
+        assert!(!filter.matches(&dir.path().join("dist/debug.log")));
+        assert!(!filter.matches(&dir.path().join("dist/screenly.js")));
+        assert!(!filter.matches(&dir.path().join("dist/.screenly-cache/signatures.json")));
+        assert!(!filter.matches(&dir.path().join("index.ts")));
+    }
+
+    #[test]
+    fn test_watch_should_notify_clients_when_a_file_changes() {
+        let dir = tempdir().unwrap();
+        let dir_path = dir.path().canonicalize().unwrap();
+        fs::write(dir_path.join("index.html"), "index").unwrap();
+        let live_reload = Arc::new(LiveReload::default());
+        let mut client = live_reload.subscribe();
+        live_reload
+            .watch(
+                dir_path.clone(),
+                Arc::new(Ignorer::new(&dir_path).unwrap()),
+                Vec::new(),
+            )
+            .unwrap();
+
+        fs::write(dir_path.join("index.html"), "changed").unwrap();
+
+        let notified = (0..50).any(|_| {
+            thread::sleep(Duration::from_millis(100));
+            matches!(client.try_next(), Ok(Some(())))
+        });
+        assert!(notified);
+    }
+
+    #[tokio::test]
+    async fn test_notify_should_reach_connected_clients_only() {
+        let live_reload = LiveReload::default();
+        let mut first = live_reload.subscribe();
+        drop(live_reload.subscribe());
+
+        live_reload.notify();
+
+        assert_eq!(first.next().await, Some(()));
+        assert_eq!(live_reload.clients.lock().unwrap().len(), 1);
+    }
+}
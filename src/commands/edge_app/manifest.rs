@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::fs::File;
 use std::io::{ErrorKind, Write};
@@ -40,6 +40,23 @@ pub struct Entrypoint {
     pub uri: Option<String>,
 }
 
+/// Build step run before `deploy` and `run`. Files are then taken from `output_dir`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Build {
+    /// Shell command, e.g. `npm run build`.
+    pub command: String,
+    /// Directory to run the command in, relative to the manifest. Defaults to its directory.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub working_dir: Option<String>,
+    /// Directory with the built app, relative to the manifest. Defaults to its directory.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub output_dir: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub env: BTreeMap<String, String>,
+}
+
+/// The `screenly.yml` manifest of an Edge App.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct EdgeAppManifest {
@@ -115,6 +132,9 @@ pub struct EdgeAppManifest {
         default
     )]
     pub settings: Vec<Setting>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub build: Option<Build>,
 }
 
 fn deserialize_auth<'de, D>(deserializer: D) -> Result<Option<Auth>, D::Error>
@@ -351,6 +371,7 @@ mod tests {
                 uri: None,
             }),
             settings: vec![create_test_setting()],
+            build: None,
         }
     }
 
@@ -686,6 +707,7 @@ settings:
                 uri: None,
             }),
             settings: vec![create_test_setting()],
+            build: None,
         };
 
         let deserialized_manifest = serialize_deserialize_cycle(manifest.clone()).unwrap();
@@ -924,6 +946,7 @@ settings:
                 is_global: true,
                 help_text: "An example of a setting that is used in index.html".to_string(),
             }],
+            build: None,
         };
 
         EdgeAppManifest::save_to_file(&manifest, &file_path).unwrap();
@@ -982,6 +1005,7 @@ settings:
                 is_global: false,
                 help_text: "An example of a setting that is used in index.html".to_string(),
             }],
+            build: None,
         };
         let result = EdgeAppManifest::prepare_payload(&manifest);
         assert_eq!(result["app_id"], json!("test_app"));
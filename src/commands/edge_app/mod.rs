@@ -1,24 +1,94 @@
 pub mod app;
+pub mod build;
+pub mod delta;
+pub mod diff;
 pub mod instance;
 pub mod instance_manifest;
+pub mod live_reload;
 pub mod manifest;
 pub mod manifest_auth;
-pub(crate) mod server;
-pub(crate) mod setting;
+pub mod plan;
+pub mod pull;
+pub mod server;
+pub mod setting;
+pub mod signature_cache;
 pub mod test_utils;
 pub mod utils;
 
 use crate::api::Api;
 use crate::authentication::Authentication;
 
+/// Asks the user to confirm the deletion of a setting.
+///
+/// Receives the setting name and returns what the user typed: the name itself to
+/// delete the setting, or an empty string to keep it.
+pub type SettingDeletionPrompt = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Edge App workflows: creating, deploying and managing apps, settings and instances.
 pub struct EdgeAppCommand {
     api: Api,
+    setting_deletion_prompt: Option<SettingDeletionPrompt>,
+    signature_cache: bool,
+    delta_uploads: bool,
+    respect_gitignore: bool,
+    run_build: bool,
+    live_reload: bool,
 }
 
 impl EdgeAppCommand {
     pub fn new(authentication: Authentication) -> Self {
         Self {
-            api: Api { authentication },
+            api: Api::new(authentication),
+            setting_deletion_prompt: None,
+            signature_cache: false,
+            delta_uploads: true,
+            respect_gitignore: false,
+            run_build: true,
+            live_reload: true,
         }
     }
+
+    /// Sets the prompt used by [`EdgeAppCommand::prepare_deploy`] when settings were removed
+    /// from the manifest and `delete_missing_settings` is not given. Without a prompt,
+    /// such settings are kept.
+    pub fn with_setting_deletion_prompt(mut self, prompt: SettingDeletionPrompt) -> Self {
+        self.setting_deletion_prompt = Some(prompt);
+        self
+    }
+
+    /// Enables the local signature cache (`.screenly-cache/signatures.json` in the app
+    /// directory), so files unchanged since the last run aren't hashed again.
+    pub fn with_signature_cache(mut self, enabled: bool) -> Self {
+        self.signature_cache = enabled;
+        self
+    }
+
+    /// Controls whether [`EdgeAppCommand::prepare_deploy`] sends only the changed chunks of files
+    /// that existed in the previous revision, when the server supports it. Enabled by
+    /// default; files are uploaded whole otherwise.
+    pub fn with_delta_uploads(mut self, enabled: bool) -> Self {
+        self.delta_uploads = enabled;
+        self
+    }
+
+    /// Makes uploads and the emulator skip files matched by `.gitignore` files too, not
+    /// only by `.ignore` files.
+    pub fn with_gitignore(mut self, enabled: bool) -> Self {
+        self.respect_gitignore = enabled;
+        self
+    }
+
+    /// Controls whether `deploy` and `run` execute the `build` section of the manifest
+    /// first. Enabled by default.
+    pub fn with_build(mut self, enabled: bool) -> Self {
+        self.run_build = enabled;
+        self
+    }
+
+    /// Controls whether pages opened in the emulator reload when the app's files, its
+    /// manifest or its mock data change. Enabled by default.
+    pub fn with_live_reload(mut self, enabled: bool) -> Self {
+        self.live_reload = enabled;
+        self
+    }
 }
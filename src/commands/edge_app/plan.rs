@@ -0,0 +1,502 @@
+//! Read-only deploy planning: everything `deploy` would change, computed without writes.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::api::edge_app::setting::Setting;
+use crate::api::version::EdgeAppVersion;
+use crate::commands::edge_app::build::files_dir;
+use crate::commands::edge_app::instance_manifest::InstanceManifest;
+use crate::commands::edge_app::manifest::{EdgeAppManifest, EntrypointType};
+use crate::commands::edge_app::utils::{
+    collect_paths_for_upload, detect_changed_files, detect_changed_settings,
+    ensure_edge_app_has_all_necessary_files, generate_file_tree,
+    transform_edge_app_path_to_manifest, transform_instance_path_to_instance_manifest, EdgeAppFile,
+    FileChanges, SettingChanges,
+};
+use crate::commands::edge_app::EdgeAppCommand;
+use crate::commands::{CommandError, Formatter, OutputType};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAction {
+    /// The file is new or changed and will be uploaded.
+    Upload,
+    /// The file is unchanged and will be copied from the latest revision.
+    Copy,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub signature: String,
+    pub action: FileAction,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SettingsPlan {
+    pub create: Vec<String>,
+    pub update: Vec<String>,
+    /// Settings missing from the manifest. They are only deleted with
+    /// `delete_missing_settings` or after confirmation.
+    pub delete: Vec<String>,
+}
+
+/// A version metadata field whose manifest value differs from the latest revision.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MetadataChange {
+    pub field: String,
+    pub remote: serde_json::Value,
+    pub local: serde_json::Value,
+}
+
+/// What a deploy would do for an Edge App.
+#[derive(Debug, Serialize)]
+pub struct DeployPlan {
+    pub app_id: String,
+    /// Latest revision on the server, or 0 if the app has none.
+    pub base_revision: u32,
+    pub files: Vec<PlannedFile>,
+    /// Files of the latest revision that are no longer part of the app.
+    pub removed_files: Vec<String>,
+    pub file_tree_changed: bool,
+    pub settings: SettingsPlan,
+    /// Value the `screenly_entrypoint` setting will be set to, for remote entrypoints.
+    pub entrypoint: Option<String>,
+    pub metadata: Vec<MetadataChange>,
+    /// Whether a new revision will be created and published.
+    pub creates_version: bool,
+
+    #[serde(skip)]
+    pub(crate) manifest: EdgeAppManifest,
+    /// Directory the files are uploaded from, the build output directory if there is one.
+    #[serde(skip)]
+    pub(crate) files_dir: PathBuf,
+    #[serde(skip)]
+    pub(crate) local_files: Vec<EdgeAppFile>,
+    #[serde(skip)]
+    pub(crate) file_changes: FileChanges,
+    #[serde(skip)]
+    pub(crate) setting_changes: SettingChanges,
+    /// File tree of the base revision, used as the reference for delta uploads.
+    #[serde(skip)]
+    pub(crate) remote_file_tree: HashMap<String, String>,
+}
+
+impl DeployPlan {
+    pub fn file_tree(&self) -> HashMap<String, String> {
+        generate_file_tree(&self.local_files, &self.files_dir)
+    }
+}
+
+fn setting_names(settings: &[Setting]) -> Vec<String> {
+    settings
+        .iter()
+        .map(|setting| setting.name.clone())
+        .collect()
+}
+
+impl From<&SettingChanges> for SettingsPlan {
+    fn from(changes: &SettingChanges) -> Self {
+        Self {
+            create: setting_names(&changes.creates),
+            update: setting_names(&changes.updates),
+            delete: setting_names(&changes.deleted),
+        }
+    }
+}
+
+/// Lists the version metadata fields that differ between `version` and `manifest`.
+pub fn metadata_changes(
+    version: &EdgeAppVersion,
+    manifest: &EdgeAppManifest,
+) -> Vec<MetadataChange> {
+    let fields = [
+        (
+            "user_version",
+            json!(version.user_version),
+            json!(manifest.user_version),
+        ),
+        (
+            "description",
+            json!(version.description),
+            json!(manifest.description),
+        ),
+        ("icon", json!(version.icon), json!(manifest.icon)),
+        ("author", json!(version.author), json!(manifest.author)),
+        (
+            "homepage_url",
+            json!(version.homepage_url),
+            json!(manifest.homepage_url),
+        ),
+        (
+            "categories",
+            json!(version.categories),
+            json!(manifest.categories),
+        ),
+        (
+            "ready_signal",
+            json!(version.ready_signal),
+            json!(manifest.ready_signal.unwrap_or(false)),
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, remote, local)| remote != local)
+        .map(|(field, remote, local)| MetadataChange {
+            field: field.to_owned(),
+            remote,
+            local,
+        })
+        .collect()
+}
+
+impl EdgeAppCommand {
+    /// Computes what deploying the Edge App at `path` would change. The `build` section of
+    /// the manifest runs first, as it does for a deploy. Only read requests are sent.
+    pub fn plan(&self, path: Option<String>) -> Result<DeployPlan, CommandError> {
+        let manifest_path = transform_edge_app_path_to_manifest(&path)?;
+
+        EdgeAppManifest::ensure_manifest_is_valid(&manifest_path)?;
+        let manifest = EdgeAppManifest::new(&manifest_path)?;
+
+        let app_id = match self.get_app_id(path.clone()) {
+            Ok(id) => id,
+            Err(_) => return Err(CommandError::MissingAppId),
+        };
+
+        let edge_app_dir = manifest_path.parent().ok_or(CommandError::MissingField)?;
+        self.build(edge_app_dir, &manifest)?;
+        let files_dir = files_dir(edge_app_dir, &manifest)?;
+
+        let local_files = collect_paths_for_upload(
+            &files_dir,
+            &self.ignorer(edge_app_dir)?,
+            self.signature_cache,
+        )?;
+        ensure_edge_app_has_all_necessary_files(&local_files)?;
+
+        let latest_version = self.api.get_latest_revision(&app_id)?;
+        let metadata = match &latest_version {
+            Some(version) => metadata_changes(version, &manifest),
+            None => Vec::new(),
+        };
+        let base_revision = latest_version.map_or(0, |version| version.revision);
+
+        let remote_files = self
+            .api
+            .get_version_asset_signatures(&app_id, base_revision)?;
+        let file_changes = detect_changed_files(&local_files, &remote_files)?;
+        let remote_signatures: HashSet<&str> = remote_files
+            .iter()
+            .map(|file| file.signature.as_str())
+            .collect();
+        let mut files: Vec<PlannedFile> = local_files
+            .iter()
+            .map(|file| PlannedFile {
+                path: file.path.clone(),
+                signature: file.signature.clone(),
+                action: if remote_signatures.contains(file.signature.as_str()) {
+                    FileAction::Copy
+                } else {
+                    FileAction::Upload
+                },
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let remote_settings = self.api.get_settings(&app_id)?;
+        let setting_changes = detect_changed_settings(&manifest, &remote_settings)?;
+        let settings = SettingsPlan::from(&setting_changes);
+
+        let file_tree = generate_file_tree(&local_files, &files_dir);
+        let remote_file_tree = self.api.get_file_tree(&app_id, base_revision);
+        let (file_tree_changed, mut removed_files) = match &remote_file_tree {
+            Ok(old_tree) => (
+                file_tree != *old_tree,
+                old_tree
+                    .keys()
+                    .filter(|path| !file_tree.contains_key(*path))
+                    .cloned()
+                    .collect(),
+            ),
+            Err(_) => (true, Vec::new()),
+        };
+        let remote_file_tree = remote_file_tree.unwrap_or_default();
+        removed_files.sort();
+
+        let entrypoint = self.entrypoint_setting_value(&manifest, &path)?;
+        let creates_version =
+            file_changes.has_changes() || file_tree_changed || !metadata.is_empty();
+
+        Ok(DeployPlan {
+            app_id,
+            base_revision,
+            files,
+            removed_files,
+            file_tree_changed,
+            settings,
+            entrypoint,
+            metadata,
+            creates_version,
+            manifest,
+            files_dir,
+            local_files,
+            file_changes,
+            setting_changes,
+            remote_file_tree,
+        })
+    }
+
+    /// Returns the value of the `screenly_entrypoint` setting for remote entrypoints.
+    pub(crate) fn entrypoint_setting_value(
+        &self,
+        manifest: &EdgeAppManifest,
+        path: &Option<String>,
+    ) -> Result<Option<String>, CommandError> {
+        let Some(entrypoint) = &manifest.entrypoint else {
+            return Ok(None);
+        };
+        match entrypoint.entrypoint_type {
+            EntrypointType::RemoteGlobal => Ok(Some(entrypoint.uri.clone().unwrap_or_default())),
+            EntrypointType::RemoteLocal => {
+                let instance_manifest =
+                    InstanceManifest::new(&transform_instance_path_to_instance_manifest(path)?)?;
+                Ok(Some(instance_manifest.entrypoint_uri.unwrap_or_default()))
+            }
+            EntrypointType::File => Ok(None),
+        }
+    }
+}
+
+/// Appends a `Settings:` section listing the setting changes.
+pub(crate) fn format_settings_lines(settings: &SettingsPlan, lines: &mut Vec<String>) {
+    lines.push("Settings:".to_owned());
+    let changes = [
+        ("create", &settings.create),
+        ("update", &settings.update),
+        ("delete", &settings.delete),
+    ];
+    let mut has_changes = false;
+    for (action, names) in changes {
+        for name in names {
+            lines.push(format!("  {action:<7} {name}"));
+            has_changes = true;
+        }
+    }
+    if !has_changes {
+        lines.push("  no changes".to_owned());
+    }
+}
+
+/// Appends a `Metadata:` section if any metadata changed.
+pub(crate) fn format_metadata_lines(metadata: &[MetadataChange], lines: &mut Vec<String>) {
+    if metadata.is_empty() {
+        return;
+    }
+    lines.push("Metadata:".to_owned());
+    for change in metadata {
+        lines.push(format!(
+            "  {}: {} -> {}",
+            change.field, change.remote, change.local
+        ));
+    }
+}
+
+impl Formatter for DeployPlan {
+    fn format(&self, output_type: OutputType) -> String {
+        match output_type {
+            OutputType::HumanReadable => {
+                let mut lines = vec![format!(
+                    "Edge App {} (latest revision: {})",
+                    self.app_id, self.base_revision
+                )];
+
+                lines.push("Files:".to_owned());
+                for file in &self.files {
+                    let action = match file.action {
+                        FileAction::Upload => "upload",
+                        FileAction::Copy => "copy",
+                    };
+                    lines.push(format!("  {action:<7} {}", file.path));
+                }
+                for path in &self.removed_files {
+                    lines.push(format!("  {:<7} {path}", "remove"));
+                }
+
+                format_settings_lines(&self.settings, &mut lines);
+                if !self.settings.delete.is_empty() {
+                    lines.push("  (settings are only deleted with --delete-missing-settings or after confirmation)".to_owned());
+                }
+
+                if let Some(entrypoint) = &self.entrypoint {
+                    lines.push(format!("Entrypoint: {entrypoint}"));
+                }
+
+                format_metadata_lines(&self.metadata, &mut lines);
+
+                lines.push(if self.creates_version {
+                    "A new revision will be created and published.".to_owned()
+                } else {
+                    "No new revision will be created.".to_owned()
+                });
+                lines.join("\n")
+            }
+            OutputType::Json => serde_json::to_string_pretty(self).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use httpmock::Method::{DELETE, GET, PATCH, POST};
+
+    use super::*;
+    use crate::api::edge_app::setting::SettingType;
+    use crate::commands::edge_app::test_utils::tests::{
+        create_edge_app_manifest_for_test, prepare_edge_apps_test,
+    };
+
+    const INDEX_HTML_SIGNATURE: &str = "0a209f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08122086cebd0c365d241e32d5b0972c07aae3a8d6499c2a9471aa85943a35577200021a180a14a94a8fe5ccb19ba61c4c0873d391e987982fbbd31000";
+
+    #[test]
+    fn test_plan_should_report_changes_without_write_requests() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+
+        let manifest = create_edge_app_manifest_for_test(vec![Setting {
+            name: "asetting".to_string(),
+            type_: SettingType::String,
+            title: Some("atitle".to_string()),
+            optional: false,
+            default_value: Some("".to_string()),
+            is_global: false,
+            help_text: "help text".to_string(),
+        }]);
+        EdgeAppManifest::save_to_file(&manifest, temp_dir.path().join("screenly.yml").as_path())
+            .unwrap();
+        fs::write(temp_dir.path().join("index.html"), "test").unwrap();
+        fs::write(temp_dir.path().join("app.js"), "console.log(1);").unwrap();
+
+        let last_version_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4.1/edge-apps/versions")
+                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW");
+            then.status(200).json_body(json!([{
+                "user_version": "1",
+                "description": "old description",
+                "icon": "asdf",
+                "author": "asdf",
+                "homepage_url": "asdfasdf",
+                "categories": ["Utilities", "Dashboards"],
+                "ready_signal": false,
+                "revision": 7,
+            }]));
+        });
+        let signatures_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/assets")
+                .query_param("app_revision", "eq.7");
+            then.status(200)
+                .json_body(json!([{"signature": INDEX_HTML_SIGNATURE}]));
+        });
+        let settings_mock = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4.1/edge-apps/settings");
+            then.status(200).json_body(json!([{
+                "name": "isetting",
+                "type": "string",
+                "default_value": "5",
+                "title": null,
+                "optional": true,
+                "help_text": "Some text",
+                "is_global": false,
+            }]));
+        });
+        let file_tree_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/versions")
+                .query_param("select", "file_tree");
+            then.status(200).json_body(json!([{"file_tree": {
+                "index.html": INDEX_HTML_SIGNATURE,
+                "old.js": "sig",
+            }}]));
+        });
+        let write_mocks = [POST, PATCH, DELETE].map(|method| {
+            mock_server.mock(|when, then| {
+                when.method(method);
+                then.status(500);
+            })
+        });
+
+        let plan = command
+            .plan(Some(temp_dir.path().to_str().unwrap().to_string()))
+            .unwrap();
+
+        last_version_mock.assert();
+        signatures_mock.assert();
+        settings_mock.assert();
+        file_tree_mock.assert();
+        for mock in write_mocks {
+            mock.assert_calls(0);
+        }
+
+        assert_eq!(plan.base_revision, 7);
+        assert_eq!(
+            plan.files
+                .iter()
+                .map(|file| (file.path.as_str(), file.action))
+                .collect::<Vec<_>>(),
+            vec![
+                ("app.js", FileAction::Upload),
+                ("index.html", FileAction::Copy)
+            ]
+        );
+        assert_eq!(plan.removed_files, vec!["old.js".to_string()]);
+        assert!(plan.file_tree_changed);
+        assert_eq!(
+            plan.settings,
+            SettingsPlan {
+                create: vec!["asetting".to_string()],
+                update: vec![],
+                delete: vec!["isetting".to_string()],
+            }
+        );
+        assert_eq!(
+            plan.metadata,
+            vec![MetadataChange {
+                field: "description".to_string(),
+                remote: json!("old description"),
+                local: json!("asdf"),
+            }]
+        );
+        assert!(plan.creates_version);
+
+        let output = plan.format(OutputType::HumanReadable);
+        assert!(output.contains("upload  app.js"));
+        assert!(output.contains("remove  old.js"));
+        assert!(output.contains("description: \"old description\" -> \"asdf\""));
+    }
+
+    #[test]
+    fn test_metadata_changes_when_version_matches_manifest_should_be_empty() {
+        let manifest = create_edge_app_manifest_for_test(vec![]);
+        let version = EdgeAppVersion {
+            user_version: manifest.user_version.clone(),
+            description: manifest.description.clone(),
+            icon: manifest.icon.clone(),
+            author: manifest.author.clone(),
+            homepage_url: manifest.homepage_url.clone(),
+            categories: manifest.categories.clone(),
+            ready_signal: false,
+            revision: 3,
+        };
+
+        assert!(metadata_changes(&version, &manifest).is_empty());
+    }
+}
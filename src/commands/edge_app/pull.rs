@@ -0,0 +1,337 @@
+//! Recreating a local Edge App directory from a deployed revision.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use log::{debug, warn};
+
+use crate::commands::edge_app::manifest::{EdgeAppManifest, MANIFEST_VERSION};
+use crate::commands::edge_app::EdgeAppCommand;
+use crate::commands::CommandError;
+use crate::signature::{generate_signature, sig_to_hex};
+
+/// Settings with this prefix are generated from the manifest's `auth` and `entrypoint`
+/// sections, so they are not written back as regular settings.
+const GENERATED_SETTING_PREFIX: &str = "screenly_";
+
+/// Resolves a file tree path inside `dir`, rejecting absolute paths and `..`.
+fn target_path(dir: &Path, path: &str) -> Result<PathBuf, CommandError> {
+    let relative = Path::new(path);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(CommandError::FileSystemError(format!(
+            "Refusing to write outside of the target directory: {path}"
+        )));
+    }
+    Ok(dir.join(relative))
+}
+
+impl EdgeAppCommand {
+    /// Downloads `revision` (the latest one by default) of the app into `dir` and writes a
+    /// `screenly.yml` reconstructed from the version metadata and settings. Every file is
+    /// checked against the signature in the revision's file tree.
+    ///
+    /// A non-empty `dir` is refused unless `force` is set, in which case files of the
+    /// revision overwrite existing ones. Files are downloaded next to `dir` first and only
+    /// moved into it once all of them were verified, so a failed pull leaves `dir` untouched.
+    ///
+    /// Returns the pulled revision.
+    pub fn pull(
+        &self,
+        app_id: &str,
+        revision: Option<u32>,
+        dir: &Path,
+        force: bool,
+    ) -> Result<u32, CommandError> {
+        if !force && dir.exists() && fs::read_dir(dir)?.next().is_some() {
+            return Err(CommandError::FileSystemError(format!(
+                "{} is not empty. Use --force to overwrite its files",
+                dir.display()
+            )));
+        }
+
+        let version = match revision {
+            Some(revision) => self.api.get_revision(app_id, revision)?,
+            None => self.api.get_latest_revision(app_id)?,
+        }
+        .ok_or(CommandError::RevisionNotFound(
+            revision.map_or("latest".to_owned(), |revision| revision.to_string()),
+        ))?;
+
+        let file_tree = self.api.get_file_tree(app_id, version.revision)?;
+        let uris: HashMap<String, String> = self
+            .api
+            .get_version_asset_files(app_id, version.revision)?
+            .into_iter()
+            .filter_map(|asset| asset.uri.map(|uri| (asset.signature, uri)))
+            .collect();
+
+        // The staging directory is on the same file system as `dir`, so files can be
+        // renamed into place. It's removed when dropped, including on errors.
+        let parent = match dir.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        fs::create_dir_all(parent)?;
+        let staging = tempfile::Builder::new()
+            .prefix(".screenly-pull-")
+            .tempdir_in(parent)?;
+
+        for (path, signature) in &file_tree {
+            let target = target_path(staging.path(), path)?;
+            let uri = uris
+                .get(signature)
+                .ok_or(CommandError::MissingRequiredFile(path.clone()))?;
+
+            debug!("Downloading {path}");
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            self.api.download(uri, &target)?;
+
+            if &sig_to_hex(&generate_signature(&target)?) != signature {
+                return Err(CommandError::SignatureMismatch(path.clone()));
+            }
+        }
+
+        let (generated, settings): (Vec<_>, Vec<_>) = self
+            .api
+            .get_settings(app_id)?
+            .into_iter()
+            .partition(|setting| setting.name.starts_with(GENERATED_SETTING_PREFIX));
+        if !generated.is_empty() {
+            let names: Vec<_> = generated
+                .iter()
+                .map(|setting| setting.name.as_str())
+                .collect();
+            warn!(
+                "Settings {} come from the auth or entrypoint sections of the manifest. Restore those sections by hand.",
+                names.join(", ")
+            );
+        }
+
+        let manifest = EdgeAppManifest {
+            syntax: MANIFEST_VERSION.to_owned(),
+            id: Some(app_id.to_owned()),
+            user_version: version.user_version,
+            description: version.description,
+            icon: version.icon,
+            author: version.author,
+            homepage_url: version.homepage_url,
+            categories: version.categories,
+            ready_signal: version.ready_signal.then_some(true),
+            settings,
+            ..Default::default()
+        };
+        EdgeAppManifest::save_to_file(&manifest, &staging.path().join("screenly.yml"))?;
+
+        fs::create_dir_all(dir)?;
+        for path in file_tree.keys().map(String::as_str).chain(["screenly.yml"]) {
+            let target = target_path(dir, path)?;
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(staging.path().join(path), target)?;
+        }
+
+        Ok(version.revision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::Method::GET;
+    use serde_json::json;
+
+    use super::*;
+    use crate::commands::edge_app::test_utils::tests::prepare_edge_apps_test;
+
+    const INDEX_HTML_SIGNATURE: &str = "0a209f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08122086cebd0c365d241e32d5b0972c07aae3a8d6499c2a9471aa85943a35577200021a180a14a94a8fe5ccb19ba61c4c0873d391e987982fbbd31000";
+
+    fn mock_revision(mock_server: &httpmock::MockServer, file_tree: serde_json::Value) {
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4.1/edge-apps/versions")
+                .query_param("app_id", "eq.01H2QZ6Z8WXWNDC0KQ198XCZEW")
+                .query_param("revision", "eq.4");
+            then.status(200).json_body(json!([{
+                "user_version": "2",
+                "description": "Weather",
+                "icon": null,
+                "author": "Team",
+                "homepage_url": null,
+                "categories": ["Dashboards"],
+                "ready_signal": true,
+                "revision": 4,
+            }]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/edge-apps/versions")
+                .query_param("select", "file_tree")
+                .query_param("revision", "eq.4");
+            then.status(200)
+                .json_body(json!([{ "file_tree": file_tree }]));
+        });
+    }
+
+    #[test]
+    fn test_pull_should_download_files_and_write_manifest() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        mock_revision(
+            &mock_server,
+            json!({"static/index.html": INDEX_HTML_SIGNATURE}),
+        );
+        let assets_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/assets")
+                .query_param("select", "signature,uri")
+                .query_param("app_revision", "eq.4");
+            then.status(200).json_body(json!([{
+                "signature": INDEX_HTML_SIGNATURE,
+                "uri": "/files/index.html",
+            }]));
+        });
+        let download_mock = mock_server.mock(|when, then| {
+            when.method(GET).path("/files/index.html");
+            then.status(200).body("test");
+        });
+        let settings_mock = mock_server.mock(|when, then| {
+            when.method(GET).path("/v4.1/edge-apps/settings");
+            then.status(200).json_body(json!([{
+                "name": "greeting",
+                "type": "string",
+                "default_value": "Hello",
+                "title": "Greeting",
+                "optional": true,
+                "help_text": "Shown on screen",
+            }, {
+                "name": "screenly_entrypoint",
+                "type": "string",
+                "default_value": null,
+                "title": "Entrypoint",
+                "optional": false,
+                "help_text": "The entrypoint for the app.",
+            }]));
+        });
+
+        let dir = temp_dir.path().join("weather");
+        let revision = command
+            .pull("01H2QZ6Z8WXWNDC0KQ198XCZEW", Some(4), &dir, false)
+            .unwrap();
+
+        assets_mock.assert();
+        download_mock.assert();
+        settings_mock.assert();
+        assert_eq!(revision, 4);
+        assert_eq!(
+            fs::read_to_string(dir.join("static/index.html")).unwrap(),
+            "test"
+        );
+
+        let manifest = EdgeAppManifest::new(&dir.join("screenly.yml")).unwrap();
+        assert_eq!(manifest.id.as_deref(), Some("01H2QZ6Z8WXWNDC0KQ198XCZEW"));
+        assert_eq!(manifest.user_version.as_deref(), Some("2"));
+        assert_eq!(manifest.ready_signal, Some(true));
+        assert_eq!(
+            manifest
+                .settings
+                .iter()
+                .map(|setting| setting.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["greeting"]
+        );
+    }
+
+    #[test]
+    fn test_pull_when_content_does_not_match_signature_should_fail() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        mock_revision(&mock_server, json!({"index.html": INDEX_HTML_SIGNATURE}));
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/assets");
+            then.status(200).json_body(json!([{
+                "signature": INDEX_HTML_SIGNATURE,
+                "uri": "/files/index.html",
+            }]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/files/index.html");
+            then.status(200).body("tampered");
+        });
+
+        let dir = temp_dir.path().join("weather");
+        let result = command.pull("01H2QZ6Z8WXWNDC0KQ198XCZEW", Some(4), &dir, false);
+
+        assert!(
+            matches!(result, Err(CommandError::SignatureMismatch(path)) if path == "index.html")
+        );
+        assert!(!dir.exists());
+        let leftovers: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(".screenly-pull-")
+            })
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn test_pull_into_non_empty_directory_should_require_force() {
+        let (temp_dir, command, mock_server, _manifest, _instance_manifest) =
+            prepare_edge_apps_test(false, false);
+        mock_revision(&mock_server, json!({"index.html": INDEX_HTML_SIGNATURE}));
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/assets");
+            then.status(200).json_body(json!([{
+                "signature": INDEX_HTML_SIGNATURE,
+                "uri": "/files/index.html",
+            }]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/files/index.html");
+            then.status(200).body("test");
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4.1/edge-apps/settings");
+            then.status(200).json_body(json!([]));
+        });
+        let dir = temp_dir.path().join("weather");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("index.html"), "old").unwrap();
+        fs::write(dir.join("notes.txt"), "keep").unwrap();
+
+        let result = command.pull("01H2QZ6Z8WXWNDC0KQ198XCZEW", Some(4), &dir, false);
+
+        assert!(matches!(result, Err(CommandError::FileSystemError(_))));
+        assert_eq!(fs::read_to_string(dir.join("index.html")).unwrap(), "old");
+
+        command
+            .pull("01H2QZ6Z8WXWNDC0KQ198XCZEW", Some(4), &dir, true)
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join("index.html")).unwrap(), "test");
+        assert_eq!(fs::read_to_string(dir.join("notes.txt")).unwrap(), "keep");
+        assert!(dir.join("screenly.yml").exists());
+    }
+
+    #[test]
+    fn test_target_path_should_reject_paths_outside_of_directory() {
+        let dir = Path::new("/tmp/app");
+
+        assert_eq!(
+            target_path(dir, "js/main.js").unwrap(),
+            PathBuf::from("/tmp/app/js/main.js")
+        );
+        assert!(target_path(dir, "../escape.js").is_err());
+        assert!(target_path(dir, "/etc/passwd").is_err());
+    }
+}
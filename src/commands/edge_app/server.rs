@@ -1,17 +1,23 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::{fs, str};
 
 use anyhow::Result;
 use futures::future::{self, BoxFuture, FutureExt};
+use futures::StreamExt;
+use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map as JsonMap, Value as JsonValue};
 use warp::reject::Reject;
 use warp::{Filter, Rejection, Reply};
 
 use crate::api::edge_app::setting::SettingType;
+use crate::commands::edge_app::build::files_dir;
+use crate::commands::edge_app::live_reload::{self, LiveReload};
 use crate::commands::edge_app::manifest::EdgeAppManifest;
+use crate::commands::edge_app::utils::transform_edge_app_path_to_manifest;
 use crate::commands::edge_app::EdgeAppCommand;
 use crate::commands::ignorer::Ignorer;
 use crate::commands::CommandError;
@@ -24,9 +30,15 @@ pub enum Value {
     Array(Vec<String>),
 }
 
+/// Serves the files in `files_dir` along with a `screenly.js` built from the mock data in
+/// the Edge App directory `path`. With `live_reload`, open pages reload whenever a served
+/// file, the manifest or the mock data changes.
 pub async fn run_server(
     path: &Path,
+    files_dir: &Path,
     secrets: Vec<(String, String)>,
+    ignorer: Ignorer,
+    live_reload: bool,
 ) -> Result<String, anyhow::Error> {
     let secrets_val = secrets
         .iter()
@@ -35,11 +47,17 @@ pub async fn run_server(
 
     let dir_path = Arc::new(path.to_path_buf());
 
-    let ignorer = Arc::new(Ignorer::new(&*dir_path)?);
+    let ignorer = Arc::new(ignorer);
+    let reloader = Arc::new(LiveReload::default());
+    if live_reload {
+        let mut extra_files = vec![path.join(MOCK_DATA_FILENAME)];
+        extra_files.extend(manifest_path(path));
+        reloader.watch(files_dir.to_path_buf(), ignorer.clone(), extra_files)?;
+    }
 
     let directory = warp::path("edge")
         .and(warp::path("1"))
-        .and(warp::fs::dir(dir_path.as_path().to_owned()))
+        .and(warp::fs::dir(files_dir.to_path_buf()))
         .and_then(
             move |file: warp::filters::fs::File| -> BoxFuture<'static, Result<_, Rejection>> {
                 if ignorer.is_ignored(file.path()) {
@@ -64,7 +82,7 @@ pub async fn run_server(
                 async move {
                     if let Some(version) = params.get("version") {
                         if version == "1" {
-                            return generate_content(dir_path, &secrets_clone).await;
+                            return generate_content(dir_path, &secrets_clone, live_reload).await;
                         }
                     }
                     Err(warp::reject::not_found())
@@ -72,7 +90,33 @@ pub async fn run_server(
             }
         });
 
-    let routes = directory.or(virtual_file);
+    let live_reload_script = warp::path("edge")
+        .and(warp::path("1"))
+        .and(warp::path(live_reload::SCRIPT_FILENAME))
+        .and(warp::path::end())
+        .map(|| {
+            warp::reply::with_header(
+                live_reload::script(),
+                "content-type",
+                "application/javascript",
+            )
+        });
+
+    let live_reload_events = warp::path("edge")
+        .and(warp::path("1"))
+        .and(warp::path(live_reload::EVENTS_PATH))
+        .and(warp::path::end())
+        .map(move || {
+            let events = reloader.subscribe().map(|()| {
+                Ok::<_, Infallible>(warp::sse::Event::default().event("reload").data("reload"))
+            });
+            warp::sse::reply(warp::sse::keep_alive().stream(events))
+        });
+
+    let routes = directory
+        .or(virtual_file)
+        .or(live_reload_script)
+        .or(live_reload_events);
 
     let server = warp::serve(routes);
     let addr: std::net::SocketAddr = ([127, 0, 0, 1], 0).into();
@@ -84,6 +128,11 @@ pub async fn run_server(
     Ok(format!("http://{addr}/edge/1"))
 }
 
+/// Path of the manifest in the Edge App directory `path`, honouring `MANIFEST_FILE_NAME`.
+fn manifest_path(path: &Path) -> Option<PathBuf> {
+    transform_edge_app_path_to_manifest(&Some(path.to_string_lossy().into_owned())).ok()
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 struct WarpError(#[allow(dead_code)] anyhow::Error);
@@ -121,26 +170,44 @@ struct MockData {
 async fn generate_content(
     dir_path: Arc<PathBuf>,
     secrets: &[(String, Value)],
+    live_reload: bool,
 ) -> Result<impl Reply, Rejection> {
     let file_path = dir_path.join(MOCK_DATA_FILENAME);
 
     let content = if file_path.exists() {
         fs::read_to_string(&file_path).unwrap_or("".to_string())
     } else {
-        eprintln!(
+        warn!(
             "Mock data does not exist. Use \"screenly edge-app run --generate-mock-data\" to create mock data."
         );
         return Err(warp::reject::not_found());
     };
-    let data: MockData = match serde_yaml::from_str(&content) {
+    let mut data: MockData = match serde_yaml::from_str(&content) {
         Ok(data) => data,
         Err(e) => {
-            eprintln!("Failed to parse mock data: {e}");
+            warn!("Failed to parse mock data: {e}");
             return Err(warp::reject::not_found());
         }
     };
 
-    let js_output = format_js(data, secrets);
+    // Settings added to the manifest after the mock data was generated use their defaults.
+    if let Some(manifest) =
+        manifest_path(&dir_path).and_then(|path| EdgeAppManifest::new(&path).ok())
+    {
+        for setting in manifest.settings {
+            if setting.type_ == SettingType::Secret {
+                continue;
+            }
+            if let Some(default_value) = setting.default_value {
+                data.settings.entry(setting.name).or_insert(default_value);
+            }
+        }
+    }
+
+    let mut js_output = format_js(data, secrets);
+    if live_reload {
+        js_output.push_str(&live_reload::loader());
+    }
 
     Ok(warp::reply::with_header(
         js_output,
@@ -232,15 +299,31 @@ fn format_section(section_name: &str, items: &[(String, Value)]) -> String {
 }
 
 impl EdgeAppCommand {
+    /// Runs the Edge App emulator for the app at `path`. The `build` section of the
+    /// manifest runs first and its output directory is served.
     pub fn run(&self, path: &Path, secrets: Vec<(String, String)>) -> Result<(), anyhow::Error> {
         let address_shared = Arc::new(Mutex::new(None));
         let address_clone = address_shared.clone();
 
+        let manifest_path =
+            transform_edge_app_path_to_manifest(&Some(path.to_string_lossy().into_owned()))?;
+        let files_dir = match EdgeAppManifest::new(&manifest_path) {
+            Ok(manifest) => {
+                self.build(path, &manifest)?;
+                files_dir(path, &manifest)?
+            }
+            Err(e) if manifest_path.exists() => return Err(e.into()),
+            Err(_) => path.to_path_buf(),
+        };
+        let ignorer = self.ignorer(path)?;
+        let live_reload = self.live_reload;
         let runtime = tokio::runtime::Runtime::new().unwrap();
         let path = path.to_path_buf();
         runtime.block_on(async {
             tokio::spawn(async move {
-                let address = run_server(path.as_path(), secrets).await.unwrap();
+                let address = run_server(path.as_path(), &files_dir, secrets, ignorer, live_reload)
+                    .await
+                    .unwrap();
                 let mut locked_address = address_clone.lock().unwrap();
                 *locked_address = Some(address);
             })
@@ -251,7 +334,7 @@ impl EdgeAppCommand {
                 tokio::time::sleep(std::time::Duration::from_millis(100)).await;
             }
 
-            println!(
+            info!(
                 "Edge App emulator is running at {}/index.html",
                 address_shared.lock().unwrap().as_ref().unwrap()
             );
@@ -260,7 +343,7 @@ impl EdgeAppCommand {
                 "{}/index.html",
                 address_shared.lock().unwrap().as_ref().unwrap()
             )) {
-                eprintln!("{e}");
+                warn!("{e}");
             }
 
             loop {
@@ -304,7 +387,7 @@ impl EdgeAppCommand {
         let edge_app_dir = path.parent().ok_or(CommandError::MissingField)?;
 
         if edge_app_dir.join(MOCK_DATA_FILENAME).exists() {
-            println!("Mock data for Edge App emulator already exists.");
+            info!("Mock data for Edge App emulator already exists.");
             return Ok(());
         }
 
@@ -335,7 +418,7 @@ impl EdgeAppCommand {
 
         fs::write(edge_app_dir.join(MOCK_DATA_FILENAME), mock_data_yaml)?;
 
-        println!("Mock data for Edge App emulator was generated.");
+        info!("Mock data for Edge App emulator was generated.");
         Ok(())
     }
 }
@@ -392,9 +475,15 @@ settings:
         let dir = setup_temp_dir_with_mock_data();
         let dir_path = dir.path().to_path_buf();
 
-        let address = run_server(&dir_path, vec![("key".to_string(), "value".to_string())])
-            .await
-            .unwrap();
+        let address = run_server(
+            &dir_path,
+            &dir_path,
+            vec![("key".to_string(), "value".to_string())],
+            Ignorer::new(&dir_path).unwrap(),
+            false,
+        )
+        .await
+        .unwrap();
         let resp = reqwest::get(format!("{address}/screenly.js?version=1"))
             .await
             .unwrap();
@@ -431,9 +520,15 @@ settings: {
         let dir = tempdir().unwrap();
         let dir_path = dir.path().to_path_buf();
 
-        let address = run_server(&dir_path, vec![("key".to_string(), "value".to_string())])
-            .await
-            .unwrap();
+        let address = run_server(
+            &dir_path,
+            &dir_path,
+            vec![("key".to_string(), "value".to_string())],
+            Ignorer::new(&dir_path).unwrap(),
+            false,
+        )
+        .await
+        .unwrap();
         let resp = reqwest::get(format!("{address}/screenly.js?version=1"))
             .await
             .unwrap();
@@ -446,9 +541,15 @@ settings: {
         let dir = setup_temp_dir_with_mock_data();
         let dir_path = dir.path().to_path_buf();
 
-        let address = run_server(&dir_path, vec![("key".to_string(), "value".to_string())])
-            .await
-            .unwrap();
+        let address = run_server(
+            &dir_path,
+            &dir_path,
+            vec![("key".to_string(), "value".to_string())],
+            Ignorer::new(&dir_path).unwrap(),
+            false,
+        )
+        .await
+        .unwrap();
 
         let resp = reqwest::get(format!("{address}/screenly.js?version=2"))
             .await
@@ -462,9 +563,15 @@ settings: {
         let dir = setup_temp_dir_with_mock_data();
         let dir_path = dir.path().to_path_buf();
 
-        let address = run_server(&dir_path, vec![("key".to_string(), "value".to_string())])
-            .await
-            .unwrap();
+        let address = run_server(
+            &dir_path,
+            &dir_path,
+            vec![("key".to_string(), "value".to_string())],
+            Ignorer::new(&dir_path).unwrap(),
+            false,
+        )
+        .await
+        .unwrap();
         let resp = reqwest::get(format!("{address}/screenly.js?version=1"))
             .await
             .unwrap();
@@ -477,6 +584,86 @@ settings: {
         assert_eq!(content_type, "application/javascript");
     }
 
+    #[tokio::test]
+    async fn test_server_with_live_reload_should_load_script_and_use_manifest_defaults() {
+        let dir = setup_temp_dir_with_mock_data();
+        let dir_path = dir.path().to_path_buf();
+        let manifest = create_edge_app_manifest_for_test(vec![Setting {
+            name: "new_setting".to_string(),
+            type_: SettingType::String,
+            title: Some("new title".to_string()),
+            optional: true,
+            default_value: Some("from manifest".to_string()),
+            is_global: false,
+            help_text: "help text".to_string(),
+        }]);
+        EdgeAppManifest::save_to_file(&manifest, &dir_path.join("screenly.yml")).unwrap();
+
+        let address = run_server(
+            &dir_path,
+            &dir_path,
+            vec![],
+            Ignorer::new(&dir_path).unwrap(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        let content = reqwest::get(format!("{address}/screenly.js?version=1"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(content.ends_with(&live_reload::loader()));
+        assert!(content.contains(r#""new_setting": "from manifest""#));
+
+        let resp = reqwest::get(format!("{address}/{}", live_reload::SCRIPT_FILENAME))
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/javascript"
+        );
+        assert_eq!(resp.text().await.unwrap(), live_reload::script());
+    }
+
+    #[tokio::test]
+    async fn test_server_with_live_reload_should_send_reload_event_on_change() {
+        let dir = setup_temp_dir_with_mock_data();
+        let dir_path = dir.path().to_path_buf();
+
+        let address = run_server(
+            &dir_path,
+            &dir_path,
+            vec![],
+            Ignorer::new(&dir_path).unwrap(),
+            true,
+        )
+        .await
+        .unwrap();
+        let mut resp = reqwest::get(format!("{address}/{}", live_reload::EVENTS_PATH))
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "text/event-stream"
+        );
+
+        fs::write(dir_path.join("index.html"), "<html></html>").unwrap();
+
+        let received = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            while let Some(chunk) = resp.chunk().await.unwrap() {
+                if String::from_utf8_lossy(&chunk).contains("event:reload") {
+                    return true;
+                }
+            }
+            false
+        })
+        .await;
+        assert_eq!(received, Ok(true));
+    }
+
     #[test]
     fn test_generate_mock_data_creates_file_with_expected_content() {
         let (_dir, command, _mock_server, _manifest, _instance_manifest) =
@@ -566,4 +753,15 @@ settings: {
         assert!(!content.contains("excluded_setting"));
         assert!(content.contains("included_setting"));
     }
+
+    #[test]
+    fn test_run_with_invalid_manifest_should_fail() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("screenly.yml"), "syntax: [unclosed").unwrap();
+        let config = Config::new("".to_owned());
+        let authentication = Authentication::new_with_config(config, "token");
+        let command = EdgeAppCommand::new(authentication);
+
+        assert!(command.run(dir.path(), Vec::new()).is_err());
+    }
 }
@@ -1,6 +1,6 @@
 use std::str;
 
-use log::debug;
+use log::{debug, info};
 
 use crate::api::edge_app::setting::Setting;
 use crate::commands::edge_app::EdgeAppCommand;
@@ -88,7 +88,7 @@ impl EdgeAppCommand {
         if setting.edge_app_setting_values.len() == 1
             && setting.edge_app_setting_values[0].get("value").unwrap() == setting_value
         {
-            println!("Setting value is already set to {setting_value}");
+            info!("Setting value is already set to {setting_value}");
             return Ok(());
         }
 
@@ -0,0 +1,259 @@
+//! Local cache of file signatures, so unchanged files aren't hashed on every deploy.
+//!
+//! Entries are keyed by the relative path and checked against the file's size,
+//! modification time and inode. Files modified within [`RACY_WINDOW`] of hashing are
+//! not cached, as a later change within the same timestamp tick would go unnoticed.
+
+use std::collections::BTreeMap;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::{debug, warn};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::CommandError;
+use crate::signature::{generate_signature, sig_to_hex};
+
+/// Directory inside the Edge App that holds local CLI state. It is never uploaded.
+pub const CACHE_DIR: &str = ".screenly-cache";
+const CACHE_FILE: &str = "signatures.json";
+const CACHE_VERSION: u32 = 1;
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+    size: u64,
+    mtime_secs: u64,
+    mtime_nanos: u32,
+    inode: u64,
+}
+
+impl FileStamp {
+    fn new(metadata: &Metadata) -> Option<Self> {
+        let mtime = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        #[cfg(unix)]
+        let inode = std::os::unix::fs::MetadataExt::ino(metadata);
+        #[cfg(not(unix))]
+        let inode = 0;
+
+        Some(Self {
+            size: metadata.len(),
+            mtime_secs: mtime.as_secs(),
+            mtime_nanos: mtime.subsec_nanos(),
+            inode,
+        })
+    }
+
+    fn is_racy(&self, now: SystemTime) -> bool {
+        let mtime = UNIX_EPOCH + Duration::new(self.mtime_secs, self.mtime_nanos);
+        now.duration_since(mtime)
+            .map_or(true, |age| age < RACY_WINDOW)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheEntry {
+    #[serde(flatten)]
+    stamp: FileStamp,
+    signature: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SignatureCache {
+    version: u32,
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+impl SignatureCache {
+    fn path(root: &Path) -> PathBuf {
+        root.join(CACHE_DIR).join(CACHE_FILE)
+    }
+
+    /// Loads the cache of the Edge App at `root`. A missing, unreadable or outdated
+    /// cache is treated as empty.
+    pub fn load(root: &Path) -> Self {
+        let cache = fs::read(Self::path(root))
+            .ok()
+            .and_then(|data| serde_json::from_slice::<Self>(&data).ok());
+        match cache {
+            Some(cache) if cache.version == CACHE_VERSION => cache,
+            _ => Self {
+                version: CACHE_VERSION,
+                entries: BTreeMap::new(),
+            },
+        }
+    }
+
+    pub fn save(&self, root: &Path) -> Result<(), CommandError> {
+        let path = Self::path(root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+}
+
+/// Computes the hex signatures of `files` (paths relative to `root`), hashing in
+/// parallel. With a `cache`, unchanged files are looked up instead and the cache is
+/// updated to hold exactly the given files.
+pub fn signatures(
+    root: &Path,
+    files: &[String],
+    cache: Option<&mut SignatureCache>,
+) -> Result<Vec<String>, CommandError> {
+    let stamps: Vec<Option<FileStamp>> = files
+        .iter()
+        .map(|file| {
+            fs::metadata(root.join(file))
+                .ok()
+                .and_then(|m| FileStamp::new(&m))
+        })
+        .collect();
+
+    let mut signatures: Vec<Option<String>> = match &cache {
+        Some(cache) => files
+            .iter()
+            .zip(&stamps)
+            .map(|(file, stamp)| {
+                let entry = cache.entries.get(file)?;
+                (Some(&entry.stamp) == stamp.as_ref()).then(|| entry.signature.clone())
+            })
+            .collect(),
+        None => vec![None; files.len()],
+    };
+
+    let missing: Vec<usize> = (0..files.len())
+        .filter(|&index| signatures[index].is_none())
+        .collect();
+    debug!(
+        "Hashing {} of {} files, {} from cache",
+        missing.len(),
+        files.len(),
+        files.len() - missing.len()
+    );
+    let paths: Vec<PathBuf> = missing
+        .iter()
+        .map(|&index| root.join(&files[index]))
+        .collect();
+    for (index, signature) in missing.into_iter().zip(hash_files(&paths)?) {
+        signatures[index] = Some(signature);
+    }
+    let signatures: Vec<String> = signatures.into_iter().flatten().collect();
+
+    if let Some(cache) = cache {
+        let now = SystemTime::now();
+        cache.entries = files
+            .iter()
+            .zip(stamps)
+            .zip(&signatures)
+            .filter_map(|((file, stamp), signature)| {
+                let stamp = stamp.filter(|stamp| !stamp.is_racy(now))?;
+                Some((
+                    file.clone(),
+                    CacheEntry {
+                        stamp,
+                        signature: signature.clone(),
+                    },
+                ))
+            })
+            .collect();
+        if let Err(e) = cache.save(root) {
+            warn!("Failed to save the signature cache: {e}");
+        }
+    }
+
+    Ok(signatures)
+}
+
+/// Hashes files on all available cores, returning signatures in the order of `paths`.
+fn hash_files(paths: &[PathBuf]) -> Result<Vec<String>, CommandError> {
+    paths
+        .par_iter()
+        .map(|path| Ok(sig_to_hex(&generate_signature(path)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn age(path: &Path, seconds: u64) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(seconds))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_signatures_should_match_uncached_hashing() {
+        let dir = tempdir().unwrap();
+        let files: Vec<String> = (0..20).map(|i| format!("file{i}.txt")).collect();
+        for (i, file) in files.iter().enumerate() {
+            fs::write(dir.path().join(file), "x".repeat(i)).unwrap();
+        }
+
+        let expected: Vec<String> = files
+            .iter()
+            .map(|file| sig_to_hex(&generate_signature(&dir.path().join(file)).unwrap()))
+            .collect();
+
+        assert_eq!(signatures(dir.path(), &files, None).unwrap(), expected);
+        assert!(!SignatureCache::path(dir.path()).exists());
+    }
+
+    #[test]
+    fn test_cache_should_be_used_for_unchanged_files_and_invalidated_on_change() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("index.html");
+        fs::write(&path, "first").unwrap();
+        age(&path, 60);
+        let files = vec!["index.html".to_string()];
+
+        let mut cache = SignatureCache::load(dir.path());
+        let first = signatures(dir.path(), &files, Some(&mut cache)).unwrap();
+
+        // A cached entry is trusted as long as the stamp matches.
+        let mut cache = SignatureCache::load(dir.path());
+        cache.entries.get_mut("index.html").unwrap().signature = "cached".to_string();
+        cache.save(dir.path()).unwrap();
+        let mut cache = SignatureCache::load(dir.path());
+        assert_eq!(
+            signatures(dir.path(), &files, Some(&mut cache)).unwrap(),
+            vec!["cached".to_string()]
+        );
+
+        // Changing the size invalidates the entry.
+        fs::write(&path, "second version").unwrap();
+        age(&path, 60);
+        let mut cache = SignatureCache::load(dir.path());
+        let second = signatures(dir.path(), &files, Some(&mut cache)).unwrap();
+        assert_ne!(second, vec!["cached".to_string()]);
+        assert_ne!(second, first);
+        assert_eq!(
+            second,
+            vec![sig_to_hex(&generate_signature(&path).unwrap())]
+        );
+    }
+
+    #[test]
+    fn test_recently_modified_files_should_not_be_cached() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("fresh.js"), "fresh").unwrap();
+        fs::write(dir.path().join("old.js"), "old").unwrap();
+        age(&dir.path().join("old.js"), 60);
+        let files = vec!["fresh.js".to_string(), "old.js".to_string()];
+
+        let mut cache = SignatureCache::load(dir.path());
+        signatures(dir.path(), &files, Some(&mut cache)).unwrap();
+
+        let cache = SignatureCache::load(dir.path());
+        assert_eq!(
+            cache.entries.keys().collect::<Vec<_>>(),
+            vec![&"old.js".to_string()]
+        );
+    }
+}
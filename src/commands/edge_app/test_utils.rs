@@ -30,6 +30,7 @@ pub mod tests {
             }),
             settings,
             ready_signal: None,
+            build: None,
         }
     }
 
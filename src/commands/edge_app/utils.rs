@@ -2,16 +2,16 @@ use std::collections::{HashMap, HashSet};
 use std::env;
 use std::path::{Path, PathBuf};
 
+use ignore::DirEntry;
 use log::debug;
-use walkdir::{DirEntry, WalkDir};
 
 use crate::api::asset::AssetSignature;
 use crate::api::edge_app::setting::{Setting, SettingType};
 use crate::commands::edge_app::instance_manifest::InstanceManifest;
 use crate::commands::edge_app::manifest::EdgeAppManifest;
+use crate::commands::edge_app::signature_cache::{self, SignatureCache, CACHE_DIR};
 use crate::commands::ignorer::Ignorer;
 use crate::commands::CommandError;
-use crate::signature::{generate_signature, sig_to_hex};
 
 const INSTANCE_FILE_NAME_ENV: &str = "INSTANCE_FILE_NAME";
 const MANIFEST_FILE_NAME_ENV: &str = "MANIFEST_FILE_NAME";
@@ -29,6 +29,12 @@ pub struct SettingChanges {
     pub deleted: Vec<Setting>,
 }
 
+impl SettingChanges {
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty() && self.updates.is_empty() && self.deleted.is_empty()
+    }
+}
+
 #[derive(Debug)]
 pub struct FileChanges {
     pub local_files: Vec<EdgeAppFile>,
@@ -63,13 +69,22 @@ impl FileChanges {
     }
 }
 
-fn is_included(entry: &DirEntry, ignore: &Ignorer) -> bool {
-    let exclusion_list = ["screenly.js", "screenly.yml", ".ignore", "instance.yml"];
-    if exclusion_list.contains(&entry.file_name().to_str().unwrap_or_default()) {
-        return false;
-    }
+/// Files that are never uploaded, wherever they are in the app.
+const EXCLUDED_FILE_NAMES: [&str; 4] = ["screenly.js", "screenly.yml", ".ignore", "instance.yml"];
+
+fn is_included(entry: &DirEntry) -> bool {
+    let file_name = entry.file_name().to_str().unwrap_or_default();
+    let is_cache_dir = entry.depth() == 1 && file_name == CACHE_DIR;
+    !EXCLUDED_FILE_NAMES.contains(&file_name) && !is_cache_dir
+}
 
-    !ignore.is_ignored(entry.path())
+/// Whether `relative_path` (relative to the files directory) is left out of uploads
+/// regardless of the ignore files.
+pub(crate) fn is_excluded(relative_path: &Path) -> bool {
+    relative_path.starts_with(CACHE_DIR)
+        || relative_path.components().any(|component| {
+            EXCLUDED_FILE_NAMES.contains(&component.as_os_str().to_str().unwrap_or_default())
+        })
 }
 
 pub fn transform_edge_app_path_to_manifest(path: &Option<String>) -> Result<PathBuf, CommandError> {
@@ -132,29 +147,38 @@ pub fn transform_instance_path_to_instance_manifest(
     Ok(result)
 }
 
-pub fn collect_paths_for_upload(path: &Path) -> Result<Vec<EdgeAppFile>, CommandError> {
-    let mut files = Vec::new();
-
-    let ignore = Ignorer::new(path).map_err(|e| {
-        CommandError::IgnoreError(format!("Failed to initialize ignore module: {e}"))
-    })?;
-
-    for entry in WalkDir::new(path)
-        .into_iter()
-        .filter_entry(|e| is_included(e, &ignore))
-        .filter_map(|v| v.ok())
-    {
-        if entry.file_type().is_file() {
+/// Lists the files of the Edge App at `path` that would be uploaded, relative to `path`
+/// and sorted.
+pub fn list_upload_paths(path: &Path, ignore: &Ignorer) -> Result<Vec<String>, CommandError> {
+    let mut paths = Vec::new();
+    for entry in ignore.walk(path, is_included).filter_map(|v| v.ok()) {
+        if entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
             let relative_path = entry.path().strip_prefix(path)?;
-            let path = relative_path.to_str().unwrap_or_default();
-            let signature = generate_signature(entry.path())?;
-            files.push(EdgeAppFile {
-                path: path.to_owned(),
-                signature: sig_to_hex(&signature),
-            });
+            paths.push(relative_path.to_str().unwrap_or_default().to_owned());
         }
     }
-    Ok(files)
+    Ok(paths)
+}
+
+/// Collects the files to upload from the Edge App at `path` along with their signatures.
+/// With `use_cache`, signatures of unchanged files are read from the local
+/// [`SignatureCache`] instead of being computed again.
+pub fn collect_paths_for_upload(
+    path: &Path,
+    ignore: &Ignorer,
+    use_cache: bool,
+) -> Result<Vec<EdgeAppFile>, CommandError> {
+    let paths = list_upload_paths(path, ignore)?;
+    let mut cache = use_cache.then(|| SignatureCache::load(path));
+    let signatures = signature_cache::signatures(path, &paths, cache.as_mut())?;
+    Ok(paths
+        .into_iter()
+        .zip(signatures)
+        .map(|(path, signature)| EdgeAppFile { path, signature })
+        .collect())
 }
 
 pub fn ensure_edge_app_has_all_necessary_files(files: &[EdgeAppFile]) -> Result<(), CommandError> {
@@ -310,7 +334,7 @@ pub fn validate_manifests_dependacies(
 
 #[cfg(test)]
 mod tests {
-    use std::fs::File;
+    use std::fs::{self, File};
     use std::io::Write;
 
     use temp_env;
@@ -358,6 +382,7 @@ mod tests {
                     help_text: "Specify a commercial Google Maps API key. Required due to the app's map feature.".to_string(),
                 },
             ],
+            build: None,
         }
     }
 
@@ -586,6 +611,7 @@ mod tests {
                     help_text: "For how long to display the map overlay every time the rover has moved to a new position.".to_string(),
                 },
             ],
+            build: None,
         };
 
         let remote_settings = vec![
@@ -751,11 +777,37 @@ mod tests {
             .write_all(b"id: 01H2QZ6Z8WXWNDC0KQ198XCZEB\nname: test\n")
             .unwrap();
 
-        let result = collect_paths_for_upload(dir_path).unwrap();
+        let result =
+            collect_paths_for_upload(dir_path, &Ignorer::new(dir_path).unwrap(), false).unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].path, "file1.txt");
     }
 
+    #[test]
+    fn test_list_upload_paths_should_exclude_cache_dir_only_at_root() {
+        let dir = tempdir().unwrap();
+        let dir_path = dir.path();
+        fs::create_dir_all(dir_path.join(CACHE_DIR)).unwrap();
+        fs::create_dir_all(dir_path.join("assets").join(CACHE_DIR)).unwrap();
+        fs::write(dir_path.join(CACHE_DIR).join("signatures.json"), "{}").unwrap();
+        fs::write(
+            dir_path.join("assets").join(CACHE_DIR).join("data.json"),
+            "{}",
+        )
+        .unwrap();
+        fs::write(dir_path.join("index.html"), "test").unwrap();
+
+        let result = list_upload_paths(dir_path, &Ignorer::new(dir_path).unwrap()).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                format!("assets/{CACHE_DIR}/data.json"),
+                "index.html".to_owned()
+            ]
+        );
+    }
+
     #[test]
     fn test_detect_changed_settings_when_basic_auth_added_should_detect_changes() {
         // Arrange
@@ -1188,6 +1240,7 @@ mod tests {
                 uri: None,
             }),
             settings: vec![],
+            build: None,
         };
 
         let instance_manifest = InstanceManifest {
@@ -1230,6 +1283,7 @@ mod tests {
                 uri: None,
             }),
             settings: vec![],
+            build: None,
         };
 
         let instance_manifest = InstanceManifest {
@@ -1263,6 +1317,7 @@ mod tests {
                 uri: None,
             }),
             settings: vec![],
+            build: None,
         };
 
         let instance_manifest = InstanceManifest {
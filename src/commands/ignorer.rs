@@ -1,51 +1,166 @@
+//! Edge App ignore files with gitignore semantics, backed by the `ignore` crate.
+//!
+//! Patterns are read from `.ignore` files (and optionally `.gitignore` files) in the app
+//! directory and its subdirectories. Each file applies to paths below its own directory,
+//! deeper files take precedence, and within a file the last matching pattern wins.
+
+use std::cmp::Reverse;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use anyhow::Result;
-use regex::RegexSet;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{DirEntry, Match, Walk, WalkBuilder};
+
+pub const IGNORE_FILE: &str = ".ignore";
+pub const GITIGNORE_FILE: &str = ".gitignore";
+
+struct Matcher {
+    /// Directory of the ignore file.
+    dir: PathBuf,
+    gitignore: Gitignore,
+}
 
 pub struct Ignorer {
     base_path: PathBuf,
-    ignores: RegexSet,
+    respect_gitignore: bool,
+    /// Ignore files ordered from the deepest to the shallowest directory. Within a
+    /// directory `.ignore` comes before `.gitignore`.
+    matchers: Arc<Vec<Matcher>>,
 }
 
 impl Ignorer {
+    /// Reads the `.ignore` files of the app at `path`.
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let base_path = path.as_ref().to_path_buf();
-        let ignore_path = base_path.join(".ignore");
-        let mut patterns = Vec::new();
-
-        if ignore_path.exists() {
-            let content = std::fs::read_to_string(&ignore_path)?;
-
-            for line in content.lines() {
-                let pattern = line.trim();
-                if pattern.ends_with('/') {
-                    patterns.push(format!("^{}.*$", regex::escape(pattern)));
-                } else if pattern.contains('*') {
-                    // Convert wildcard '*' to regex '.*'
-                    let converted = pattern.replace('.', r"\.").replace('*', r".*");
-                    patterns.push(format!("^{converted}$"));
-                } else {
-                    patterns.push(format!("^{}$", regex::escape(pattern)));
-                }
+        Self::load(path.as_ref(), false)
+    }
+
+    /// Reads `.gitignore` files as well. Patterns in `.ignore` take precedence over
+    /// those in a `.gitignore` of the same directory.
+    pub fn with_gitignore<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::load(path.as_ref(), true)
+    }
+
+    fn load(base_path: &Path, respect_gitignore: bool) -> Result<Self> {
+        let mut found = Vec::new();
+        // Ignored directories are pruned, so ignore files inside them don't apply.
+        for entry in walk_builder(base_path, respect_gitignore)
+            .filter_entry(|entry| !is_git_dir(entry))
+            .build()
+            .filter_map(|entry| entry.ok())
+        {
+            let priority = if entry.file_name() == IGNORE_FILE {
+                0
+            } else if respect_gitignore && entry.file_name() == GITIGNORE_FILE {
+                1
+            } else {
+                continue;
+            };
+            if entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                found.push((Reverse(entry.depth()), priority, entry.into_path()));
             }
         }
-
-        let regex_set = RegexSet::new(&patterns)?;
+        found.sort();
+
+        let mut matchers = Vec::new();
+        for (_, _, path) in found {
+            let dir = path.parent().unwrap_or(base_path).to_path_buf();
+            let mut builder = GitignoreBuilder::new(&dir);
+            if let Some(error) = builder.add(&path) {
+                return Err(error.into());
+            }
+            matchers.push(Matcher {
+                dir,
+                gitignore: builder.build()?,
+            });
+        }
 
         Ok(Self {
-            base_path,
-            ignores: regex_set,
+            base_path: base_path.to_path_buf(),
+            respect_gitignore,
+            matchers: Arc::new(matchers),
         })
     }
 
+    /// Checks a path inside the app directory. Everything below an ignored directory is
+    /// ignored, even if a later pattern would re-include it.
     pub fn is_ignored(&self, path: &Path) -> bool {
         let relative_path = path.strip_prefix(&self.base_path).unwrap_or(path);
-        self.ignores
-            .is_match(relative_path.to_string_lossy().as_ref())
+        let mut current = self.base_path.clone();
+        let mut components = relative_path.components().peekable();
+        while let Some(component) = components.next() {
+            current.push(component);
+            let is_dir = components.peek().is_some() || current.is_dir();
+            if matches(&self.matchers, &current, is_dir) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Walks `root`, which may be the app directory or a directory inside it, skipping
+    /// ignored paths, `.git` directories and paths rejected by `include`. Ignored
+    /// directories aren't entered.
+    pub fn walk<F>(&self, root: &Path, include: F) -> Walk
+    where
+        F: Fn(&DirEntry) -> bool + Send + Sync + 'static,
+    {
+        let mut builder = walk_builder(root, self.respect_gitignore);
+        builder.sort_by_file_name(|a, b| a.cmp(b));
+        if root == self.base_path {
+            builder.filter_entry(move |entry| !is_git_dir(entry) && include(entry));
+        } else {
+            // The walker only reads ignore files at and below `root`, so those of the
+            // directories above it are applied here.
+            let matchers = self.matchers.clone();
+            builder.filter_entry(move |entry| {
+                let is_dir = entry
+                    .file_type()
+                    .is_some_and(|file_type| file_type.is_dir());
+                !is_git_dir(entry) && include(entry) && !matches(&matchers, entry.path(), is_dir)
+            });
+        }
+        builder.build()
     }
 }
 
+/// Git metadata is never part of an Edge App, whatever the ignore files say.
+fn is_git_dir(entry: &DirEntry) -> bool {
+    entry.file_name() == ".git"
+}
+
+/// Walker reading the ignore files at and below `root`. Every walk must also skip
+/// `.git` with [`is_git_dir`] in its entry filter, which the builder holds only one of.
+fn walk_builder(root: &Path, respect_gitignore: bool) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .standard_filters(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .git_ignore(respect_gitignore)
+        .require_git(false);
+    builder
+}
+
+/// Matches `path` against the ignore files of its parent directories, the deepest
+/// file with a matching pattern deciding. A path inside an ignored directory is ignored.
+fn matches(matchers: &[Matcher], path: &Path, is_dir: bool) -> bool {
+    for matcher in matchers {
+        // `matched_path_or_any_parents` requires a path below the matcher's root.
+        if path == matcher.dir || !path.starts_with(&matcher.dir) {
+            continue;
+        }
+        match matcher.gitignore.matched_path_or_any_parents(path, is_dir) {
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+            Match::None => {}
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs::File;
@@ -149,4 +264,164 @@ mod tests {
         // Check if files in other directory are not ignored
         assert!(!ignorer.is_ignored(&other_directory_path.join("some_file.txt")));
     }
+
+    fn ignorer_with(content: &str) -> (tempfile::TempDir, Ignorer) {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(".ignore"), content).unwrap();
+        let ignorer = Ignorer::new(dir.path()).unwrap();
+        (dir, ignorer)
+    }
+
+    #[test]
+    fn test_ignore_should_skip_comments_and_blank_lines() {
+        let (_dir, ignorer) = ignorer_with("# build output\n\n   \n\\#notes.txt\n");
+
+        assert!(!ignorer.is_ignored(Path::new("")));
+        assert!(!ignorer.is_ignored(Path::new("index.html")));
+        assert!(!ignorer.is_ignored(Path::new("# build output")));
+        assert!(ignorer.is_ignored(Path::new("#notes.txt")));
+    }
+
+    #[test]
+    fn test_ignore_when_pattern_negated_should_reinclude_matching_files() {
+        let (_dir, ignorer) = ignorer_with("*.js\n!keep.js\n");
+
+        assert!(ignorer.is_ignored(Path::new("app.js")));
+        assert!(ignorer.is_ignored(Path::new("lib/app.js")));
+        assert!(!ignorer.is_ignored(Path::new("keep.js")));
+        assert!(!ignorer.is_ignored(Path::new("lib/keep.js")));
+    }
+
+    #[test]
+    fn test_ignore_should_distinguish_anchored_and_unanchored_patterns() {
+        let (_dir, ignorer) = ignorer_with("/config.json\ndocs/draft.md\nnotes.txt\n");
+
+        assert!(ignorer.is_ignored(Path::new("config.json")));
+        assert!(!ignorer.is_ignored(Path::new("static/config.json")));
+        assert!(ignorer.is_ignored(Path::new("docs/draft.md")));
+        assert!(!ignorer.is_ignored(Path::new("static/docs/draft.md")));
+        assert!(ignorer.is_ignored(Path::new("notes.txt")));
+        assert!(ignorer.is_ignored(Path::new("static/notes.txt")));
+    }
+
+    #[test]
+    fn test_ignore_should_support_double_star_question_mark_and_classes() {
+        let (_dir, ignorer) =
+            ignorer_with("**/cache\nsrc/**/*.map\nlogs/**\nimg?.png\nfile[0-9].txt\nv[!a].css\n");
+
+        assert!(ignorer.is_ignored(Path::new("cache")));
+        assert!(ignorer.is_ignored(Path::new("a/b/cache")));
+        assert!(ignorer.is_ignored(Path::new("src/app.js.map")));
+        assert!(ignorer.is_ignored(Path::new("src/lib/deep/app.js.map")));
+        assert!(!ignorer.is_ignored(Path::new("app.js.map")));
+        assert!(ignorer.is_ignored(Path::new("logs/2024/today.log")));
+        assert!(ignorer.is_ignored(Path::new("img1.png")));
+        assert!(!ignorer.is_ignored(Path::new("img12.png")));
+        assert!(ignorer.is_ignored(Path::new("file7.txt")));
+        assert!(!ignorer.is_ignored(Path::new("filex.txt")));
+        assert!(ignorer.is_ignored(Path::new("vb.css")));
+        assert!(!ignorer.is_ignored(Path::new("va.css")));
+    }
+
+    #[test]
+    fn test_ignore_when_directory_ignored_should_not_reinclude_its_files() {
+        let (_dir, ignorer) = ignorer_with("build/\n!build/index.html\n");
+
+        assert!(ignorer.is_ignored(Path::new("build/index.html")));
+    }
+
+    #[test]
+    fn test_ignore_should_apply_nested_ignore_files_to_their_directory() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(".ignore"), "*.md\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("docs/api")).unwrap();
+        std::fs::write(dir.path().join("docs/.ignore"), "!README.md\n/api/\n").unwrap();
+
+        let ignorer = Ignorer::new(dir.path()).unwrap();
+
+        assert!(ignorer.is_ignored(Path::new("CHANGELOG.md")));
+        assert!(!ignorer.is_ignored(Path::new("docs/README.md")));
+        assert!(ignorer.is_ignored(&dir.path().join("docs/api/index.html")));
+        assert!(!ignorer.is_ignored(Path::new("api/index.html")));
+    }
+
+    #[test]
+    fn test_gitignore_should_only_be_read_when_requested() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "node_modules/\n*.js\n").unwrap();
+        std::fs::write(dir.path().join(".ignore"), "!main.js\n").unwrap();
+        std::fs::create_dir(dir.path().join("node_modules")).unwrap();
+
+        let ignorer = Ignorer::new(dir.path()).unwrap();
+        assert!(!ignorer.is_ignored(&dir.path().join("node_modules/lib.js")));
+
+        let ignorer = Ignorer::with_gitignore(dir.path()).unwrap();
+        assert!(ignorer.is_ignored(&dir.path().join("node_modules/lib.js")));
+        assert!(ignorer.is_ignored(Path::new("app.js")));
+        assert!(!ignorer.is_ignored(Path::new("main.js")));
+    }
+
+    #[test]
+    fn test_walk_below_base_path_should_apply_ignore_files_of_parent_directories() {
+        let dir = tempdir().unwrap();
+        let dist = dir.path().join("dist");
+        std::fs::create_dir_all(dist.join("maps")).unwrap();
+        std::fs::write(dir.path().join(".ignore"), "*.map\nmaps/\n").unwrap();
+        std::fs::write(dist.join(".ignore"), "!keep.map\n").unwrap();
+        for file in ["app.js", "app.js.map", "keep.map", "maps/lib.js"] {
+            std::fs::write(dist.join(file), "").unwrap();
+        }
+
+        let ignorer = Ignorer::new(dir.path()).unwrap();
+        let files: Vec<String> = ignorer
+            .walk(&dist, |entry| entry.file_name() != ".ignore")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+
+        assert_eq!(files, vec!["app.js", "keep.map"]);
+    }
+
+    #[test]
+    fn test_walk_inside_ignored_directory_should_skip_its_files() {
+        let dir = tempdir().unwrap();
+        let lib = dir.path().join("vendor").join("lib");
+        std::fs::create_dir_all(&lib).unwrap();
+        std::fs::write(dir.path().join(".ignore"), "vendor/\n").unwrap();
+        std::fs::write(lib.join("lib.js"), "").unwrap();
+
+        let ignorer = Ignorer::new(dir.path()).unwrap();
+        let files: Vec<_> = ignorer
+            .walk(&lib, |_| true)
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .collect();
+
+        assert!(files.is_empty());
+        assert!(ignorer.is_ignored(&lib.join("lib.js")));
+    }
+
+    #[test]
+    fn test_walk_should_always_skip_git_directory() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::create_dir_all(src.join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git").join("config"), "").unwrap();
+        std::fs::write(src.join(".git").join("HEAD"), "").unwrap();
+        std::fs::write(src.join("index.html"), "").unwrap();
+
+        let ignorer = Ignorer::new(dir.path()).unwrap();
+        for root in [dir.path(), src.as_path()] {
+            let files: Vec<String> = ignorer
+                .walk(root, |_| true)
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect();
+
+            assert_eq!(files, vec!["index.html"]);
+        }
+    }
 }
@@ -1,22 +1,30 @@
 use std::time::Duration;
 
 use log::debug;
-use prettytable::{cell, Cell, Row};
+use prettytable::{cell, row, Cell, Row};
 use reqwest::header::{HeaderMap, InvalidHeaderValue};
 use reqwest::StatusCode;
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::api::asset::Asset;
 use crate::api::edge_app::app::EdgeApps;
+use crate::api::edge_app::channel::Channel;
 use crate::api::edge_app::installation::EdgeAppInstances;
+use crate::api::edge_app::version::Version;
+use crate::api::label::{ScreenLabel, ScreenPlaylist};
+use crate::api::playlist::{Playlist, PlaylistItem};
+use crate::api::screen::Screen;
 use crate::{Authentication, AuthenticationError};
 
 pub mod asset;
+pub mod auth;
 pub mod edge_app;
 
 mod ignorer;
-pub(crate) mod playlist;
+pub mod playlist;
 pub mod screen;
+pub mod screen_monitor;
 pub(crate) mod serde_utils;
 
 pub enum OutputType {
@@ -70,7 +78,45 @@ where
     }
 }
 
+// Helper function to format typed items returned from the API.
+// Items are serialized as-is for JSON output, so fields unknown to the CLI are kept.
+fn format_items<T, F>(
+    output_type: OutputType,
+    column_names: Vec<&str>,
+    items: &[T],
+    row: F,
+) -> String
+where
+    T: Serialize,
+    F: Fn(&T) -> Vec<Cell>,
+{
+    match output_type {
+        OutputType::HumanReadable => {
+            let mut table = prettytable::Table::new();
+            table.add_row(Row::from(column_names));
+            for item in items {
+                table.add_row(Row::new(row(item)));
+            }
+            table.to_string()
+        }
+        OutputType::Json => serde_json::to_string_pretty(items).unwrap(),
+    }
+}
+
+fn format_boolean_field(value: Option<bool>) -> Cell {
+    if value.unwrap_or(false) {
+        cell!(c -> "✅")
+    } else {
+        cell!(c -> "❌")
+    }
+}
+
+fn format_optional_field(value: &Option<String>) -> Cell {
+    Cell::new(value.as_deref().unwrap_or("N/A"))
+}
+
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum CommandError {
     #[error("auth error")]
     Authentication(#[from] AuthenticationError),
@@ -130,29 +176,95 @@ pub enum CommandError {
     MissingInstallationId,
     #[error("App not found: {0}")]
     AppNotFound(String),
+    #[error("Option is only supported for web assets: {0}")]
+    WebAssetOnlyOption(String),
+    #[error("HTTP header is given more than once: {0}")]
+    DuplicateHeader(String),
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+    #[error("JavaScript syntax error in {0}: {1}")]
+    JsSyntaxError(String, String),
+    #[error("Screen not found: {0}")]
+    ScreenNotFound(String),
+    #[error("Command `{0}` failed: {1}")]
+    HookFailed(String, String),
+    #[error("Label not found: {0}")]
+    LabelNotFound(String),
+    #[error("Invalid name pattern: {0}")]
+    InvalidPattern(String),
+    #[error("More than one screen is named {0}")]
+    AmbiguousScreenName(String),
+    #[error("A screen name is required, so that the row is not registered again on re-runs")]
+    MissingScreenName,
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Signature of {0} doesn't match the revision's file tree")]
+    SignatureMismatch(String),
+}
+
+fn request_url(authentication: &Authentication, endpoint: &str) -> String {
+    format!("{}/{}", &authentication.config.url, endpoint)
+}
+
+fn representation_headers() -> Result<HeaderMap, CommandError> {
+    let mut headers = HeaderMap::new();
+    headers.insert("Prefer", "return=representation".parse()?);
+    Ok(headers)
+}
+
+fn parse_get_response(status: StatusCode, body: &str) -> Result<serde_json::Value, CommandError> {
+    if status != StatusCode::OK {
+        debug!("Response: {body:?}");
+        return Err(CommandError::WrongResponseStatus(status.as_u16()));
+    }
+    Ok(serde_json::from_str(body)?)
+}
+
+fn parse_post_response(status: StatusCode, body: &str) -> Result<serde_json::Value, CommandError> {
+    // Ok, No_Content are acceptable because some of our RPC code returns that.
+    if ![StatusCode::CREATED, StatusCode::OK, StatusCode::NO_CONTENT].contains(&status) {
+        debug!("Response: {body:?}");
+        return Err(CommandError::WrongResponseStatus(status.as_u16()));
+    }
+    if status == StatusCode::NO_CONTENT {
+        return Ok(serde_json::Value::Null);
+    }
+
+    Ok(serde_json::from_str(body)?)
+}
+
+fn parse_delete_response(status: StatusCode, body: &str) -> Result<(), CommandError> {
+    if ![StatusCode::OK, StatusCode::NO_CONTENT].contains(&status) {
+        debug!("Response: {body:?}");
+        return Err(CommandError::WrongResponseStatus(status.as_u16()));
+    }
+    Ok(())
+}
+
+fn parse_patch_response(status: StatusCode, body: &str) -> Result<serde_json::Value, CommandError> {
+    if status != StatusCode::OK {
+        debug!("Response: {body:?}");
+        return Err(CommandError::WrongResponseStatus(status.as_u16()));
+    }
+
+    match serde_json::from_str(body) {
+        Ok(v) => Ok(v),
+        Err(_) => Ok(serde_json::Value::Null),
+    }
 }
 
 pub fn get(
     authentication: &Authentication,
     endpoint: &str,
 ) -> Result<serde_json::Value, CommandError> {
-    let url = format!("{}/{}", &authentication.config.url, endpoint);
-    let mut headers = HeaderMap::new();
-    headers.insert("Prefer", "return=representation".parse()?);
-
     let response = authentication
-        .build_client()?
-        .get(url)
-        .headers(headers)
+        .blocking_client()?
+        .get(request_url(authentication, endpoint))
+        .headers(representation_headers()?)
         .send()?;
 
     let status = response.status();
-
-    if status != StatusCode::OK {
-        println!("Response: {:?}", &response.text());
-        return Err(CommandError::WrongResponseStatus(status.as_u16()));
-    }
-    Ok(serde_json::from_str(&response.text()?)?)
+    parse_get_response(status, &response.text()?)
 }
 
 pub fn post<T: Serialize + ?Sized>(
@@ -160,43 +272,26 @@ pub fn post<T: Serialize + ?Sized>(
     endpoint: &str,
     payload: &T,
 ) -> Result<serde_json::Value, CommandError> {
-    let url = format!("{}/{}", &authentication.config.url, endpoint);
-    let mut headers = HeaderMap::new();
-    headers.insert("Prefer", "return=representation".parse()?);
-
     let response = authentication
-        .build_client()?
-        .post(url)
-        .headers(headers)
+        .blocking_client()?
+        .post(request_url(authentication, endpoint))
+        .headers(representation_headers()?)
         .timeout(Duration::from_secs(60))
         .json(&payload)
         .send()?;
 
     let status = response.status();
-
-    // Ok, No_Content are acceptable because some of our RPC code returns that.
-    if ![StatusCode::CREATED, StatusCode::OK, StatusCode::NO_CONTENT].contains(&status) {
-        debug!("Response: {:?}", &response.text()?);
-        return Err(CommandError::WrongResponseStatus(status.as_u16()));
-    }
-    if status == StatusCode::NO_CONTENT {
-        return Ok(serde_json::Value::Null);
-    }
-
-    Ok(serde_json::from_str(&response.text()?)?)
+    parse_post_response(status, &response.text()?)
 }
 
 pub fn delete(authentication: &Authentication, endpoint: &str) -> anyhow::Result<(), CommandError> {
-    let url = format!("{}/{}", &authentication.config.url, endpoint);
-    let response = authentication.build_client()?.delete(url).send()?;
+    let response = authentication
+        .blocking_client()?
+        .delete(request_url(authentication, endpoint))
+        .send()?;
 
     let status = response.status();
-
-    if ![StatusCode::OK, StatusCode::NO_CONTENT].contains(&status) {
-        debug!("Response: {:?}", &response.text()?);
-        return Err(CommandError::WrongResponseStatus(status.as_u16()));
-    }
-    Ok(())
+    parse_delete_response(status, &response.text()?)
 }
 
 pub fn patch<T: Serialize + ?Sized>(
@@ -204,52 +299,83 @@ pub fn patch<T: Serialize + ?Sized>(
     endpoint: &str,
     payload: &T,
 ) -> anyhow::Result<serde_json::Value, CommandError> {
-    let url = format!("{}/{}", &authentication.config.url, endpoint);
-    let mut headers = HeaderMap::new();
-    headers.insert("Prefer", "return=representation".parse()?);
-
     let response = authentication
-        .build_client()?
-        .patch(url)
+        .blocking_client()?
+        .patch(request_url(authentication, endpoint))
         .json(&payload)
-        .headers(headers)
+        .headers(representation_headers()?)
         .send()?;
 
     let status = response.status();
-    if status != StatusCode::OK {
-        debug!("Response: {:?}", &response.text()?);
-        return Err(CommandError::WrongResponseStatus(status.as_u16()));
-    }
+    parse_patch_response(status, &response.text()?)
+}
 
-    if status == StatusCode::NO_CONTENT {
-        return Ok(serde_json::Value::Null);
-    }
+/// Async counterpart of [`get`], for use inside a tokio runtime.
+pub async fn get_async(
+    authentication: &Authentication,
+    endpoint: &str,
+) -> Result<serde_json::Value, CommandError> {
+    let response = authentication
+        .client()?
+        .get(request_url(authentication, endpoint))
+        .headers(representation_headers()?)
+        .send()
+        .await?;
 
-    match serde_json::from_str(&response.text()?) {
-        Ok(v) => Ok(v),
-        Err(_) => Ok(serde_json::Value::Null),
-    }
+    let status = response.status();
+    parse_get_response(status, &response.text().await?)
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
-pub struct PlaylistItem {
-    pub asset_id: String,
-    #[serde(deserialize_with = "deserialize_float_to_u32")]
-    pub duration: u32,
-    #[serde(skip_serializing, default = "default_pos_value")]
-    pub position: u64,
+/// Async counterpart of [`post`], for use inside a tokio runtime.
+pub async fn post_async<T: Serialize + ?Sized>(
+    authentication: &Authentication,
+    endpoint: &str,
+    payload: &T,
+) -> Result<serde_json::Value, CommandError> {
+    let response = authentication
+        .client()?
+        .post(request_url(authentication, endpoint))
+        .headers(representation_headers()?)
+        .timeout(Duration::from_secs(60))
+        .json(&payload)
+        .send()
+        .await?;
+
+    let status = response.status();
+    parse_post_response(status, &response.text().await?)
 }
 
-fn default_pos_value() -> u64 {
-    0
+/// Async counterpart of [`delete`], for use inside a tokio runtime.
+pub async fn delete_async(
+    authentication: &Authentication,
+    endpoint: &str,
+) -> Result<(), CommandError> {
+    let response = authentication
+        .client()?
+        .delete(request_url(authentication, endpoint))
+        .send()
+        .await?;
+
+    let status = response.status();
+    parse_delete_response(status, &response.text().await?)
 }
 
-fn deserialize_float_to_u32<'de, D>(deserializer: D) -> Result<u32, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let float_value: f64 = Deserialize::deserialize(deserializer)?;
-    Ok(float_value as u32)
+/// Async counterpart of [`patch`], for use inside a tokio runtime.
+pub async fn patch_async<T: Serialize + ?Sized>(
+    authentication: &Authentication,
+    endpoint: &str,
+    payload: &T,
+) -> Result<serde_json::Value, CommandError> {
+    let response = authentication
+        .client()?
+        .patch(request_url(authentication, endpoint))
+        .json(&payload)
+        .headers(representation_headers()?)
+        .send()
+        .await?;
+
+    let status = response.status();
+    parse_patch_response(status, &response.text().await?)
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
@@ -260,16 +386,12 @@ pub struct PlaylistFile {
 }
 
 impl PlaylistFile {
-    pub fn new(
-        predicate: String,
-        playlist_id: String,
-        items: serde_json::Value,
-    ) -> Result<Self, CommandError> {
-        Ok(Self {
+    pub fn new(predicate: String, playlist_id: String, items: Vec<PlaylistItem>) -> Self {
+        Self {
             predicate,
             playlist_id,
-            items: serde_json::from_value(items)?,
-        })
+            items,
+        }
     }
 }
 
@@ -381,62 +503,188 @@ impl Formatter for EdgeAppInstances {
 }
 
 #[derive(Debug)]
-pub struct Assets {
-    pub value: serde_json::Value,
+pub struct EdgeAppChannels {
+    pub channels: Vec<Channel>,
 }
 
-impl Assets {
-    pub fn new(value: serde_json::Value) -> Self {
-        Self { value }
+impl EdgeAppChannels {
+    pub fn new(channels: Vec<Channel>) -> Self {
+        Self { channels }
     }
 }
 
-impl FormatterValue for Assets {
-    fn value(&self) -> &serde_json::Value {
-        &self.value
+impl Formatter for EdgeAppChannels {
+    fn format(&self, output_type: OutputType) -> String {
+        format_items(
+            output_type,
+            vec!["Channel", "Revision"],
+            &self.channels,
+            |channel| {
+                vec![
+                    Cell::new(&channel.channel),
+                    Cell::new(
+                        &channel
+                            .app_revision
+                            .map_or("N/A".to_owned(), |revision| revision.to_string()),
+                    ),
+                ]
+            },
+        )
+    }
+}
+
+/// Paths of the files an Edge App deploy would upload.
+#[derive(Debug)]
+pub struct EdgeAppFileList {
+    pub paths: Vec<String>,
+}
+
+impl Formatter for EdgeAppFileList {
+    fn format(&self, output_type: OutputType) -> String {
+        match output_type {
+            OutputType::HumanReadable => self.paths.join("\n"),
+            OutputType::Json => serde_json::to_string_pretty(&self.paths).unwrap(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EdgeAppVersions {
+    pub versions: Vec<Version>,
+}
+
+impl EdgeAppVersions {
+    pub fn new(versions: Vec<Version>) -> Self {
+        Self { versions }
+    }
+}
+
+impl Formatter for EdgeAppVersions {
+    fn format(&self, output_type: OutputType) -> String {
+        format_items(
+            output_type,
+            vec!["Revision", "Version", "Published", "Channels", "Created"],
+            &self.versions,
+            |version| {
+                vec![
+                    Cell::new(&version.revision.to_string()),
+                    format_optional_field(&version.user_version),
+                    format_boolean_field(Some(version.published)),
+                    Cell::new(&version.channels.join(", ")),
+                    format_optional_field(&version.created_at),
+                ]
+            },
+        )
+    }
+}
+
+/// A single revision with its file tree.
+#[derive(Debug)]
+pub struct EdgeAppVersionDetails {
+    pub version: Version,
+}
+
+impl Formatter for EdgeAppVersionDetails {
+    fn format(&self, output_type: OutputType) -> String {
+        match output_type {
+            OutputType::HumanReadable => {
+                let version = &self.version;
+                let mut table = prettytable::Table::new();
+                table.add_row(Row::new(vec![
+                    cell!("Revision"),
+                    Cell::new(&version.revision.to_string()),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("Version"),
+                    format_optional_field(&version.user_version),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("Description"),
+                    format_optional_field(&version.description),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("Published"),
+                    format_boolean_field(Some(version.published)),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("Channels"),
+                    Cell::new(&version.channels.join(", ")),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("Created"),
+                    format_optional_field(&version.created_at),
+                ]));
+
+                let mut files = version.file_tree.iter().flatten().collect::<Vec<_>>();
+                files.sort();
+                let mut file_table = prettytable::Table::new();
+                file_table.add_row(row!["File", "Signature"]);
+                for (path, signature) in files {
+                    let short_signature = signature.get(..16).unwrap_or(signature);
+                    file_table.add_row(row![path, short_signature]);
+                }
+
+                format!("{table}\n{file_table}")
+            }
+            OutputType::Json => serde_json::to_string_pretty(&self.version).unwrap(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Assets {
+    pub assets: Vec<Asset>,
+}
+
+impl Assets {
+    pub fn new(assets: Vec<Asset>) -> Self {
+        Self { assets }
     }
 }
 
 impl Formatter for Assets {
     fn format(&self, output_type: OutputType) -> String {
-        format_value(
+        format_items(
             output_type,
             vec!["Id", "Title", "Type", "Status"],
-            vec!["id", "title", "type", "status"],
-            self,
-            None::<fn(&str, &serde_json::Value) -> Cell>,
+            &self.assets,
+            |asset| {
+                vec![
+                    Cell::new(&asset.id),
+                    format_optional_field(&asset.title),
+                    format_optional_field(&asset.asset_type),
+                    format_optional_field(&asset.status),
+                ]
+            },
         )
     }
 }
 
 #[derive(Debug)]
 pub struct Screens {
-    pub value: serde_json::Value,
+    pub screens: Vec<Screen>,
 }
 
 impl Screens {
-    pub fn new(value: serde_json::Value) -> Self {
-        Self { value }
+    pub fn new(screens: Vec<Screen>) -> Self {
+        Self { screens }
     }
-}
 
-impl FormatterValue for Screens {
-    fn value(&self) -> &serde_json::Value {
-        &self.value
+    pub fn ids(&self) -> Vec<String> {
+        self.screens
+            .iter()
+            .map(|screen| screen.id.clone())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.screens.is_empty()
     }
 }
 
 impl Formatter for Screens {
     fn format(&self, output_type: OutputType) -> String {
-        fn format_boolean_field(value: &serde_json::Value) -> Cell {
-            if value.as_bool().unwrap_or(false) {
-                cell!(c -> "✅")
-            } else {
-                cell!(c -> "❌")
-            }
-        }
-
-        format_value(
+        format_items(
             output_type,
             vec![
                 "Id",
@@ -448,112 +696,228 @@ impl Formatter for Screens {
                 "Last Ping",
                 "Uptime",
             ],
-            vec![
-                "id",
-                "name",
-                "is_enabled",
-                "priority",
-                "hardware_version",
-                "in_sync",
-                "last_ping",
-                "uptime",
-            ],
-            self,
-            Some(|field: &str, value: &serde_json::Value| {
-                if field.eq("is_enabled") || field.eq("priority") || field.eq("in_sync") {
-                    format_boolean_field(value)
-                } else if field.eq("uptime") {
-                    let uptime = if let Some(uptime) = value.as_u64() {
-                        indicatif::HumanDuration(Duration::new(uptime, 0)).to_string()
-                    } else {
-                        "N/A".to_owned()
-                    };
-                    Cell::new(&uptime).style_spec("r")
-                } else {
-                    Cell::new(value.as_str().unwrap_or("N/A"))
-                }
-            }),
+            &self.screens,
+            |screen| {
+                let uptime = match screen.uptime() {
+                    Some(uptime) => indicatif::HumanDuration(uptime).to_string(),
+                    None => "N/A".to_owned(),
+                };
+                vec![
+                    Cell::new(&screen.id),
+                    format_optional_field(&screen.name),
+                    format_boolean_field(screen.is_enabled),
+                    format_boolean_field(screen.priority),
+                    format_optional_field(&screen.hardware_version),
+                    format_boolean_field(screen.in_sync),
+                    format_optional_field(&screen.last_ping),
+                    Cell::new(&uptime).style_spec("r"),
+                ]
+            },
         )
     }
 }
 
+/// Per-row outcome of `screen add-batch`.
 #[derive(Debug)]
-pub struct Playlists {
+pub struct ScreenBatchResults {
     pub value: serde_json::Value,
 }
 
-impl Playlists {
+impl ScreenBatchResults {
     pub fn new(value: serde_json::Value) -> Self {
         Self { value }
     }
+
+    pub fn has_failures(&self) -> bool {
+        self.value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|row| row["status"] == "failed")
+    }
 }
 
-impl FormatterValue for Playlists {
+impl FormatterValue for ScreenBatchResults {
     fn value(&self) -> &serde_json::Value {
         &self.value
     }
 }
 
-impl Formatter for Playlists {
+impl Formatter for ScreenBatchResults {
     fn format(&self, output_type: OutputType) -> String {
-        fn format_boolean_field(value: &serde_json::Value) -> Cell {
-            if value.as_bool().unwrap_or(false) {
-                cell!(c -> "✅")
-            } else {
-                cell!(c -> "❌")
+        format_value(
+            output_type,
+            vec!["Line", "Pin", "Name", "Screen Id", "Status", "Details"],
+            vec!["line", "pin", "name", "screen_id", "status", "details"],
+            self,
+            Some(
+                |field_name: &str, field_value: &serde_json::Value| match field_name {
+                    "line" => Cell::new(&field_value.to_string()),
+                    "status" => {
+                        let status = field_value.as_str().unwrap_or("N/A");
+                        let style = if status == "failed" { "Fr" } else { "Fg" };
+                        Cell::new(status).style_spec(style)
+                    }
+                    _ => Cell::new(field_value.as_str().unwrap_or("")),
+                },
+            ),
+        )
+    }
+}
+
+/// Screens that haven't pinged for longer than this are considered stale.
+pub const STALE_SCREEN_THRESHOLD: Duration = Duration::from_secs(10 * 60);
+
+/// A single screen joined with its labels and the playlists targeting it.
+#[derive(Debug)]
+pub struct ScreenDetails {
+    pub screen: Screen,
+    pub labels: Vec<ScreenLabel>,
+    pub playlists: Vec<ScreenPlaylist>,
+}
+
+impl ScreenDetails {
+    pub fn new(screen: Screen, labels: Vec<ScreenLabel>, playlists: Vec<ScreenPlaylist>) -> Self {
+        Self {
+            screen,
+            labels,
+            playlists,
+        }
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.screen.is_stale(STALE_SCREEN_THRESHOLD)
+    }
+}
+
+impl Formatter for ScreenDetails {
+    fn format(&self, output_type: OutputType) -> String {
+        match output_type {
+            OutputType::HumanReadable => {
+                let screen = &self.screen;
+                let health_style = if self.is_stale() { "Fr" } else { "Fg" };
+
+                let last_ping = screen.last_ping.as_deref().unwrap_or("N/A");
+                let last_ping = match screen.last_ping_age() {
+                    Some(age) => format!("{last_ping} ({} ago)", indicatif::HumanDuration(age)),
+                    None => last_ping.to_owned(),
+                };
+                let uptime = screen
+                    .uptime()
+                    .map(|uptime| indicatif::HumanDuration(uptime).to_string())
+                    .unwrap_or("N/A".to_owned());
+                let labels = self
+                    .labels
+                    .iter()
+                    .map(|label| label.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let playlists = self
+                    .playlists
+                    .iter()
+                    .map(|playlist| playlist.title.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                let mut table = prettytable::Table::new();
+                table.add_row(Row::new(vec![cell!("Id"), Cell::new(&screen.id)]));
+                table.add_row(Row::new(vec![
+                    cell!("Name"),
+                    format_optional_field(&screen.name).style_spec(health_style),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("Enabled"),
+                    format_boolean_field(screen.is_enabled),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("In Sync"),
+                    format_boolean_field(screen.in_sync),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("Last Ping"),
+                    Cell::new(&last_ping).style_spec(health_style),
+                ]));
+                table.add_row(Row::new(vec![cell!("Uptime"), cell!(uptime)]));
+                table.add_row(Row::new(vec![
+                    cell!("Hardware Version"),
+                    format_optional_field(&screen.hardware_version),
+                ]));
+                table.add_row(Row::new(vec![
+                    cell!("Software Version"),
+                    format_optional_field(&screen.software_version),
+                ]));
+                table.add_row(Row::new(vec![cell!("Labels"), cell!(labels)]));
+                table.add_row(Row::new(vec![cell!("Playlists"), cell!(playlists)]));
+                table.to_string()
+            }
+            OutputType::Json => {
+                let mut screen = serde_json::to_value(&self.screen).unwrap();
+                if let Some(fields) = screen.as_object_mut() {
+                    fields.insert("labels".to_owned(), serde_json::json!(self.labels));
+                    fields.insert("playlists".to_owned(), serde_json::json!(self.playlists));
+                    fields.insert("is_stale".to_owned(), serde_json::json!(self.is_stale()));
+                }
+                serde_json::to_string_pretty(&screen).unwrap()
             }
         }
+    }
+}
 
-        format_value(
+#[derive(Debug)]
+pub struct Playlists {
+    pub playlists: Vec<Playlist>,
+}
+
+impl Playlists {
+    pub fn new(playlists: Vec<Playlist>) -> Self {
+        Self { playlists }
+    }
+}
+
+impl Formatter for Playlists {
+    fn format(&self, output_type: OutputType) -> String {
+        format_items(
             output_type,
             vec!["Id", "Title", "Enabled", "Priority"],
-            vec!["id", "title", "is_enabled", "priority"],
-            self,
-            Some(|field: &str, value: &serde_json::Value| {
-                if field.eq("is_enabled") || field.eq("priority") {
-                    format_boolean_field(value)
-                } else {
-                    Cell::new(value.as_str().unwrap_or("N/A"))
-                }
-            }),
+            &self.playlists,
+            |playlist| {
+                vec![
+                    Cell::new(&playlist.id),
+                    format_optional_field(&playlist.title),
+                    format_boolean_field(playlist.is_enabled),
+                    format_boolean_field(playlist.priority),
+                ]
+            },
         )
     }
 }
 
 #[derive(Debug)]
 pub struct PlaylistItems {
-    pub value: serde_json::Value,
+    pub items: Vec<PlaylistItem>,
 }
 
 impl PlaylistItems {
-    pub fn new(value: serde_json::Value) -> Self {
-        Self { value }
-    }
-}
-
-impl FormatterValue for PlaylistItems {
-    fn value(&self) -> &serde_json::Value {
-        &self.value
+    pub fn new(items: Vec<PlaylistItem>) -> Self {
+        Self { items }
     }
 }
 
 impl Formatter for PlaylistItems {
     fn format(&self, output_type: OutputType) -> String {
-        format_value(
+        format_items(
             output_type,
             vec!["Asset Id", "Duration"],
-            vec!["asset_id", "duration"],
-            self,
-            Some(|field: &str, value: &serde_json::Value| {
-                if field.eq("duration") {
-                    cell!(indicatif::HumanDuration(Duration::from_secs(
-                        value.as_f64().unwrap_or(0.0) as u64
+            &self.items,
+            |item| {
+                vec![
+                    Cell::new(&item.asset_id),
+                    cell!(indicatif::HumanDuration(Duration::from_secs_f64(
+                        item.duration.max(0.0)
                     ))
-                    .to_string())
-                } else {
-                    Cell::new(value.as_str().unwrap_or("N/A"))
-                }
-            }),
+                    .to_string()),
+                ]
+            },
         )
     }
 }
@@ -587,4 +951,23 @@ mod tests {
 "#
         );
     }
+
+    #[test]
+    fn test_playlists_json_output_should_preserve_unknown_fields() {
+        let response = serde_json::json!([{
+            "id": "playlist-1",
+            "title": "Welcome",
+            "predicate": "TRUE",
+            "is_enabled": true,
+            "priority": null,
+            "transitions": true,
+            "schedule": {"start": "09:00"}
+        }]);
+        let playlists = Playlists::new(serde_json::from_value(response.clone()).unwrap());
+
+        assert_eq!(playlists.playlists[0].title.as_deref(), Some("Welcome"));
+        let output: serde_json::Value =
+            serde_json::from_str(&playlists.format(OutputType::Json)).unwrap();
+        assert_eq!(output, response);
+    }
 }
@@ -1,84 +1,52 @@
 use serde_json::json;
 
+use crate::api::playlist::PlaylistItem;
+use crate::api::Api;
 use crate::authentication::Authentication;
-use crate::commands;
-use crate::commands::{CommandError, PlaylistFile, PlaylistItem, PlaylistItems, Playlists};
+use crate::commands::{CommandError, PlaylistFile, PlaylistItems, Playlists};
 
 const POSITION_MULTIPLIER: u64 = 100000;
 pub struct PlaylistCommand {
-    authentication: Authentication,
+    api: Api,
 }
 
 impl PlaylistCommand {
     pub fn new(authentication: Authentication) -> Self {
-        Self { authentication }
+        Self {
+            api: Api::new(authentication),
+        }
     }
 
     pub fn list(&self) -> Result<Playlists, CommandError> {
-        Ok(Playlists::new(commands::get(
-            &self.authentication,
-            "v4/playlists",
-        )?))
+        Ok(Playlists::new(self.api.list_playlists()?))
     }
 
     pub fn create(&self, title: &str, predicate: &str) -> Result<Playlists, CommandError> {
-        let response = commands::post(
-            &self.authentication,
-            "v4/playlists",
-            &json!({
-                "title": title,
-                "predicate": predicate,
-                "priority": false,
-                "is_enabled": true,
-                "transitions": true
-            }),
-        )?;
-        Ok(Playlists::new(response))
+        Ok(Playlists::new(self.api.create_playlist(title, predicate)?))
     }
 
-    fn get_playlist_field(&self, uuid: &str, field_name: &str) -> Result<String, CommandError> {
-        let playlists = Playlists::new(commands::get(
-            &self.authentication,
-            &format!("v4/playlists?id=eq.{uuid}&select=predicate"),
-        )?);
-        let mut field = String::new();
-        if let Some(pl) = playlists.value.as_array() {
-            field = pl[0]
-                .get(field_name)
-                .ok_or(CommandError::MissingField)?
-                .as_str()
-                .ok_or(CommandError::MissingField)?
-                .to_string();
-        }
-        Ok(field)
+    fn get_playlist_predicate(&self, uuid: &str) -> Result<String, CommandError> {
+        self.api
+            .get_playlist_predicate(uuid)?
+            .ok_or(CommandError::MissingField)
     }
     pub fn get_playlist_file(&self, uuid: &str) -> Result<PlaylistFile, CommandError> {
-        let predicate = self.get_playlist_field(uuid, "predicate")?;
-        let response = commands::get(
-            &self.authentication,
-            &format!("v4/playlist-items?select=asset_id,duration&playlist_id=eq.{uuid}&order=position.asc"),
-        )?;
+        let predicate = self.get_playlist_predicate(uuid)?;
+        let items = self.api.get_playlist_items(uuid)?;
 
-        PlaylistFile::new(predicate, uuid.to_string(), response)
+        Ok(PlaylistFile::new(predicate, uuid.to_string(), items))
     }
 
     pub fn update(&self, playlist: &PlaylistFile) -> Result<PlaylistItems, CommandError> {
-        let old_predicate = self.get_playlist_field(&playlist.playlist_id, "predicate")?;
+        let old_predicate = self.get_playlist_predicate(&playlist.playlist_id)?;
         if old_predicate != playlist.predicate {
-            commands::patch(
-                &self.authentication,
-                &format!("v4/playlists?id=eq.{id}", id = playlist.playlist_id),
+            self.api.update_playlist(
+                &playlist.playlist_id,
                 &json!({"predicate": playlist.predicate}),
             )?;
         }
 
-        commands::delete(
-            &self.authentication,
-            &format!(
-                "v4/playlist-items?playlist_id=eq.{id}",
-                id = playlist.playlist_id
-            ),
-        )?;
+        self.api.delete_playlist_items(&playlist.playlist_id)?;
 
         let mut new_items = Vec::<serde_json::Value>::new();
         let mut position = 0;
@@ -93,16 +61,13 @@ impl PlaylistCommand {
             new_items.push(v);
         }
 
-        Ok(PlaylistItems::new(commands::post(
-            &self.authentication,
-            "v4/playlist-items",
-            &json!(new_items),
-        )?))
+        Ok(PlaylistItems::new(
+            self.api.create_playlist_items(&json!(new_items))?,
+        ))
     }
 
     pub fn delete(&self, uuid: &str) -> Result<(), CommandError> {
-        commands::delete(&self.authentication, &format!("v4/playlists?id=eq.{uuid}"))?;
-        Ok(())
+        self.api.delete_playlist(uuid)
     }
 
     pub fn append_asset(
@@ -112,16 +77,11 @@ impl PlaylistCommand {
         duration: u32,
     ) -> Result<PlaylistItems, CommandError> {
         // selecting duration and playlist_id just so that we can convert it to PlaylistItem
-        let response = commands::get(
-            &self.authentication,
-            &format!("v4/playlist-items?select=position,asset_id,duration&playlist_id=eq.{playlist_uuid}&order=position.desc&limit=1"),
-        )?;
-
-        let playlist_items = serde_json::from_value::<Vec<PlaylistItem>>(response)?;
+        let playlist_items = self.api.get_last_playlist_item(playlist_uuid)?;
         let position = if playlist_items.is_empty() {
             POSITION_MULTIPLIER
         } else if playlist_items.len() == 1 {
-            playlist_items[0].position + POSITION_MULTIPLIER
+            playlist_items[0].position.unwrap_or(0) + POSITION_MULTIPLIER
         } else {
             return Err(CommandError::MissingField);
         };
@@ -133,11 +93,9 @@ impl PlaylistCommand {
             "position": position
         }]);
 
-        Ok(PlaylistItems::new(commands::post(
-            &self.authentication,
-            "v4/playlist-items",
-            &payload,
-        )?))
+        Ok(PlaylistItems::new(
+            self.api.create_playlist_items(&payload)?,
+        ))
     }
 
     pub fn prepend_asset(
@@ -151,14 +109,14 @@ impl PlaylistCommand {
             0,
             PlaylistItem {
                 asset_id: asset_uuid.to_string(),
-                duration,
-                position: 0,
+                duration: duration.into(),
+                ..Default::default()
             },
         );
         let mut position = 0;
         for playlist_item in &mut playlist_file.items {
             position += 1;
-            playlist_item.position = position * POSITION_MULTIPLIER;
+            playlist_item.position = Some(position * POSITION_MULTIPLIER);
         }
 
         self.update(&playlist_file)
@@ -314,13 +272,13 @@ mod tests {
         let items_request = json!([
           {
             "asset_id": "01AWJ47DP0000FXX7R00C5KX3F",
-            "duration": 33,
+            "duration": 33.0,
             "position": 100000,
             "playlist_id": "test-playlist-id",
           },
           {
             "asset_id": "01H2QDPVQ5JMKCBYJA78GGSEY4",
-            "duration": 10,
+            "duration": 10.0,
             "position": 200000,
             "playlist_id": "test-playlist-id",
           },
@@ -506,19 +464,19 @@ mod tests {
         let items_request = json!([
             {
             "asset_id": "test-asset-id",
-            "duration": 100,
+            "duration": 100.0,
             "position": 100000,
             "playlist_id": "test-playlist-id",
           },
           {
             "asset_id": "01AWJ47DP0000FXX7R00C5KX3F",
-            "duration": 33,
+            "duration": 33.0,
             "position": 200000,
             "playlist_id": "test-playlist-id",
           },
           {
             "asset_id": "01H2QDPVQ5JMKCBYJA78GGSEY4",
-            "duration": 10,
+            "duration": 10.0,
             "position": 300000,
             "playlist_id": "test-playlist-id",
           },
@@ -1,34 +1,169 @@
 use std::collections::HashMap;
+use std::path::Path;
 
-use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
+use crate::api::Api;
 use crate::authentication::Authentication;
-use crate::commands;
-use crate::commands::{CommandError, Screens};
+use crate::commands::{
+    CommandError, ScreenBatchResults, ScreenDetails, Screens, STALE_SCREEN_THRESHOLD,
+};
+
+/// A row of the CSV file accepted by `screen add-batch`.
+///
+/// Multiple labels are separated with `;`.
+#[derive(Debug, Deserialize)]
+struct BatchScreenRow {
+    pin: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    labels: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchRowStatus {
+    Added,
+    Exists,
+    Failed,
+}
+
+/// Outcome of a row of `screen add-batch`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BatchRowResult {
+    pub line: usize,
+    pub pin: Option<String>,
+    pub name: Option<String>,
+    pub screen_id: Option<String>,
+    pub status: BatchRowStatus,
+    pub details: String,
+}
+
+impl BatchRowResult {
+    fn failed(
+        line: usize,
+        pin: Option<String>,
+        name: Option<String>,
+        error: &dyn std::fmt::Display,
+    ) -> Self {
+        Self {
+            line,
+            pin,
+            name,
+            screen_id: None,
+            status: BatchRowStatus::Failed,
+            details: error.to_string(),
+        }
+    }
+}
+
+/// Criteria used to pick the screens a bulk command acts on.
+///
+/// All given criteria must match. Without explicit ids every screen of the team is considered.
+#[derive(Debug, Default, Clone)]
+pub struct ScreenSelector {
+    pub ids: Vec<String>,
+    pub label: Option<String>,
+    /// Glob pattern (e.g. `Lobby*`) matched against the screen name.
+    pub name_match: Option<String>,
+    pub all_stale: bool,
+}
 
 pub struct ScreenCommand {
-    authentication: Authentication,
+    api: Api,
 }
 
 impl ScreenCommand {
     pub fn new(authentication: Authentication) -> Self {
-        Self { authentication }
+        Self {
+            api: Api::new(authentication),
+        }
     }
 
     pub fn list(&self) -> anyhow::Result<Screens, CommandError> {
-        Ok(Screens::new(commands::get(
-            &self.authentication,
-            "v4/screens",
-        )?))
+        Ok(Screens::new(self.api.list_screens()?))
     }
 
     pub fn get(&self, id: &str) -> anyhow::Result<Screens, CommandError> {
-        let endpoint = format!("v4/screens?id=eq.{id}");
+        Ok(Screens::new(self.api.get_screen(id)?))
+    }
+
+    pub fn get_details(&self, id: &str) -> anyhow::Result<ScreenDetails, CommandError> {
+        let screen = self
+            .api
+            .get_screen(id)?
+            .into_iter()
+            .next()
+            .ok_or(CommandError::ScreenNotFound(id.to_string()))?;
 
-        Ok(Screens::new(commands::get(
-            &self.authentication,
-            &endpoint,
-        )?))
+        let label_ids = self.api.get_screen_label_ids(id)?;
+        Ok(ScreenDetails::new(
+            screen,
+            self.api.get_labels(&label_ids)?,
+            self.api.get_label_playlists(&label_ids)?,
+        ))
+    }
+
+    /// Returns the screens matching the selector.
+    pub fn select(&self, selector: &ScreenSelector) -> anyhow::Result<Screens, CommandError> {
+        let name_pattern = selector
+            .name_match
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
+            .map_err(|e| CommandError::InvalidPattern(e.to_string()))?;
+
+        let screens = if selector.ids.is_empty() {
+            self.api.list_screens()?
+        } else {
+            self.api.get_screens(&selector.ids)?
+        };
+
+        let labelled_ids = match &selector.label {
+            Some(label) => {
+                let label_ids = self.api.find_label_ids_by_name(label)?;
+                if label_ids.is_empty() {
+                    return Err(CommandError::LabelNotFound(label.to_owned()));
+                }
+                Some(self.api.get_labelled_screen_ids(&label_ids)?)
+            }
+            None => None,
+        };
+
+        let selected = screens
+            .into_iter()
+            .filter(|screen| {
+                let name = screen.name.as_deref().unwrap_or_default();
+                labelled_ids
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&screen.id))
+                    && name_pattern
+                        .as_ref()
+                        .is_none_or(|pattern| pattern.matches(name))
+                    && (!selector.all_stale || screen.is_stale(STALE_SCREEN_THRESHOLD))
+            })
+            .collect();
+
+        Ok(Screens::new(selected))
+    }
+
+    pub fn rename(&self, id: &str, name: &str) -> anyhow::Result<Screens, CommandError> {
+        Ok(Screens::new(
+            self.api.update_screen(id, &json!({ "name": name }))?,
+        ))
+    }
+
+    pub fn set_enabled(
+        &self,
+        ids: &[String],
+        enabled: bool,
+    ) -> anyhow::Result<Screens, CommandError> {
+        Ok(Screens::new(
+            self.api
+                .update_screens(ids, &json!({ "is_enabled": enabled }))?,
+        ))
     }
 
     pub fn add(
@@ -36,39 +171,120 @@ impl ScreenCommand {
         pin: &str,
         maybe_name: Option<String>,
     ) -> anyhow::Result<Screens, CommandError> {
-        let url = format!("{}/v3/screens/", &self.authentication.config.url);
-        let mut payload = HashMap::new();
-        payload.insert("pin".to_string(), pin.to_string());
-        if let Some(name) = maybe_name {
-            payload.insert("name".to_string(), name);
+        Ok(Screens::new(
+            self.api.register_screen(pin, maybe_name.as_deref())?,
+        ))
+    }
+
+    /// Registers every pin listed in a CSV file and attaches the given labels.
+    ///
+    /// Failures are recorded per row instead of aborting the batch. Rows whose name matches
+    /// an existing screen are not registered again, so the same file can be re-run safely.
+    /// Pins can't be looked up once used, so rows without a name are rejected.
+    pub fn add_batch(&self, path: &Path) -> anyhow::Result<ScreenBatchResults, CommandError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(path)?;
+        let mut label_ids: HashMap<String, String> = HashMap::new();
+        let mut results = Vec::new();
+
+        for (index, row) in reader.deserialize::<BatchScreenRow>().enumerate() {
+            // Line 1 is the header.
+            let line = index + 2;
+            results.push(match row {
+                Ok(row) => self.add_batch_row(line, &row, &mut label_ids),
+                Err(e) => BatchRowResult::failed(line, None, None, &e),
+            });
+        }
+
+        Ok(ScreenBatchResults::new(serde_json::to_value(results)?))
+    }
+
+    fn add_batch_row(
+        &self,
+        line: usize,
+        row: &BatchScreenRow,
+        label_ids: &mut HashMap<String, String>,
+    ) -> BatchRowResult {
+        let name = row.name.clone().filter(|name| !name.is_empty());
+        match self.register_batch_row(row, name.clone(), label_ids) {
+            Ok((screen_id, status, labels)) => BatchRowResult {
+                line,
+                pin: Some(row.pin.clone()),
+                name,
+                screen_id: Some(screen_id),
+                status,
+                details: if labels.is_empty() {
+                    String::new()
+                } else {
+                    format!("labels: {}", labels.join(", "))
+                },
+            },
+            Err(e) => BatchRowResult::failed(line, Some(row.pin.clone()), name, &e),
+        }
+    }
+
+    fn register_batch_row(
+        &self,
+        row: &BatchScreenRow,
+        name: Option<String>,
+        label_ids: &mut HashMap<String, String>,
+    ) -> anyhow::Result<(String, BatchRowStatus, Vec<String>), CommandError> {
+        let name = name.ok_or(CommandError::MissingScreenName)?;
+        let existing = self.api.find_screen_ids_by_name(&name)?;
+
+        let (screen_id, status) = match existing.as_slice() {
+            [] => {
+                let screens = self.add(&row.pin, Some(name))?;
+                let screen_id = screens.ids().pop().ok_or(CommandError::MissingField)?;
+                (screen_id, BatchRowStatus::Added)
+            }
+            [screen_id] => (screen_id.clone(), BatchRowStatus::Exists),
+            _ => return Err(CommandError::AmbiguousScreenName(name)),
+        };
+
+        let labels: Vec<String> = row
+            .labels
+            .as_deref()
+            .unwrap_or_default()
+            .split(';')
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(str::to_owned)
+            .collect();
+        if labels.is_empty() {
+            return Ok((screen_id, status, labels));
         }
-        let response = self
-            .authentication
-            .build_client()?
-            .post(url)
-            .json(&payload)
-            .send()?;
-        if response.status() != StatusCode::CREATED {
-            return Err(CommandError::WrongResponseStatus(
-                response.status().as_u16(),
-            ));
+
+        let attached = self.api.get_screen_label_ids(&screen_id)?;
+        for label in &labels {
+            let label_id = match label_ids.get(label) {
+                Some(label_id) => label_id.clone(),
+                None => {
+                    let label_id = match self.api.find_label_ids_by_name(label)?.first() {
+                        Some(label_id) => label_id.clone(),
+                        None => self.api.create_label(label)?,
+                    };
+                    label_ids.insert(label.clone(), label_id.clone());
+                    label_id
+                }
+            };
+            if !attached.contains(&label_id) {
+                self.api.attach_label(&label_id, &screen_id)?;
+            }
         }
 
-        // Our newer endpoints all return arrays so let's just convert the output from v3 to be the same
-        let mut array: Vec<serde_json::Value> = Vec::new();
-        array.insert(0, serde_json::from_str(&response.text()?)?);
-        Ok(Screens::new(serde_json::Value::Array(array)))
+        Ok((screen_id, status, labels))
     }
 
     pub fn delete(&self, id: &str) -> anyhow::Result<(), CommandError> {
-        let endpoint = format!("v3/screens/{id}/");
-        commands::delete(&self.authentication, &endpoint)
+        self.api.delete_screen(id)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use httpmock::Method::{DELETE, GET, POST};
+    use httpmock::Method::{DELETE, GET, PATCH, POST};
     use httpmock::MockServer;
     use serde_json::{json, Value};
     use tempfile::tempdir;
@@ -81,7 +297,7 @@ mod tests {
     fn test_list_screens_should_return_correct_screen_list() {
         let _tmp_dir = tempdir().unwrap();
 
-        let screens = serde_json::from_str::<Value>("[{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Renat's integrated wired NM\",\"is_enabled\":true,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":\"230728.38\",\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}]").unwrap();
+        let screens = serde_json::from_str::<Value>("[{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Renat's integrated wired NM\",\"is_enabled\":true,\"priority\":false,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":\"230728.38\",\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}]").unwrap();
 
         let mock_server = MockServer::start();
         mock_server.mock(|when, then| {
@@ -99,16 +315,16 @@ mod tests {
         let authentication = Authentication::new_with_config(config, "token");
         let screen_command = ScreenCommand::new(authentication);
         let v = screen_command.list().unwrap();
-        assert_eq!(v.value, screens);
+        assert_eq!(serde_json::to_value(&v.screens).unwrap(), screens);
     }
 
     #[test]
     fn test_add_screen_should_send_correct_request() {
-        let new_screen = serde_json::from_str::<Value>("{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Test\",\"is_enabled\":true,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":\"230728.38\",\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}").unwrap();
+        let new_screen = serde_json::from_str::<Value>("{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Test\",\"is_enabled\":true,\"priority\":false,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":\"230728.38\",\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}").unwrap();
         let mock_server = MockServer::start();
         let post_mock = mock_server.mock(|when, then| {
             when.method(POST)
-                .path("/v3/screens/")
+                .path("/v4/screens")
                 .header("Authorization", "Token token")
                 .header("content-type", "application/json")
                 .header(
@@ -116,7 +332,7 @@ mod tests {
                     format!("screenly-cli {}", env!("CARGO_PKG_VERSION")),
                 )
                 .json_body(json!({"pin": "test-pin", "name": "test"}));
-            then.status(201).json_body(new_screen.clone());
+            then.status(201).json_body(json!([new_screen.clone()]));
         });
 
         let config = Config::new(mock_server.base_url());
@@ -125,7 +341,10 @@ mod tests {
         let v = screen_command.add("test-pin", Some("test".to_string()));
         post_mock.assert();
         assert!(v.is_ok());
-        assert_eq!(v.unwrap().value.as_array().unwrap()[0], new_screen);
+        assert_eq!(
+            serde_json::to_value(&v.unwrap().screens[0]).unwrap(),
+            new_screen
+        );
     }
 
     #[test]
@@ -139,17 +358,414 @@ mod tests {
                 .header("Authorization", "Token token");
             then
                 .status(200)
-                .body(b"[{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Renat's integrated wired NM\",\"is_enabled\":true,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":\"230728.38\",\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}]");
+                .body(b"[{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Renat's integrated wired NM\",\"is_enabled\":true,\"priority\":false,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":\"230728.38\",\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}]");
         });
 
         let config = Config::new(mock_server.base_url());
         let authentication = Authentication::new_with_config(config, "token");
         let screen_command = ScreenCommand::new(authentication);
-        let expected = serde_json::from_str::<Value>("[{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Renat's integrated wired NM\",\"is_enabled\":true,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":\"230728.38\",\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}]").unwrap();
+        let expected = serde_json::from_str::<Value>("[{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Renat's integrated wired NM\",\"is_enabled\":true,\"priority\":false,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":\"230728.38\",\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}]").unwrap();
         let v = screen_command
             .get("017a5104-524b-33d8-8026-9087b59e7eb5")
             .unwrap();
-        assert_eq!(v.value, expected);
+        assert_eq!(serde_json::to_value(&v.screens).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_get_screen_details_should_join_labels_and_playlists() {
+        let screen = json!([{
+            "id": "017a5104-524b-33d8-8026-9087b59e7eb5",
+            "name": "Lobby",
+            "is_enabled": true,
+            "in_sync": true,
+            "last_ping": "2021-08-25T06:17:20.728+00:00",
+            "uptime": 230728,
+            "hardware_version": "Raspberry Pi 3B",
+            "software_version": "Screenly 2 Player"
+        }]);
+
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/screens")
+                .query_param("id", "eq.017a5104-524b-33d8-8026-9087b59e7eb5");
+            then.status(200).json_body(screen.clone());
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels/screens")
+                .query_param("screen_id", "eq.017a5104-524b-33d8-8026-9087b59e7eb5");
+            then.status(200)
+                .json_body(json!([{"label_id": "label-2"}, {"label_id": "label-1"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels")
+                .query_param("id", "in.(label-1,label-2)");
+            then.status(200).json_body(json!([
+                {"id": "label-1", "name": "Lobby screens"},
+                {"id": "label-2", "name": "All screens"}
+            ]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels/playlists")
+                .query_param("label_id", "in.(label-1,label-2)");
+            then.status(200)
+                .json_body(json!([{"playlist_id": "playlist-1"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/playlists")
+                .query_param("id", "in.(playlist-1)");
+            then.status(200)
+                .json_body(json!([{"id": "playlist-1", "title": "Welcome", "is_enabled": true}]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let details = screen_command
+            .get_details("017a5104-524b-33d8-8026-9087b59e7eb5")
+            .unwrap();
+
+        let output: Value = serde_json::from_str(&details.format(OutputType::Json)).unwrap();
+        assert_eq!(output["name"], "Lobby");
+        assert_eq!(output["labels"].as_array().unwrap().len(), 2);
+        assert_eq!(output["playlists"][0]["title"], "Welcome");
+        assert_eq!(output["is_stale"], true);
+
+        let human_output = details.format(OutputType::HumanReadable);
+        assert!(human_output.contains("Lobby screens, All screens"));
+        assert!(human_output.contains("Welcome"));
+        assert!(human_output.contains("Screenly 2 Player"));
+    }
+
+    #[test]
+    fn test_get_screen_details_when_screen_is_missing_should_return_error() {
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/screens");
+            then.status(200).json_body(json!([]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let result = screen_command.get_details("missing");
+        assert!(matches!(result, Err(CommandError::ScreenNotFound(_))));
+    }
+
+    #[test]
+    fn test_select_screens_should_apply_all_criteria() {
+        let fresh_ping = chrono::Utc::now().to_rfc3339();
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/screens");
+            then.status(200).json_body(json!([
+                {"id": "screen-1", "name": "Lobby 1", "last_ping": "2021-08-25T06:17:20+00:00"},
+                {"id": "screen-2", "name": "Lobby 2", "last_ping": fresh_ping},
+                {"id": "screen-3", "name": "Kitchen", "last_ping": "2021-08-25T06:17:20+00:00"},
+                {"id": "screen-4", "name": "Lobby 3", "last_ping": "2021-08-25T06:17:20+00:00"}
+            ]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels")
+                .query_param("name", "eq.Store 42");
+            then.status(200).json_body(json!([{"id": "label-1"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels/screens")
+                .query_param("label_id", "in.(label-1)");
+            then.status(200).json_body(json!([
+                {"screen_id": "screen-1"},
+                {"screen_id": "screen-2"},
+                {"screen_id": "screen-3"}
+            ]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let screens = screen_command
+            .select(&ScreenSelector {
+                label: Some("Store 42".to_owned()),
+                name_match: Some("Lobby*".to_owned()),
+                all_stale: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(screens.ids(), vec!["screen-1"]);
+    }
+
+    #[test]
+    fn test_select_screens_with_unknown_label_should_return_error() {
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/screens");
+            then.status(200).json_body(json!([]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/labels");
+            then.status(200).json_body(json!([]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let result = screen_command.select(&ScreenSelector {
+            label: Some("missing".to_owned()),
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(CommandError::LabelNotFound(_))));
+    }
+
+    #[test]
+    fn test_set_enabled_should_patch_all_screens() {
+        let mock_server = MockServer::start();
+        let patch_mock = mock_server.mock(|when, then| {
+            when.method(PATCH)
+                .path("/v4/screens")
+                .query_param("id", "in.(screen-1,screen-2)")
+                .json_body(json!({"is_enabled": false}));
+            then.status(200).json_body(json!([
+                {"id": "screen-1", "is_enabled": false},
+                {"id": "screen-2", "is_enabled": false}
+            ]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let screens = screen_command
+            .set_enabled(&["screen-1".to_owned(), "screen-2".to_owned()], false)
+            .unwrap();
+
+        patch_mock.assert();
+        assert_eq!(screens.ids().len(), 2);
+    }
+
+    #[test]
+    fn test_rename_screen_should_send_correct_request() {
+        let mock_server = MockServer::start();
+        let patch_mock = mock_server.mock(|when, then| {
+            when.method(PATCH)
+                .path("/v4/screens")
+                .query_param("id", "eq.screen-1")
+                .json_body(json!({"name": "Front desk"}));
+            then.status(200)
+                .json_body(json!([{"id": "screen-1", "name": "Front desk"}]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let screens = screen_command.rename("screen-1", "Front desk").unwrap();
+
+        patch_mock.assert();
+        assert_eq!(screens.screens[0].name.as_deref(), Some("Front desk"));
+    }
+
+    #[test]
+    fn test_add_batch_should_register_pins_attach_labels_and_continue_on_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pins.csv");
+        std::fs::write(
+            &path,
+            "pin,name,labels\n\
+             111,Lobby,Store 42;Lobby\n\
+             222,Kitchen,\n\
+             333,Broken,\n",
+        )
+        .unwrap();
+
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/screens")
+                .query_param("name", "eq.Lobby");
+            then.status(200).json_body(json!([]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/screens")
+                .query_param("name", "eq.Kitchen");
+            then.status(200).json_body(json!([{"id": "screen-2"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/screens")
+                .query_param("name", "eq.Broken");
+            then.status(200).json_body(json!([]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/v4/screens")
+                .json_body(json!({"pin": "111", "name": "Lobby"}));
+            then.status(201)
+                .json_body(json!([{"id": "screen-1", "name": "Lobby"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/v4/screens")
+                .json_body(json!({"pin": "333", "name": "Broken"}));
+            then.status(400);
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels/screens")
+                .query_param("screen_id", "eq.screen-1");
+            then.status(200).json_body(json!([{"label_id": "label-1"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels")
+                .query_param("name", "eq.Store 42");
+            then.status(200).json_body(json!([{"id": "label-1"}]));
+        });
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/labels")
+                .query_param("name", "eq.Lobby");
+            then.status(200).json_body(json!([]));
+        });
+        let create_label_mock = mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/v4/labels")
+                .json_body(json!({"name": "Lobby"}));
+            then.status(201).json_body(json!([{"id": "label-2"}]));
+        });
+        let attach_label_mock = mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/v4/labels/screens")
+                .json_body(json!({"label_id": "label-2", "screen_id": "screen-1"}));
+            then.status(201).json_body(json!([]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let results = screen_command.add_batch(&path).unwrap();
+
+        create_label_mock.assert();
+        attach_label_mock.assert();
+        assert!(results.has_failures());
+        let rows = results.value.as_array().unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0]["status"], "added");
+        assert_eq!(rows[0]["screen_id"], "screen-1");
+        assert_eq!(rows[0]["details"], "labels: Store 42, Lobby");
+        assert_eq!(rows[1]["status"], "exists");
+        assert_eq!(rows[1]["screen_id"], "screen-2");
+        assert_eq!(rows[2]["status"], "failed");
+        assert_eq!(rows[2]["line"], 4);
+    }
+
+    #[test]
+    fn test_add_batch_when_re_run_should_not_register_named_screens_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pins.csv");
+        std::fs::write(&path, "pin,name\n111,Lobby\n").unwrap();
+
+        let mock_server = MockServer::start();
+        let mut find_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/screens")
+                .query_param("name", "eq.Lobby");
+            then.status(200).json_body(json!([]));
+        });
+        let register_mock = mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/v4/screens")
+                .json_body(json!({"pin": "111", "name": "Lobby"}));
+            then.status(201)
+                .json_body(json!([{"id": "screen-1", "name": "Lobby"}]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let first = screen_command.add_batch(&path).unwrap();
+
+        // The screen registered by the first run is now found by its name.
+        find_mock.delete();
+        mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/screens")
+                .query_param("name", "eq.Lobby");
+            then.status(200).json_body(json!([{"id": "screen-1"}]));
+        });
+        let second = screen_command.add_batch(&path).unwrap();
+
+        register_mock.assert_calls(1);
+        assert_eq!(first.value[0]["status"], "added");
+        assert_eq!(second.value[0]["status"], "exists");
+        assert_eq!(second.value[0]["screen_id"], "screen-1");
+    }
+
+    #[test]
+    fn test_add_batch_when_re_run_should_reject_rows_without_name_every_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pins.csv");
+        std::fs::write(&path, "pin,name\n111,\n").unwrap();
+
+        let mock_server = MockServer::start();
+        let register_mock = mock_server.mock(|when, then| {
+            when.method(POST).path("/v4/screens");
+            then.status(201).json_body(json!([{"id": "screen-1"}]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        for _ in 0..2 {
+            let results = screen_command.add_batch(&path).unwrap();
+            assert!(results.has_failures());
+            assert_eq!(results.value[0]["status"], "failed");
+            assert_eq!(
+                results.value[0]["details"],
+                CommandError::MissingScreenName.to_string()
+            );
+        }
+
+        register_mock.assert_calls(0);
+    }
+
+    #[test]
+    fn test_add_batch_should_match_names_with_reserved_characters_literally() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pins.csv");
+        std::fs::write(&path, "pin,name\n444,Lobby & Hall #2\n").unwrap();
+
+        let mock_server = MockServer::start();
+        let find_mock = mock_server.mock(|when, then| {
+            when.method(GET)
+                .path("/v4/screens")
+                .query_param("select", "id")
+                .query_param("name", "eq.Lobby & Hall #2");
+            then.status(200).json_body(json!([{"id": "screen-4"}]));
+        });
+
+        let config = Config::new(mock_server.base_url());
+        let authentication = Authentication::new_with_config(config, "token");
+        let screen_command = ScreenCommand::new(authentication);
+        let results = screen_command.add_batch(&path).unwrap();
+
+        find_mock.assert();
+        assert_eq!(
+            results.value,
+            json!([{
+                "line": 2,
+                "pin": "444",
+                "name": "Lobby & Hall #2",
+                "screen_id": "screen-4",
+                "status": "exists",
+                "details": "",
+            }])
+        );
     }
 
     #[test]
@@ -157,7 +773,8 @@ mod tests {
         let mock_server = MockServer::start();
         mock_server.mock(|when, then| {
             when.method(DELETE)
-                .path("/v3/screens/test-id/")
+                .path("/v4/screens")
+                .query_param("id", "eq.test-id")
                 .header(
                     "user-agent",
                     format!("screenly-cli {}", env!("CARGO_PKG_VERSION")),
@@ -175,7 +792,7 @@ mod tests {
     #[test]
     fn test_format_screen_when_human_readable_output_is_set_should_return_correct_formatted_string()
     {
-        let screen = Screens::new(serde_json::from_str("[{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Renat's integrated wired NM\",\"is_enabled\":true,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\": 230728,\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}, {\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb6\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875d\",\"created_at\":\"2020-06-28T05:07:55+00:00\",\"name\":\"Not Renat's integrated wired NM\",\"is_enabled\":true,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2020-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":230728,\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}]").unwrap());
+        let screen = Screens::new(serde_json::from_str("[{\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb5\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"created_at\":\"2021-06-28T05:07:55+00:00\",\"name\":\"Renat's integrated wired NM\",\"is_enabled\":true,\"priority\":false,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2021-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\": 230728,\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}, {\"id\":\"017a5104-524b-33d8-8026-9087b59e7eb6\",\"team_id\":\"016343c2-82b8-0000-a121-e30f1035875d\",\"created_at\":\"2020-06-28T05:07:55+00:00\",\"name\":\"Not Renat's integrated wired NM\",\"is_enabled\":true,\"priority\":false,\"coords\":[55.22931, 48.90429],\"last_ping\":\"2020-08-25T06:17:20.728+00:00\",\"last_ip\":null,\"local_ip\":\"192.168.1.146\",\"mac\":\"b8:27:eb:d6:83:6f\",\"last_screenshot_time\":\"2021-08-25T06:09:04.399+00:00\",\"uptime\":230728,\"load_avg\":\"0.14\",\"signal_strength\":null,\"interface\":\"eth0\",\"debug\":false,\"location\":\"Kamsko-Ust'inskiy rayon, Russia\",\"team\":\"016343c2-82b8-0000-a121-e30f1035875e\",\"timezone\":\"Europe/Moscow\",\"type\":\"hardware\",\"hostname\":\"srly-4shnfrdc5cd2p0p\",\"ws_open\":false,\"status\":\"Offline\",\"last_screenshot\":\"https://us-assets.screenlyapp.com/01CD1W50NR000A28F31W83B1TY/screenshots/01F98G8MJB6FC809MGGYTSWZNN/5267668e6db35498e61b83d4c702dbe8\",\"in_sync\":false,\"software_version\":\"Screenly 2 Player\",\"hardware_version\":\"Raspberry Pi 3B\",\"config\":{\"hdmi_mode\": 34, \"hdmi_boost\": 2, \"hdmi_drive\": 0, \"hdmi_group\": 0, \"verify_ssl\": true, \"audio_output\": \"hdmi\", \"hdmi_timings\": \"\", \"overscan_top\": 0, \"overscan_left\": 0, \"use_composite\": false, \"display_rotate\": 0, \"overscan_right\": 0, \"overscan_scale\": 0, \"overscan_bottom\": 0, \"disable_overscan\": 0, \"shuffle_playlist\": false, \"framebuffer_width\": 0, \"use_composite_pal\": false, \"framebuffer_height\": 0, \"hdmi_force_hotplug\": true, \"use_composite_ntsc\": false, \"hdmi_pixel_encoding\": 0, \"play_history_enabled\": false}}]").unwrap());
         println!("{}", screen.format(OutputType::HumanReadable));
         let expected_output =
             "+--------------------------------------+---------------------------------+---------+----------+------------------+---------+-------------------------------+--------+\n\
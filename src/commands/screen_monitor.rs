@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::api::screen::Screen;
+use crate::authentication::AuthenticationError;
+use crate::commands::screen::ScreenCommand;
+use crate::commands::CommandError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenEventKind {
+    StoppedPinging,
+    ResumedPinging,
+    OutOfSync,
+    BackInSync,
+    Rebooted,
+    Removed,
+}
+
+impl fmt::Display for ScreenEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            ScreenEventKind::StoppedPinging => "stopped pinging",
+            ScreenEventKind::ResumedPinging => "resumed pinging",
+            ScreenEventKind::OutOfSync => "went out of sync",
+            ScreenEventKind::BackInSync => "is back in sync",
+            ScreenEventKind::Rebooted => "rebooted",
+            ScreenEventKind::Removed => "was removed",
+        };
+        write!(f, "{description}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ScreenEvent {
+    pub timestamp: String,
+    pub screen_id: String,
+    pub screen_name: String,
+    pub event: ScreenEventKind,
+}
+
+impl fmt::Display for ScreenEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({}) {}",
+            self.timestamp, self.screen_name, self.screen_id, self.event
+        )
+    }
+}
+
+#[derive(Debug)]
+struct ScreenState {
+    name: String,
+    stale: bool,
+    in_sync: bool,
+    uptime: Option<Duration>,
+}
+
+/// Tracks the health of screens between polls and reports transitions.
+///
+/// The first observation of a screen only reports it if it is already unhealthy,
+/// so that a freshly started monitor surfaces existing problems once.
+pub struct ScreenMonitor {
+    stale_after: Duration,
+    screens: HashMap<String, ScreenState>,
+}
+
+impl ScreenMonitor {
+    pub fn new(stale_after: Duration) -> Self {
+        Self {
+            stale_after,
+            screens: HashMap::new(),
+        }
+    }
+
+    /// Compares a `v4/screens` response with the previous one and returns the transitions.
+    /// Screens missing from the response are reported as removed.
+    pub fn observe(&mut self, screens: &[Screen]) -> Vec<ScreenEvent> {
+        let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let mut events = Vec::new();
+
+        let mut previous_screens = std::mem::take(&mut self.screens);
+        for screen in screens {
+            let id = screen.id.as_str();
+            let screen_name = screen.name.clone().unwrap_or("N/A".to_owned());
+            let state = ScreenState {
+                name: screen_name.clone(),
+                stale: screen.is_stale(self.stale_after),
+                in_sync: screen.in_sync.unwrap_or(false),
+                uptime: screen.uptime(),
+            };
+
+            let mut kinds = Vec::new();
+            match previous_screens.remove(id) {
+                Some(previous) => {
+                    if state.stale != previous.stale {
+                        kinds.push(if state.stale {
+                            ScreenEventKind::StoppedPinging
+                        } else {
+                            ScreenEventKind::ResumedPinging
+                        });
+                    }
+                    if state.in_sync != previous.in_sync {
+                        kinds.push(if state.in_sync {
+                            ScreenEventKind::BackInSync
+                        } else {
+                            ScreenEventKind::OutOfSync
+                        });
+                    }
+                    if let (Some(uptime), Some(previous_uptime)) = (state.uptime, previous.uptime) {
+                        if uptime < previous_uptime {
+                            kinds.push(ScreenEventKind::Rebooted);
+                        }
+                    }
+                }
+                None => {
+                    if state.stale {
+                        kinds.push(ScreenEventKind::StoppedPinging);
+                    }
+                    if !state.in_sync {
+                        kinds.push(ScreenEventKind::OutOfSync);
+                    }
+                }
+            }
+
+            events.extend(kinds.into_iter().map(|event| ScreenEvent {
+                timestamp: timestamp.clone(),
+                screen_id: id.to_owned(),
+                screen_name: screen_name.clone(),
+                event,
+            }));
+            self.screens.insert(id.to_owned(), state);
+        }
+
+        let mut removed: Vec<_> = previous_screens.into_iter().collect();
+        removed.sort_by(|a, b| a.0.cmp(&b.0));
+        events.extend(removed.into_iter().map(|(id, state)| ScreenEvent {
+            timestamp: timestamp.clone(),
+            screen_id: id,
+            screen_name: state.name,
+            event: ScreenEventKind::Removed,
+        }));
+
+        events
+    }
+}
+
+/// Fetches the screens to observe. A rejected token is reported as an authentication
+/// error rather than a response status, so the monitor stops instead of polling on.
+pub fn poll_screens(screen_command: &ScreenCommand) -> Result<Vec<Screen>, CommandError> {
+    match screen_command.list() {
+        Ok(screens) => Ok(screens.screens),
+        Err(CommandError::WrongResponseStatus(401 | 403)) => Err(CommandError::Authentication(
+            AuthenticationError::WrongCredentials,
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+/// Runs a local command for an event.
+///
+/// The event is passed as JSON on stdin and as `SCREENLY_EVENT`, `SCREENLY_SCREEN_ID`
+/// and `SCREENLY_SCREEN_NAME` environment variables.
+pub fn run_event_hook(command: &str, event: &ScreenEvent) -> Result<(), CommandError> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+
+    let mut child = Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .env("SCREENLY_EVENT", event.event.to_string())
+        .env("SCREENLY_SCREEN_ID", &event.screen_id)
+        .env("SCREENLY_SCREEN_NAME", &event.screen_name)
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The hook may exit without reading its input, so a broken pipe is not an error.
+        let _ = stdin.write_all(serde_json::to_string(event)?.as_bytes());
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(CommandError::HookFailed(
+            command.to_owned(),
+            status.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::Method::GET;
+    use httpmock::MockServer;
+    use serde_json::json;
+
+    use super::*;
+    use crate::authentication::{Authentication, Config};
+
+    fn screen(in_sync: bool, last_ping: chrono::DateTime<chrono::Utc>, uptime: u64) -> Vec<Screen> {
+        serde_json::from_value(json!([{
+            "id": "screen-1",
+            "name": "Lobby",
+            "in_sync": in_sync,
+            "last_ping": last_ping.to_rfc3339(),
+            "uptime": uptime,
+        }]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_observe_healthy_screen_first_time_should_not_report_anything() {
+        let mut monitor = ScreenMonitor::new(Duration::from_secs(600));
+        let events = monitor.observe(&screen(true, chrono::Utc::now(), 100));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_observe_unhealthy_screen_first_time_should_report_it() {
+        let mut monitor = ScreenMonitor::new(Duration::from_secs(600));
+        let last_ping = chrono::Utc::now() - chrono::Duration::hours(1);
+        let events = monitor.observe(&screen(false, last_ping, 100));
+        let kinds: Vec<_> = events.iter().map(|e| e.event).collect();
+        assert_eq!(
+            kinds,
+            vec![ScreenEventKind::StoppedPinging, ScreenEventKind::OutOfSync]
+        );
+        assert_eq!(events[0].screen_name, "Lobby");
+    }
+
+    #[test]
+    fn test_observe_should_report_transitions() {
+        let mut monitor = ScreenMonitor::new(Duration::from_secs(600));
+        monitor.observe(&screen(true, chrono::Utc::now(), 1000));
+
+        let last_ping = chrono::Utc::now() - chrono::Duration::minutes(11);
+        let events = monitor.observe(&screen(false, last_ping, 1000));
+        let kinds: Vec<_> = events.iter().map(|e| e.event).collect();
+        assert_eq!(
+            kinds,
+            vec![ScreenEventKind::StoppedPinging, ScreenEventKind::OutOfSync]
+        );
+
+        let events = monitor.observe(&screen(true, chrono::Utc::now(), 30));
+        let kinds: Vec<_> = events.iter().map(|e| e.event).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ScreenEventKind::ResumedPinging,
+                ScreenEventKind::BackInSync,
+                ScreenEventKind::Rebooted
+            ]
+        );
+
+        assert!(monitor
+            .observe(&screen(true, chrono::Utc::now(), 90))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_observe_should_report_removed_screens_once() {
+        let mut monitor = ScreenMonitor::new(Duration::from_secs(600));
+        monitor.observe(&screen(true, chrono::Utc::now(), 100));
+
+        let events = monitor.observe(&[]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, ScreenEventKind::Removed);
+        assert_eq!(events[0].screen_id, "screen-1");
+        assert_eq!(events[0].screen_name, "Lobby");
+
+        assert!(monitor.observe(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_poll_screens_when_token_is_rejected_should_return_authentication_error() {
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(GET).path("/v4/screens");
+            then.status(401);
+        });
+        let authentication =
+            Authentication::new_with_config(Config::new(mock_server.base_url()), "token");
+
+        let result = poll_screens(&ScreenCommand::new(authentication));
+
+        assert!(matches!(
+            result,
+            Err(CommandError::Authentication(
+                AuthenticationError::WrongCredentials
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_screen_event_should_serialize_to_snake_case() {
+        let event = ScreenEvent {
+            timestamp: "2024-01-01T00:00:00Z".to_owned(),
+            screen_id: "screen-1".to_owned(),
+            screen_name: "Lobby".to_owned(),
+            event: ScreenEventKind::StoppedPinging,
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "timestamp": "2024-01-01T00:00:00Z",
+                "screen_id": "screen-1",
+                "screen_name": "Lobby",
+                "event": "stopped_pinging"
+            })
+        );
+        assert_eq!(
+            event.to_string(),
+            "2024-01-01T00:00:00Z Lobby (screen-1) stopped pinging"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Client library for the [Screenly](https://www.screenly.io) API.
+//!
+//! This crate powers the `screenly` command line tool and its MCP server, and can be
+//! used directly from Rust. Library code never prints to stdout or terminates the
+//! process; every failure is returned as a [`CommandError`].
+//!
+//! The public API consists of:
+//!
+//! * [`Api`] — low-level, typed access to the REST endpoints.
+//! * [`Authentication`] and [`Config`] — credentials and the API endpoint to talk to.
+//! * The command types in [`commands`], such as [`EdgeAppCommand`], which implement
+//!   higher-level workflows like deploying an Edge App.
+//! * [`EdgeAppManifest`] and friends, the `screenly.yml` manifest types.
+//! * [`generate_signature`], the content signature used for asset deduplication.
+//!
+//! Everything else is considered an implementation detail of the CLI and may change
+//! in any release.
+//!
+//! [`Config`], [`AuthenticationError`] and [`CommandError`] are `#[non_exhaustive]`:
+//! new settings and error variants may be added in minor releases, so construct a
+//! [`Config`] with [`Config::new`] and match errors with a wildcard arm.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use screenly::commands::edge_app::app::DEFAULT_CHANNEL;
+//! use screenly::{Authentication, EdgeAppCommand, EdgeAppManifest};
+//!
+//! let authentication = Authentication::new()?;
+//! let client = authentication.client()?;
+//! let manifest = EdgeAppManifest::new(Path::new("screenly.yml"))?;
+//! let command = EdgeAppCommand::new(authentication);
+//! if let Some(deploy) = command.prepare_deploy(Some(".".to_owned()), Some(false))? {
+//!     // Files are uploaded concurrently on the caller's runtime.
+//!     tokio::runtime::Runtime::new()?.block_on(command.upload_files(&client, &deploy))?;
+//!     let revision = command.finish_deploy(&deploy, Some(DEFAULT_CHANNEL))?;
+//!     println!("Deployed {:?} as revision {revision}", manifest.id);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub mod api;
+pub mod authentication;
+pub mod commands;
+pub mod mcp;
+pub mod pb_signature;
+pub mod signature;
+
+pub use api::Api;
+pub use authentication::{Authentication, AuthenticationError, Config};
+pub use commands::edge_app::manifest::EdgeAppManifest;
+pub use commands::edge_app::EdgeAppCommand;
+pub use commands::CommandError;
+pub use signature::generate_signature;
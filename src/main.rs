@@ -1,10 +1,5 @@
-mod api;
-mod authentication;
 mod cli;
-mod commands;
-mod mcp;
-mod pb_signature;
-mod signature;
+mod cli_config;
 
 extern crate prettytable;
 
@@ -13,8 +8,6 @@ use std::env;
 use clap::Parser;
 use simple_logger::{init_with_env, SimpleLogger};
 
-use crate::authentication::{Authentication, AuthenticationError};
-
 fn main() {
     if env::var("RUST_LOG").is_ok() {
         init_with_env().unwrap();
@@ -197,11 +197,15 @@ pub struct ScreenlyMcpServer {
 impl ScreenlyMcpServer {
     /// Create a new ScreenlyMcpServer instance.
     pub fn new() -> Result<Self, crate::authentication::AuthenticationError> {
-        let auth = Authentication::new()?;
-        Ok(Self {
+        Ok(Self::with_authentication(Authentication::new()?))
+    }
+
+    /// Create a new ScreenlyMcpServer instance with the given credentials.
+    pub fn with_authentication(auth: Authentication) -> Self {
+        Self {
             auth: Arc::new(auth),
             tool_router: Self::tool_router(),
-        })
+        }
     }
 
     /// Run the MCP server on stdio transport.
@@ -224,16 +228,16 @@ impl ScreenlyMcpServer {
     // ============ SCREEN TOOLS ============
 
     #[tool(description = "List all screens with their status, hardware info, and sync state.")]
-    fn screen_list(&self) -> String {
-        match ScreenTools::list(&self.auth) {
+    async fn screen_list(&self) -> String {
+        match ScreenTools::list(&self.auth).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Get a screen by UUID.")]
-    fn screen_get(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
-        match ScreenTools::get(&self.auth, &uuid) {
+    async fn screen_get(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
+        match ScreenTools::get(&self.auth, &uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
@@ -242,34 +246,34 @@ impl ScreenlyMcpServer {
     // ============ ASSET TOOLS ============
 
     #[tool(description = "List all assets with their type, status, and metadata.")]
-    fn asset_list(&self) -> String {
-        match AssetTools::list(&self.auth) {
+    async fn asset_list(&self) -> String {
+        match AssetTools::list(&self.auth).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Get an asset by UUID.")]
-    fn asset_get(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
-        match AssetTools::get(&self.auth, &uuid) {
+    async fn asset_get(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
+        match AssetTools::get(&self.auth, &uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Create a new asset from a URL. Supports web pages, images, and videos.")]
-    fn asset_create(
+    async fn asset_create(
         &self,
         Parameters(AssetCreateParam { title, source_url }): Parameters<AssetCreateParam>,
     ) -> String {
-        match AssetTools::create(&self.auth, &title, &source_url) {
+        match AssetTools::create(&self.auth, &title, &source_url).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Update an asset's properties (title, js_injection, headers).")]
-    fn asset_update(
+    async fn asset_update(
         &self,
         Parameters(AssetUpdateParam {
             uuid,
@@ -278,15 +282,15 @@ impl ScreenlyMcpServer {
             headers,
         }): Parameters<AssetUpdateParam>,
     ) -> String {
-        match AssetTools::update(&self.auth, &uuid, title, js_injection, headers) {
+        match AssetTools::update(&self.auth, &uuid, title, js_injection, headers).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Delete an asset by UUID.")]
-    fn asset_delete(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
-        match AssetTools::delete(&self.auth, &uuid) {
+    async fn asset_delete(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
+        match AssetTools::delete(&self.auth, &uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
@@ -295,38 +299,41 @@ impl ScreenlyMcpServer {
     // ============ ASSET GROUP TOOLS ============
 
     #[tool(description = "List all asset groups (folders for organizing assets).")]
-    fn asset_group_list(&self) -> String {
-        match AssetGroupTools::list(&self.auth) {
+    async fn asset_group_list(&self) -> String {
+        match AssetGroupTools::list(&self.auth).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Create a new asset group.")]
-    fn asset_group_create(
+    async fn asset_group_create(
         &self,
         Parameters(TitleParam { title }): Parameters<TitleParam>,
     ) -> String {
-        match AssetGroupTools::create(&self.auth, &title) {
+        match AssetGroupTools::create(&self.auth, &title).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Update an asset group.")]
-    fn asset_group_update(
+    async fn asset_group_update(
         &self,
         Parameters(AssetGroupUpdateParam { uuid, title }): Parameters<AssetGroupUpdateParam>,
     ) -> String {
-        match AssetGroupTools::update(&self.auth, &uuid, &title) {
+        match AssetGroupTools::update(&self.auth, &uuid, &title).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Delete an asset group. WARNING: Also deletes all assets in the group.")]
-    fn asset_group_delete(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
-        match AssetGroupTools::delete(&self.auth, &uuid) {
+    async fn asset_group_delete(
+        &self,
+        Parameters(UuidParam { uuid }): Parameters<UuidParam>,
+    ) -> String {
+        match AssetGroupTools::delete(&self.auth, &uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
@@ -335,15 +342,15 @@ impl ScreenlyMcpServer {
     // ============ PLAYLIST TOOLS ============
 
     #[tool(description = "List all playlists.")]
-    fn playlist_list(&self) -> String {
-        match PlaylistTools::list(&self.auth) {
+    async fn playlist_list(&self) -> String {
+        match PlaylistTools::list(&self.auth).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Create a new playlist.")]
-    fn playlist_create(
+    async fn playlist_create(
         &self,
         Parameters(PlaylistCreateParam {
             title,
@@ -352,14 +359,14 @@ impl ScreenlyMcpServer {
             is_enabled,
         }): Parameters<PlaylistCreateParam>,
     ) -> String {
-        match PlaylistTools::create(&self.auth, &title, predicate, priority, is_enabled) {
+        match PlaylistTools::create(&self.auth, &title, predicate, priority, is_enabled).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Update a playlist.")]
-    fn playlist_update(
+    async fn playlist_update(
         &self,
         Parameters(PlaylistUpdateParam {
             uuid,
@@ -369,15 +376,19 @@ impl ScreenlyMcpServer {
             is_enabled,
         }): Parameters<PlaylistUpdateParam>,
     ) -> String {
-        match PlaylistTools::update(&self.auth, &uuid, title, predicate, priority, is_enabled) {
+        match PlaylistTools::update(&self.auth, &uuid, title, predicate, priority, is_enabled).await
+        {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Delete a playlist by UUID.")]
-    fn playlist_delete(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
-        match PlaylistTools::delete(&self.auth, &uuid) {
+    async fn playlist_delete(
+        &self,
+        Parameters(UuidParam { uuid }): Parameters<UuidParam>,
+    ) -> String {
+        match PlaylistTools::delete(&self.auth, &uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
@@ -386,15 +397,18 @@ impl ScreenlyMcpServer {
     // ============ PLAYLIST ITEM TOOLS ============
 
     #[tool(description = "List all items in a playlist.")]
-    fn playlist_item_list(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
-        match PlaylistItemTools::list(&self.auth, &uuid) {
+    async fn playlist_item_list(
+        &self,
+        Parameters(UuidParam { uuid }): Parameters<UuidParam>,
+    ) -> String {
+        match PlaylistItemTools::list(&self.auth, &uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Add an asset to a playlist.")]
-    fn playlist_item_create(
+    async fn playlist_item_create(
         &self,
         Parameters(PlaylistItemCreateParam {
             playlist_uuid,
@@ -404,6 +418,7 @@ impl ScreenlyMcpServer {
         }): Parameters<PlaylistItemCreateParam>,
     ) -> String {
         match PlaylistItemTools::create(&self.auth, &playlist_uuid, &asset_uuid, duration, position)
+            .await
         {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
@@ -411,7 +426,7 @@ impl ScreenlyMcpServer {
     }
 
     #[tool(description = "Update a playlist item (duration, position).")]
-    fn playlist_item_update(
+    async fn playlist_item_update(
         &self,
         Parameters(PlaylistItemUpdateParam {
             playlist_uuid,
@@ -421,6 +436,7 @@ impl ScreenlyMcpServer {
         }): Parameters<PlaylistItemUpdateParam>,
     ) -> String {
         match PlaylistItemTools::update(&self.auth, &playlist_uuid, &item_uuid, duration, position)
+            .await
         {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
@@ -428,14 +444,14 @@ impl ScreenlyMcpServer {
     }
 
     #[tool(description = "Remove an item from a playlist.")]
-    fn playlist_item_delete(
+    async fn playlist_item_delete(
         &self,
         Parameters(PlaylistItemDeleteParam {
             playlist_uuid,
             item_uuid,
         }): Parameters<PlaylistItemDeleteParam>,
     ) -> String {
-        match PlaylistItemTools::delete(&self.auth, &playlist_uuid, &item_uuid) {
+        match PlaylistItemTools::delete(&self.auth, &playlist_uuid, &item_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
@@ -444,91 +460,91 @@ impl ScreenlyMcpServer {
     // ============ LABEL TOOLS ============
 
     #[tool(description = "List all labels. Labels group screens and target playlists.")]
-    fn label_list(&self) -> String {
-        match LabelTools::list(&self.auth) {
+    async fn label_list(&self) -> String {
+        match LabelTools::list(&self.auth).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Create a new label.")]
-    fn label_create(&self, Parameters(NameParam { name }): Parameters<NameParam>) -> String {
-        match LabelTools::create(&self.auth, &name) {
+    async fn label_create(&self, Parameters(NameParam { name }): Parameters<NameParam>) -> String {
+        match LabelTools::create(&self.auth, &name).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Update a label.")]
-    fn label_update(
+    async fn label_update(
         &self,
         Parameters(LabelUpdateParam { uuid, name }): Parameters<LabelUpdateParam>,
     ) -> String {
-        match LabelTools::update(&self.auth, &uuid, &name) {
+        match LabelTools::update(&self.auth, &uuid, &name).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Delete a label.")]
-    fn label_delete(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
-        match LabelTools::delete(&self.auth, &uuid) {
+    async fn label_delete(&self, Parameters(UuidParam { uuid }): Parameters<UuidParam>) -> String {
+        match LabelTools::delete(&self.auth, &uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Attach a label to a screen.")]
-    fn label_link_screen(
+    async fn label_link_screen(
         &self,
         Parameters(LabelScreenParam {
             label_uuid,
             screen_uuid,
         }): Parameters<LabelScreenParam>,
     ) -> String {
-        match LabelTools::link_screen(&self.auth, &label_uuid, &screen_uuid) {
+        match LabelTools::link_screen(&self.auth, &label_uuid, &screen_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Remove a label from a screen.")]
-    fn label_unlink_screen(
+    async fn label_unlink_screen(
         &self,
         Parameters(LabelScreenParam {
             label_uuid,
             screen_uuid,
         }): Parameters<LabelScreenParam>,
     ) -> String {
-        match LabelTools::unlink_screen(&self.auth, &label_uuid, &screen_uuid) {
+        match LabelTools::unlink_screen(&self.auth, &label_uuid, &screen_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Attach a label to a playlist.")]
-    fn label_link_playlist(
+    async fn label_link_playlist(
         &self,
         Parameters(LabelPlaylistParam {
             label_uuid,
             playlist_uuid,
         }): Parameters<LabelPlaylistParam>,
     ) -> String {
-        match LabelTools::link_playlist(&self.auth, &label_uuid, &playlist_uuid) {
+        match LabelTools::link_playlist(&self.auth, &label_uuid, &playlist_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Remove a label from a playlist.")]
-    fn label_unlink_playlist(
+    async fn label_unlink_playlist(
         &self,
         Parameters(LabelPlaylistParam {
             label_uuid,
             playlist_uuid,
         }): Parameters<LabelPlaylistParam>,
     ) -> String {
-        match LabelTools::unlink_playlist(&self.auth, &label_uuid, &playlist_uuid) {
+        match LabelTools::unlink_playlist(&self.auth, &label_uuid, &playlist_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
@@ -537,36 +553,36 @@ impl ScreenlyMcpServer {
     // ============ SHARED PLAYLIST TOOLS ============
 
     #[tool(description = "List shared playlists.")]
-    fn shared_playlist_list(&self) -> String {
-        match SharedPlaylistTools::list(&self.auth) {
+    async fn shared_playlist_list(&self) -> String {
+        match SharedPlaylistTools::list(&self.auth).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Share a playlist with another team.")]
-    fn shared_playlist_create(
+    async fn shared_playlist_create(
         &self,
         Parameters(SharedPlaylistParam {
             playlist_uuid,
             team_uuid,
         }): Parameters<SharedPlaylistParam>,
     ) -> String {
-        match SharedPlaylistTools::create(&self.auth, &playlist_uuid, &team_uuid) {
+        match SharedPlaylistTools::create(&self.auth, &playlist_uuid, &team_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "Unshare a playlist from a team.")]
-    fn shared_playlist_delete(
+    async fn shared_playlist_delete(
         &self,
         Parameters(SharedPlaylistParam {
             playlist_uuid,
             team_uuid,
         }): Parameters<SharedPlaylistParam>,
     ) -> String {
-        match SharedPlaylistTools::delete(&self.auth, &playlist_uuid, &team_uuid) {
+        match SharedPlaylistTools::delete(&self.auth, &playlist_uuid, &team_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
@@ -575,30 +591,30 @@ impl ScreenlyMcpServer {
     // ============ EDGE APP TOOLS ============
 
     #[tool(description = "List all Edge Apps.")]
-    fn edge_app_list(&self) -> String {
-        match EdgeAppTools::list(&self.auth) {
+    async fn edge_app_list(&self) -> String {
+        match EdgeAppTools::list(&self.auth).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "List settings for an Edge App.")]
-    fn edge_app_list_settings(
+    async fn edge_app_list_settings(
         &self,
         Parameters(AppUuidParam { app_uuid }): Parameters<AppUuidParam>,
     ) -> String {
-        match EdgeAppTools::list_settings(&self.auth, &app_uuid) {
+        match EdgeAppTools::list_settings(&self.auth, &app_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
     }
 
     #[tool(description = "List instances of an Edge App.")]
-    fn edge_app_list_instances(
+    async fn edge_app_list_instances(
         &self,
         Parameters(AppUuidParam { app_uuid }): Parameters<AppUuidParam>,
     ) -> String {
-        match EdgeAppTools::list_instances(&self.auth, &app_uuid) {
+        match EdgeAppTools::list_instances(&self.auth, &app_uuid).await {
             Ok(result) => result,
             Err(e) => json!({"error": e}).to_string(),
         }
@@ -2,6 +2,8 @@
 
 use serde_json::json;
 
+use crate::api::asset::Asset;
+use crate::api::{eq_filter, V4};
 use crate::authentication::Authentication;
 use crate::commands;
 
@@ -10,32 +12,45 @@ pub struct AssetTools;
 
 impl AssetTools {
     /// List all assets (excluding edge-app-file type).
-    pub fn list(auth: &Authentication) -> Result<String, String> {
-        let result = commands::get(auth, "v4/assets?type=neq.edge-app-file")
+    pub async fn list(auth: &Authentication) -> Result<String, String> {
+        let result = commands::get_async(auth, &format!("{V4}/assets?type=neq.edge-app-file"))
+            .await
             .map_err(|e| format!("Failed to list assets: {}", e))?;
 
+        let result: Vec<Asset> =
+            serde_json::from_value(result).map_err(|e| format!("Failed to parse assets: {}", e))?;
+
         serde_json::to_string_pretty(&result)
             .map_err(|e| format!("Failed to serialize response: {}", e))
     }
 
     /// Get an asset by UUID.
-    pub fn get(auth: &Authentication, uuid: &str) -> Result<String, String> {
-        let endpoint = format!("v4/assets?id=eq.{}", uuid);
-        let result =
-            commands::get(auth, &endpoint).map_err(|e| format!("Failed to get asset: {}", e))?;
+    pub async fn get(auth: &Authentication, uuid: &str) -> Result<String, String> {
+        let endpoint = format!("{V4}/assets?{}", eq_filter("id", uuid));
+        let result = commands::get_async(auth, &endpoint)
+            .await
+            .map_err(|e| format!("Failed to get asset: {}", e))?;
+
+        let result: Vec<Asset> =
+            serde_json::from_value(result).map_err(|e| format!("Failed to parse asset: {}", e))?;
 
         serde_json::to_string_pretty(&result)
             .map_err(|e| format!("Failed to serialize response: {}", e))
     }
 
     /// Create a new asset from a URL.
-    pub fn create(auth: &Authentication, title: &str, source_url: &str) -> Result<String, String> {
+    pub async fn create(
+        auth: &Authentication,
+        title: &str,
+        source_url: &str,
+    ) -> Result<String, String> {
         let payload = json!({
             "title": title,
             "source_url": source_url,
         });
 
-        let result = commands::post(auth, "v4/assets", &payload)
+        let result = commands::post_async(auth, &format!("{V4}/assets"), &payload)
+            .await
             .map_err(|e| format!("Failed to create asset: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -43,7 +58,7 @@ impl AssetTools {
     }
 
     /// Update an asset.
-    pub fn update(
+    pub async fn update(
         auth: &Authentication,
         uuid: &str,
         title: Option<String>,
@@ -71,8 +86,9 @@ impl AssetTools {
             return Err("No fields to update".to_string());
         }
 
-        let endpoint = format!("v4/assets?id=eq.{}", uuid);
-        let result = commands::patch(auth, &endpoint, &serde_json::Value::Object(payload))
+        let endpoint = format!("{V4}/assets?{}", eq_filter("id", uuid));
+        let result = commands::patch_async(auth, &endpoint, &serde_json::Value::Object(payload))
+            .await
             .map_err(|e| format!("Failed to update asset: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -80,9 +96,11 @@ impl AssetTools {
     }
 
     /// Delete an asset.
-    pub fn delete(auth: &Authentication, uuid: &str) -> Result<String, String> {
-        let endpoint = format!("v4/assets?id=eq.{}", uuid);
-        commands::delete(auth, &endpoint).map_err(|e| format!("Failed to delete asset: {}", e))?;
+    pub async fn delete(auth: &Authentication, uuid: &str) -> Result<String, String> {
+        let endpoint = format!("{V4}/assets?{}", eq_filter("id", uuid));
+        commands::delete_async(auth, &endpoint)
+            .await
+            .map_err(|e| format!("Failed to delete asset: {}", e))?;
 
         Ok(json!({"status": "deleted", "id": uuid}).to_string())
     }
@@ -2,6 +2,7 @@
 
 use serde_json::json;
 
+use crate::api::{eq_filter, V4};
 use crate::authentication::Authentication;
 use crate::commands;
 
@@ -10,8 +11,9 @@ pub struct AssetGroupTools;
 
 impl AssetGroupTools {
     /// List all asset groups.
-    pub fn list(auth: &Authentication) -> Result<String, String> {
-        let result = commands::get(auth, "v4/asset-groups")
+    pub async fn list(auth: &Authentication) -> Result<String, String> {
+        let result = commands::get_async(auth, &format!("{V4}/asset-groups"))
+            .await
             .map_err(|e| format!("Failed to list asset groups: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -19,12 +21,13 @@ impl AssetGroupTools {
     }
 
     /// Create a new asset group.
-    pub fn create(auth: &Authentication, title: &str) -> Result<String, String> {
+    pub async fn create(auth: &Authentication, title: &str) -> Result<String, String> {
         let payload = json!({
             "title": title,
         });
 
-        let result = commands::post(auth, "v4/asset-groups", &payload)
+        let result = commands::post_async(auth, &format!("{V4}/asset-groups"), &payload)
+            .await
             .map_err(|e| format!("Failed to create asset group: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -32,13 +35,14 @@ impl AssetGroupTools {
     }
 
     /// Update an asset group.
-    pub fn update(auth: &Authentication, uuid: &str, title: &str) -> Result<String, String> {
+    pub async fn update(auth: &Authentication, uuid: &str, title: &str) -> Result<String, String> {
         let payload = json!({
             "title": title,
         });
 
-        let endpoint = format!("v4/asset-groups?id=eq.{}", uuid);
-        let result = commands::patch(auth, &endpoint, &payload)
+        let endpoint = format!("{V4}/asset-groups?{}", eq_filter("id", uuid));
+        let result = commands::patch_async(auth, &endpoint, &payload)
+            .await
             .map_err(|e| format!("Failed to update asset group: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -46,9 +50,10 @@ impl AssetGroupTools {
     }
 
     /// Delete an asset group (and all assets within it).
-    pub fn delete(auth: &Authentication, uuid: &str) -> Result<String, String> {
-        let endpoint = format!("v4/asset-groups?id=eq.{}", uuid);
-        commands::delete(auth, &endpoint)
+    pub async fn delete(auth: &Authentication, uuid: &str) -> Result<String, String> {
+        let endpoint = format!("{V4}/asset-groups?{}", eq_filter("id", uuid));
+        commands::delete_async(auth, &endpoint)
+            .await
             .map_err(|e| format!("Failed to delete asset group: {}", e))?;
 
         Ok(json!({"status": "deleted", "id": uuid}).to_string())
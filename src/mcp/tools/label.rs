@@ -2,6 +2,7 @@
 
 use serde_json::json;
 
+use crate::api::{eq_filter, V4};
 use crate::authentication::Authentication;
 use crate::commands;
 
@@ -10,8 +11,9 @@ pub struct LabelTools;
 
 impl LabelTools {
     /// List all labels.
-    pub fn list(auth: &Authentication) -> Result<String, String> {
-        let result = commands::get(auth, "v4/labels")
+    pub async fn list(auth: &Authentication) -> Result<String, String> {
+        let result = commands::get_async(auth, &format!("{V4}/labels"))
+            .await
             .map_err(|e| format!("Failed to list labels: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -19,12 +21,13 @@ impl LabelTools {
     }
 
     /// Create a new label.
-    pub fn create(auth: &Authentication, name: &str) -> Result<String, String> {
+    pub async fn create(auth: &Authentication, name: &str) -> Result<String, String> {
         let payload = json!({
             "name": name,
         });
 
-        let result = commands::post(auth, "v4/labels", &payload)
+        let result = commands::post_async(auth, &format!("{V4}/labels"), &payload)
+            .await
             .map_err(|e| format!("Failed to create label: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -32,13 +35,14 @@ impl LabelTools {
     }
 
     /// Update a label.
-    pub fn update(auth: &Authentication, uuid: &str, name: &str) -> Result<String, String> {
+    pub async fn update(auth: &Authentication, uuid: &str, name: &str) -> Result<String, String> {
         let payload = json!({
             "name": name,
         });
 
-        let endpoint = format!("v4/labels?id=eq.{}", uuid);
-        let result = commands::patch(auth, &endpoint, &payload)
+        let endpoint = format!("{V4}/labels?{}", eq_filter("id", uuid));
+        let result = commands::patch_async(auth, &endpoint, &payload)
+            .await
             .map_err(|e| format!("Failed to update label: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -46,15 +50,17 @@ impl LabelTools {
     }
 
     /// Delete a label.
-    pub fn delete(auth: &Authentication, uuid: &str) -> Result<String, String> {
-        let endpoint = format!("v4/labels?id=eq.{}", uuid);
-        commands::delete(auth, &endpoint).map_err(|e| format!("Failed to delete label: {}", e))?;
+    pub async fn delete(auth: &Authentication, uuid: &str) -> Result<String, String> {
+        let endpoint = format!("{V4}/labels?{}", eq_filter("id", uuid));
+        commands::delete_async(auth, &endpoint)
+            .await
+            .map_err(|e| format!("Failed to delete label: {}", e))?;
 
         Ok(json!({"status": "deleted", "id": uuid}).to_string())
     }
 
     /// Attach a label to a screen.
-    pub fn link_screen(
+    pub async fn link_screen(
         auth: &Authentication,
         label_uuid: &str,
         screen_uuid: &str,
@@ -64,7 +70,8 @@ impl LabelTools {
             "screen_id": screen_uuid,
         });
 
-        let result = commands::post(auth, "v4/labels/screens", &payload)
+        let result = commands::post_async(auth, &format!("{V4}/labels/screens"), &payload)
+            .await
             .map_err(|e| format!("Failed to link label to screen: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -72,16 +79,18 @@ impl LabelTools {
     }
 
     /// Remove a label from a screen.
-    pub fn unlink_screen(
+    pub async fn unlink_screen(
         auth: &Authentication,
         label_uuid: &str,
         screen_uuid: &str,
     ) -> Result<String, String> {
         let endpoint = format!(
-            "v4/labels/screens?label_id=eq.{}&screen_id=eq.{}",
-            label_uuid, screen_uuid
+            "{V4}/labels/screens?{}&{}",
+            eq_filter("label_id", label_uuid),
+            eq_filter("screen_id", screen_uuid)
         );
-        commands::delete(auth, &endpoint)
+        commands::delete_async(auth, &endpoint)
+            .await
             .map_err(|e| format!("Failed to unlink label from screen: {}", e))?;
 
         Ok(json!({
@@ -93,7 +102,7 @@ impl LabelTools {
     }
 
     /// Attach a label to a playlist.
-    pub fn link_playlist(
+    pub async fn link_playlist(
         auth: &Authentication,
         label_uuid: &str,
         playlist_uuid: &str,
@@ -103,7 +112,8 @@ impl LabelTools {
             "playlist_id": playlist_uuid,
         });
 
-        let result = commands::post(auth, "v4/labels/playlists", &payload)
+        let result = commands::post_async(auth, &format!("{V4}/labels/playlists"), &payload)
+            .await
             .map_err(|e| format!("Failed to link label to playlist: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -111,16 +121,18 @@ impl LabelTools {
     }
 
     /// Remove a label from a playlist.
-    pub fn unlink_playlist(
+    pub async fn unlink_playlist(
         auth: &Authentication,
         label_uuid: &str,
         playlist_uuid: &str,
     ) -> Result<String, String> {
         let endpoint = format!(
-            "v4/labels/playlists?label_id=eq.{}&playlist_id=eq.{}",
-            label_uuid, playlist_uuid
+            "{V4}/labels/playlists?{}&{}",
+            eq_filter("label_id", label_uuid),
+            eq_filter("playlist_id", playlist_uuid)
         );
-        commands::delete(auth, &endpoint)
+        commands::delete_async(auth, &endpoint)
+            .await
             .map_err(|e| format!("Failed to unlink label from playlist: {}", e))?;
 
         Ok(json!({
@@ -2,6 +2,8 @@
 
 use serde_json::json;
 
+use crate::api::playlist::Playlist;
+use crate::api::{eq_filter, V4};
 use crate::authentication::Authentication;
 use crate::commands;
 
@@ -10,16 +12,20 @@ pub struct PlaylistTools;
 
 impl PlaylistTools {
     /// List all playlists.
-    pub fn list(auth: &Authentication) -> Result<String, String> {
-        let result = commands::get(auth, "v4/playlists")
+    pub async fn list(auth: &Authentication) -> Result<String, String> {
+        let result = commands::get_async(auth, &format!("{V4}/playlists"))
+            .await
             .map_err(|e| format!("Failed to list playlists: {}", e))?;
 
+        let result: Vec<Playlist> = serde_json::from_value(result)
+            .map_err(|e| format!("Failed to parse playlists: {}", e))?;
+
         serde_json::to_string_pretty(&result)
             .map_err(|e| format!("Failed to serialize response: {}", e))
     }
 
     /// Create a new playlist.
-    pub fn create(
+    pub async fn create(
         auth: &Authentication,
         title: &str,
         predicate: Option<String>,
@@ -34,7 +40,8 @@ impl PlaylistTools {
             "transitions": true
         });
 
-        let result = commands::post(auth, "v4/playlists", &payload)
+        let result = commands::post_async(auth, &format!("{V4}/playlists"), &payload)
+            .await
             .map_err(|e| format!("Failed to create playlist: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -42,7 +49,7 @@ impl PlaylistTools {
     }
 
     /// Update a playlist.
-    pub fn update(
+    pub async fn update(
         auth: &Authentication,
         uuid: &str,
         title: Option<String>,
@@ -72,8 +79,9 @@ impl PlaylistTools {
             return Err("No fields to update".to_string());
         }
 
-        let endpoint = format!("v4/playlists?id=eq.{}", uuid);
-        let result = commands::patch(auth, &endpoint, &serde_json::Value::Object(payload))
+        let endpoint = format!("{V4}/playlists?{}", eq_filter("id", uuid));
+        let result = commands::patch_async(auth, &endpoint, &serde_json::Value::Object(payload))
+            .await
             .map_err(|e| format!("Failed to update playlist: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -81,9 +89,10 @@ impl PlaylistTools {
     }
 
     /// Delete a playlist.
-    pub fn delete(auth: &Authentication, uuid: &str) -> Result<String, String> {
-        let endpoint = format!("v4/playlists?id=eq.{}", uuid);
-        commands::delete(auth, &endpoint)
+    pub async fn delete(auth: &Authentication, uuid: &str) -> Result<String, String> {
+        let endpoint = format!("{V4}/playlists?{}", eq_filter("id", uuid));
+        commands::delete_async(auth, &endpoint)
+            .await
             .map_err(|e| format!("Failed to delete playlist: {}", e))?;
 
         Ok(json!({"status": "deleted", "id": uuid}).to_string())
@@ -2,6 +2,8 @@
 
 use serde_json::json;
 
+use crate::api::playlist::PlaylistItem;
+use crate::api::{eq_filter, V4};
 use crate::authentication::Authentication;
 use crate::commands;
 
@@ -15,20 +17,24 @@ pub struct PlaylistItemTools;
 
 impl PlaylistItemTools {
     /// List all items in a playlist.
-    pub fn list(auth: &Authentication, playlist_uuid: &str) -> Result<String, String> {
+    pub async fn list(auth: &Authentication, playlist_uuid: &str) -> Result<String, String> {
         let endpoint = format!(
-            "v4/playlist-items?playlist_id=eq.{}&order=position.asc",
-            playlist_uuid
+            "{V4}/playlist-items?{}&order=position.asc",
+            eq_filter("playlist_id", playlist_uuid)
         );
-        let result = commands::get(auth, &endpoint)
+        let result = commands::get_async(auth, &endpoint)
+            .await
             .map_err(|e| format!("Failed to list playlist items: {}", e))?;
 
+        let result: Vec<PlaylistItem> = serde_json::from_value(result)
+            .map_err(|e| format!("Failed to parse playlist items: {}", e))?;
+
         serde_json::to_string_pretty(&result)
             .map_err(|e| format!("Failed to serialize response: {}", e))
     }
 
     /// Add an asset to a playlist.
-    pub fn create(
+    pub async fn create(
         auth: &Authentication,
         playlist_uuid: &str,
         asset_uuid: &str,
@@ -41,10 +47,11 @@ impl PlaylistItemTools {
         } else {
             // Get the highest position in the playlist
             let endpoint = format!(
-                "v4/playlist-items?select=position&playlist_id=eq.{}&order=position.desc&limit=1",
-                playlist_uuid
+                "{V4}/playlist-items?select=position&{}&order=position.desc&limit=1",
+                eq_filter("playlist_id", playlist_uuid)
             );
-            let result = commands::get(auth, &endpoint)
+            let result = commands::get_async(auth, &endpoint)
+                .await
                 .map_err(|e| format!("Failed to get playlist positions: {}", e))?;
 
             if let Some(items) = result.as_array() {
@@ -67,7 +74,8 @@ impl PlaylistItemTools {
             "position": final_position
         }]);
 
-        let result = commands::post(auth, "v4/playlist-items", &payload)
+        let result = commands::post_async(auth, &format!("{V4}/playlist-items"), &payload)
+            .await
             .map_err(|e| format!("Failed to create playlist item: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -75,7 +83,7 @@ impl PlaylistItemTools {
     }
 
     /// Update a playlist item.
-    pub fn update(
+    pub async fn update(
         auth: &Authentication,
         playlist_uuid: &str,
         item_uuid: &str,
@@ -97,10 +105,12 @@ impl PlaylistItemTools {
         }
 
         let endpoint = format!(
-            "v4/playlist-items?playlist_id=eq.{}&id=eq.{}",
-            playlist_uuid, item_uuid
+            "{V4}/playlist-items?{}&{}",
+            eq_filter("playlist_id", playlist_uuid),
+            eq_filter("id", item_uuid)
         );
-        let result = commands::patch(auth, &endpoint, &serde_json::Value::Object(payload))
+        let result = commands::patch_async(auth, &endpoint, &serde_json::Value::Object(payload))
+            .await
             .map_err(|e| format!("Failed to update playlist item: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -108,16 +118,18 @@ impl PlaylistItemTools {
     }
 
     /// Remove an item from a playlist.
-    pub fn delete(
+    pub async fn delete(
         auth: &Authentication,
         playlist_uuid: &str,
         item_uuid: &str,
     ) -> Result<String, String> {
         let endpoint = format!(
-            "v4/playlist-items?playlist_id=eq.{}&id=eq.{}",
-            playlist_uuid, item_uuid
+            "{V4}/playlist-items?{}&{}",
+            eq_filter("playlist_id", playlist_uuid),
+            eq_filter("id", item_uuid)
         );
-        commands::delete(auth, &endpoint)
+        commands::delete_async(auth, &endpoint)
+            .await
             .map_err(|e| format!("Failed to delete playlist item: {}", e))?;
 
         Ok(
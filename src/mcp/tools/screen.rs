@@ -1,5 +1,7 @@
 //! Screen-related MCP tools.
 
+use crate::api::screen::Screen;
+use crate::api::{deserialize_rows, eq_filter, V4};
 use crate::authentication::Authentication;
 use crate::commands;
 
@@ -8,21 +10,28 @@ pub struct ScreenTools;
 
 impl ScreenTools {
     /// List all screens.
-    pub fn list(auth: &Authentication) -> Result<String, String> {
-        let result = commands::get(auth, "v4/screens")
+    pub async fn list(auth: &Authentication) -> Result<String, String> {
+        let result = commands::get_async(auth, &format!("{V4}/screens"))
+            .await
             .map_err(|e| format!("Failed to list screens: {}", e))?;
 
-        serde_json::to_string_pretty(&result)
-            .map_err(|e| format!("Failed to serialize response: {}", e))
+        serialize_screens(result)
     }
 
     /// Get a screen by UUID.
-    pub fn get(auth: &Authentication, uuid: &str) -> Result<String, String> {
-        let endpoint = format!("v4/screens?id=eq.{}", uuid);
-        let result =
-            commands::get(auth, &endpoint).map_err(|e| format!("Failed to get screen: {}", e))?;
+    pub async fn get(auth: &Authentication, uuid: &str) -> Result<String, String> {
+        let result = commands::get_async(auth, &format!("{V4}/screens?{}", eq_filter("id", uuid)))
+            .await
+            .map_err(|e| format!("Failed to get screen: {}", e))?;
 
-        serde_json::to_string_pretty(&result)
-            .map_err(|e| format!("Failed to serialize response: {}", e))
+        serialize_screens(result)
     }
 }
+
+fn serialize_screens(value: serde_json::Value) -> Result<String, String> {
+    let screens: Vec<Screen> =
+        deserialize_rows(value).map_err(|e| format!("Failed to parse screens: {}", e))?;
+
+    serde_json::to_string_pretty(&screens)
+        .map_err(|e| format!("Failed to serialize response: {}", e))
+}
@@ -2,6 +2,7 @@
 
 use serde_json::json;
 
+use crate::api::{eq_filter, V4};
 use crate::authentication::Authentication;
 use crate::commands;
 
@@ -10,8 +11,9 @@ pub struct SharedPlaylistTools;
 
 impl SharedPlaylistTools {
     /// List all shared playlists.
-    pub fn list(auth: &Authentication) -> Result<String, String> {
-        let result = commands::get(auth, "v4/playlists/shared")
+    pub async fn list(auth: &Authentication) -> Result<String, String> {
+        let result = commands::get_async(auth, &format!("{V4}/playlists/shared"))
+            .await
             .map_err(|e| format!("Failed to list shared playlists: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -19,7 +21,7 @@ impl SharedPlaylistTools {
     }
 
     /// Share a playlist with a team.
-    pub fn create(
+    pub async fn create(
         auth: &Authentication,
         playlist_uuid: &str,
         team_uuid: &str,
@@ -29,7 +31,8 @@ impl SharedPlaylistTools {
             "team_id": team_uuid,
         });
 
-        let result = commands::post(auth, "v4/playlists/shared", &payload)
+        let result = commands::post_async(auth, &format!("{V4}/playlists/shared"), &payload)
+            .await
             .map_err(|e| format!("Failed to share playlist: {}", e))?;
 
         serde_json::to_string_pretty(&result)
@@ -37,16 +40,18 @@ impl SharedPlaylistTools {
     }
 
     /// Unshare a playlist from a team.
-    pub fn delete(
+    pub async fn delete(
         auth: &Authentication,
         playlist_uuid: &str,
         team_uuid: &str,
     ) -> Result<String, String> {
         let endpoint = format!(
-            "v4/playlists/shared?playlist_id=eq.{}&team_id=eq.{}",
-            playlist_uuid, team_uuid
+            "{V4}/playlists/shared?{}&{}",
+            eq_filter("playlist_id", playlist_uuid),
+            eq_filter("team_id", team_uuid)
         );
-        commands::delete(auth, &endpoint)
+        commands::delete_async(auth, &endpoint)
+            .await
             .map_err(|e| format!("Failed to unshare playlist: {}", e))?;
 
         Ok(json!({
@@ -19,6 +19,10 @@ pub fn sig_to_hex(signature: &Signature) -> String {
 
 const CHUNK_SIZE: usize = 512 * 1024;
 
+/// Computes the content signature of a file.
+///
+/// The signature holds a SHA-1 hash per 512 KiB chunk plus SHA-256 hashes of the whole
+/// file and of the chunk hashes. Use [`sig_to_hex`] to get the form the API expects.
 pub fn generate_signature(path: &Path) -> Result<Signature, CommandError> {
     let mut file = File::open(path)?;
     let mut fullhash = Sha256::new();